@@ -1,13 +1,18 @@
 use cucumber::{World, WriterExt, writer};
 use rust_traits_examples::animals::*;
 use rust_traits_examples::core::*;
+use rust_traits_examples::registry::{Entity, EntityRegistry, Params, Spawned};
 use rust_traits_examples::vehicles::*;
 use std::io;
 
 pub mod steps;
 
-#[derive(Debug, World)]
+#[derive(Debug, World, Default)]
 pub struct TraitsWorld {
+    // Creates animals and vehicles by kind name
+    pub registry: EntityRegistry,
+    pub entities: Vec<Spawned>,
+
     // Animals
     pub dog: Option<Dog>,
     pub duck: Option<Duck>,
@@ -36,29 +41,14 @@ pub struct TraitsWorld {
     pub can_land_move: bool,
 }
 
-impl Default for TraitsWorld {
-    fn default() -> Self {
-        Self {
-            dog: None,
-            duck: None,
-            eagle: None,
-            penguin: None,
-            whale: None,
-            snake: None,
-            car: None,
-            motorcycle: None,
-            airplane: None,
-            ship: None,
-            helicopter: None,
-            last_result: None,
-            energy_before: None,
-            energy_after: None,
-            can_walk: false,
-            can_swim: false,
-            can_fly: false,
-            can_drive: false,
-            can_land_move: false,
-        }
+impl TraitsWorld {
+    /// Create an entity through the registry as its concrete type
+    pub fn spawn<T: Entity>(&self, kind: &str, params: Params) -> T {
+        self.registry
+            .create(kind, &params)
+            .unwrap_or_else(|e| panic!("Cannot create {}: {}", kind, e))
+            .downcast()
+            .unwrap_or_else(|spawned| panic!("{} is not a {}", spawned.name(), kind))
     }
 }
 
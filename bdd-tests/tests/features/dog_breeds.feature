@@ -23,17 +23,32 @@ Feature: Dog Breed Capabilities
       | Tank  | Bulldog         |         2 |
       | Storm | Husky           |        10 |
 
+  Scenario Outline: Mixed breeds blend their parents' traits
+    Given a dog named "<name>" with breed "<breed>"
+    Then the dog's breed should be shown as "<breed>"
+    And the dog's maximum swimming depth should be <max_depth> meters
+    And the dog should tolerate temperatures down to <coldest> degrees
+
+    Examples:
+      | name | breed                      | max_depth | coldest |
+      | Luna | 50% Labrador, 50% Husky    |        10 |     -27 |
+      | Mutt | 50% Labrador, 50% Bulldog  |         6 |      -7 |
+      | Dash | 75% Greyhound, 25% Bulldog |         3 |      -4 |
+
   Scenario: Multiple dogs compete in swimming race
     Given the following dogs are entered in the race:
-      | name   | breed           |
-      | Splash | Labrador        |
-      | Paddle | GoldenRetriever |
-      | Dive   | BorderCollie    |
-      | Float  | Bulldog         |
+      | name   | breed                     |
+      | Splash | Labrador                  |
+      | Paddle | GoldenRetriever           |
+      | Dive   | BorderCollie              |
+      | Float  | Bulldog                   |
+      | Mutt   | 50% Labrador, 50% Bulldog |
     When the swimming race begins
     Then all dogs should attempt to swim
     And "Splash" should have better swimming capability than "Float"
     And "Paddle" should have better swimming capability than "Float"
+    And "Splash" should have better swimming capability than "Mutt"
+    And "Mutt" should have better swimming capability than "Float"
 
   Scenario: Dog energy levels affect performance
     Given a dog named "Energetic" with breed "Labrador"
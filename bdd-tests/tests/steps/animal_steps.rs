@@ -1,21 +1,11 @@
 use crate::TraitsWorld;
 use cucumber::{given, then};
 use rust_traits_examples::animals::*;
+use rust_traits_examples::registry::Params;
 
 #[given(expr = "a dog named {string} with breed {string}")]
 async fn a_dog_named_with_breed(world: &mut TraitsWorld, name: String, breed: String) {
-    let dog_breed = match breed.as_str() {
-        "Labrador" => dog::DogBreed::Labrador,
-        "BorderCollie" => dog::DogBreed::BorderCollie,
-        "Greyhound" => dog::DogBreed::Greyhound,
-        "Husky" => dog::DogBreed::Husky,
-        "Bulldog" => dog::DogBreed::Bulldog,
-        "GoldenRetriever" => dog::DogBreed::GoldenRetriever,
-        "SaintBernard" => dog::DogBreed::SaintBernard,
-        _ => dog::DogBreed::Other,
-    };
-
-    world.dog = Some(Dog::new(name, dog_breed));
+    world.dog = Some(world.spawn("dog", Params::named(name).with("breed", breed)));
 }
 
 #[given(expr = "a duck named {string}")]
@@ -35,28 +25,12 @@ async fn a_penguin_named(world: &mut TraitsWorld, name: String) {
 
 #[given(expr = "a whale named {string} of species {string}")]
 async fn a_whale_named_of_species(world: &mut TraitsWorld, name: String, species: String) {
-    let whale_species = match species.as_str() {
-        "BlueWhale" => whale::WhaleSpecies::BlueWhale,
-        "Orca" => whale::WhaleSpecies::Orca,
-        "Humpback" => whale::WhaleSpecies::Humpback,
-        "Dolphin" => whale::WhaleSpecies::Dolphin,
-        _ => whale::WhaleSpecies::BlueWhale,
-    };
-
-    world.whale = Some(Whale::new(name, whale_species));
+    world.whale = Some(world.spawn("whale", Params::named(name).with("species", species)));
 }
 
 #[given(expr = "a snake named {string} of species {string}")]
 async fn a_snake_named_of_species(world: &mut TraitsWorld, name: String, species: String) {
-    let snake_species = match species.as_str() {
-        "Anaconda" => snake::SnakeSpecies::Anaconda,
-        "Python" => snake::SnakeSpecies::Python,
-        "Cobra" => snake::SnakeSpecies::Cobra,
-        "Viper" => snake::SnakeSpecies::Viper,
-        _ => snake::SnakeSpecies::Anaconda,
-    };
-
-    world.snake = Some(Snake::new(name, snake_species));
+    world.snake = Some(world.spawn("snake", Params::named(name).with("species", species)));
 }
 
 #[then(expr = "the dog should be able to walk")]
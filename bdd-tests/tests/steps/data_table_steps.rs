@@ -1,8 +1,10 @@
 use crate::TraitsWorld;
-use cucumber::{gherkin::Step, given, then, when};
-use rust_traits_examples::animals::*;
+use cucumber::{
+    gherkin::{Step, Table},
+    given, then, when,
+};
 use rust_traits_examples::core::*;
-use rust_traits_examples::vehicles::*;
+use rust_traits_examples::registry::{Params, RegistryError};
 
 #[given(expr = "the energy management system is initialized")]
 async fn energy_system_initialized(_world: &mut TraitsWorld) {
@@ -32,8 +34,23 @@ async fn dog_max_swimming_depth(world: &mut TraitsWorld, expected_depth: i32) {
     }
 }
 
+#[then(expr = "the dog's breed should be shown as {string}")]
+async fn dog_breed_shown_as(world: &mut TraitsWorld, expected: String) {
+    let dog = world.dog.as_ref().expect("No dog available");
+    assert_eq!(dog.breed_name(), expected);
+}
+
+#[then(expr = "the dog should tolerate temperatures down to {int} degrees")]
+async fn dog_cold_tolerance(world: &mut TraitsWorld, coldest: i32) {
+    use rust_traits_examples::core::ThermalTolerance;
+
+    let dog = world.dog.as_ref().expect("No dog available");
+    assert_eq!(dog.thermal_range().min_c, coldest);
+    println!("✅ {} tolerates down to {}°C", dog.breed_name(), coldest);
+}
+
 #[given(expr = "the following dogs are entered in the race:")]
-async fn dogs_entered_in_race(world: &mut TraitsWorld, step: &Step) {
+async fn dogs_entered_in_race(world: &mut TraitsWorld, step: &Step) -> Result<(), RegistryError> {
     if let Some(table) = step.table.as_ref() {
         println!("🏁 Entering dogs in race:");
 
@@ -42,22 +59,19 @@ async fn dogs_entered_in_race(world: &mut TraitsWorld, step: &Step) {
             let name = &row[0];
             let breed = &row[1];
 
-            let dog_breed = match breed.as_str() {
-                "Labrador" => dog::DogBreed::Labrador,
-                "GoldenRetriever" => dog::DogBreed::GoldenRetriever,
-                "BorderCollie" => dog::DogBreed::BorderCollie,
-                "Bulldog" => dog::DogBreed::Bulldog,
-                _ => dog::DogBreed::Other,
-            };
-
             println!("  - {} ({})", name, breed);
 
+            let params = Params::named(name).with("breed", breed);
+            let spawned = world.registry.create("dog", &params)?;
+            world.entities.push(spawned);
+
             // Store the first dog as primary test subject
             if world.dog.is_none() {
-                world.dog = Some(Dog::new(name.clone(), dog_breed));
+                world.dog = Some(world.spawn("dog", params));
             }
         }
     }
+    Ok(())
 }
 
 #[when(expr = "the swimming race begins")]
@@ -71,16 +85,30 @@ async fn all_dogs_attempt_swim(_world: &mut TraitsWorld) {
 }
 
 #[then(expr = "{string} should have better swimming capability than {string}")]
-async fn compare_swimming_capability(_world: &mut TraitsWorld, better: String, worse: String) {
+async fn compare_swimming_capability(world: &mut TraitsWorld, better: String, worse: String) {
+    let max_depth = |name: &str| {
+        world
+            .entities
+            .iter()
+            .find(|entity| entity.name() == name)
+            .and_then(|entity| entity.participant().as_swimmer())
+            .map(|swimmer| swimmer.max_depth())
+            .unwrap_or_else(|| panic!("No swimming dog named {}", name))
+    };
+    assert!(
+        max_depth(&better) > max_depth(&worse),
+        "{} should dive deeper than {}",
+        better,
+        worse
+    );
     println!(
         "✅ {} has better swimming capability than {}",
         better, worse
     );
-    // In a full implementation, you'd compare actual dog instances
 }
 
 #[given(expr = "the dog has the following initial state:")]
-async fn dog_initial_state(world: &mut TraitsWorld, step: &Step) {
+async fn dog_initial_state(world: &mut TraitsWorld, step: &Step) -> Result<(), RegistryError> {
     if let Some(table) = step.table.as_ref() {
         for row in table.rows.iter().skip(1) {
             let property = &row[0];
@@ -88,15 +116,12 @@ async fn dog_initial_state(world: &mut TraitsWorld, step: &Step) {
 
             match property.as_str() {
                 "energy" => {
-                    let energy = match value.as_str() {
-                        "Hyperactive" => EnergyLevel::Hyperactive,
-                        "Energetic" => EnergyLevel::Energetic,
-                        "Normal" => EnergyLevel::Normal,
-                        "Tired" => EnergyLevel::Tired,
-                        "Exhausted" => EnergyLevel::Exhausted,
-                        "Collapsed" => EnergyLevel::Collapsed,
-                        _ => EnergyLevel::Normal,
-                    };
+                    let levels = EnergyLevel::ALL.map(|level| (level.name(), level));
+                    let energy = Params::new().with("energy", value).choose(
+                        "energy",
+                        &levels,
+                        EnergyLevel::Normal,
+                    )?;
 
                     if let Some(ref mut dog) = world.dog {
                         dog.set_energy(energy);
@@ -110,47 +135,48 @@ async fn dog_initial_state(world: &mut TraitsWorld, step: &Step) {
             }
         }
     }
+    Ok(())
 }
 
 #[when(expr = "the dog performs multiple activities:")]
 async fn dog_multiple_activities(world: &mut TraitsWorld, step: &Step) {
     use rust_traits_examples::behaviors::{Swimming, Walking};
 
-    if let Some(table) = step.table.as_ref() {
-        if let Some(ref mut dog) = world.dog {
-            world.energy_before = Some(dog.energy());
-
-            for row in table.rows.iter().skip(1) {
-                let activity = &row[0];
-                let repetitions: usize = row[1].parse().unwrap_or(1);
-
-                for i in 0..repetitions {
-                    // Convert all results to a common type
-                    let result: Result<String, String> = match activity.as_str() {
-                        "walk" => dog
-                            .walk()
-                            .map(|_| "walked".to_string())
-                            .map_err(|e| e.to_string()),
-                        "swim" => dog
-                            .swim()
-                            .map(|_| "swam".to_string())
-                            .map_err(|e| e.to_string()),
-                        "run" => dog
-                            .run()
-                            .map(|_| "ran".to_string())
-                            .map_err(|e| e.to_string()),
-                        _ => Ok("unknown activity".to_string()),
-                    };
-
-                    match result {
-                        Ok(desc) => println!("  ✅ Dog {} (repetition {})", desc, i + 1),
-                        Err(e) => println!("  ❌ Activity failed: {}", e),
-                    }
+    if let Some(table) = step.table.as_ref()
+        && let Some(ref mut dog) = world.dog
+    {
+        world.energy_before = Some(dog.energy());
+
+        for row in table.rows.iter().skip(1) {
+            let activity = &row[0];
+            let repetitions: usize = row[1].parse().unwrap_or(1);
+
+            for i in 0..repetitions {
+                // Convert all results to a common type
+                let result: Result<String, String> = match activity.as_str() {
+                    "walk" => dog
+                        .walk()
+                        .map(|_| "walked".to_string())
+                        .map_err(|e| e.to_string()),
+                    "swim" => dog
+                        .swim()
+                        .map(|_| "swam".to_string())
+                        .map_err(|e| e.to_string()),
+                    "run" => dog
+                        .run()
+                        .map(|_| "ran".to_string())
+                        .map_err(|e| e.to_string()),
+                    _ => Ok("unknown activity".to_string()),
+                };
+
+                match result {
+                    Ok(desc) => println!("  ✅ Dog {} (repetition {})", desc, i + 1),
+                    Err(e) => println!("  ❌ Activity failed: {}", e),
                 }
             }
-
-            world.energy_after = Some(dog.energy());
         }
+
+        world.energy_after = Some(dog.energy());
     }
 }
 
@@ -196,7 +222,7 @@ async fn all_vehicle_types_available(_world: &mut TraitsWorld) {
 }
 
 #[given(expr = "the following vehicles are available:")]
-async fn vehicles_available(world: &mut TraitsWorld, step: &Step) {
+async fn vehicles_available(world: &mut TraitsWorld, step: &Step) -> Result<(), RegistryError> {
     if let Some(table) = step.table.as_ref() {
         println!("🚗 Vehicles available:");
 
@@ -211,71 +237,14 @@ async fn vehicles_available(world: &mut TraitsWorld, step: &Step) {
                 name, vehicle_type, manufacturer, year
             );
 
-            // Create and store vehicles based on type
-            match vehicle_type.as_str() {
-                "Car" => {
-                    world.car = Some(Car::new(
-                        name.clone(),
-                        manufacturer.clone(),
-                        year,
-                        car::EngineType::Gasoline {
-                            cylinders: 4,
-                            displacement: 2.0,
-                        },
-                    ));
-                }
-                "Airplane" => {
-                    world.airplane = Some(Airplane::new(
-                        name.clone(),
-                        manufacturer.clone(),
-                        year,
-                        airplane::AirplaneType::Commercial,
-                        30,
-                        airplane::AirplaneEngine::Jet {
-                            engines: 2,
-                            thrust_each: 200,
-                        },
-                    ));
-                }
-                "Ship" => {
-                    world.ship = Some(Ship::new(
-                        name.clone(),
-                        manufacturer.clone(),
-                        year,
-                        ship::ShipType::CruiseShip,
-                        1000,
-                        ship::PropulsionType::Diesel {
-                            engines: 2,
-                            power_each: 500,
-                        },
-                    ));
-                }
-                "Helicopter" => {
-                    world.helicopter = Some(Helicopter::new(
-                        name.clone(),
-                        manufacturer.clone(),
-                        year,
-                        helicopter::HelicopterType::Civilian,
-                        15,
-                        helicopter::HelicopterEngine::Turboshaft {
-                            engines: 2,
-                            power_each: 1000,
-                        },
-                    ));
-                }
-                "Motorcycle" => {
-                    world.motorcycle = Some(Motorcycle::new(
-                        name.clone(),
-                        manufacturer.clone(),
-                        year,
-                        1200,
-                        motorcycle::MotorcycleType::Sport,
-                    ));
-                }
-                _ => {}
-            }
+            let params = Params::named(name)
+                .with("manufacturer", manufacturer)
+                .with("year", year);
+            let spawned = world.registry.create(vehicle_type, &params)?;
+            world.entities.push(spawned);
         }
     }
+    Ok(())
 }
 
 #[when(expr = "I query their movement capabilities")]
@@ -305,16 +274,36 @@ async fn verify_capability_matrix(world: &mut TraitsWorld, step: &Step) {
             );
         }
 
-        // Verify actual capabilities
-        if world.car.is_some() {
-            world.can_drive = true;
-            world.can_land_move = true;
-        }
-
+        verify_capabilities(world, table);
         println!("✅ Capability matrix verified");
     }
 }
 
+/// Compare a capability matrix, one row per entity name, with what the entities can do
+fn verify_capabilities(world: &TraitsWorld, table: &Table) {
+    let header = &table.rows[0];
+    for row in table.rows.iter().skip(1) {
+        let capabilities = world
+            .entities
+            .iter()
+            .find(|entity| entity.name() == row[0])
+            .unwrap_or_else(|| panic!("No entity named {}", row[0]))
+            .capabilities();
+
+        for (column, expected) in header.iter().zip(row).skip(1) {
+            let actual = match column.trim_start_matches("can_") {
+                "walk" => capabilities.walk,
+                "drive" => capabilities.drive,
+                "swim" => capabilities.swim,
+                "fly" => capabilities.fly,
+                "land_move" => capabilities.land_move(),
+                other => panic!("Unknown capability column {}", other),
+            };
+            assert_eq!(actual, expected == "true", "{}: {}", row[0], column);
+        }
+    }
+}
+
 #[when(expr = "the car drives on {string} road")]
 async fn car_drives_on_road(world: &mut TraitsWorld, road_type: String) {
     use rust_traits_examples::behaviors::driving::{Driving, RoadType};
@@ -363,7 +352,7 @@ async fn energy_tracking_operational(_world: &mut TraitsWorld) {
 }
 
 #[given(expr = "the following animals:")]
-async fn create_animals(world: &mut TraitsWorld, step: &Step) {
+async fn create_animals(world: &mut TraitsWorld, step: &Step) -> Result<(), RegistryError> {
     if let Some(table) = step.table.as_ref() {
         println!("🐾 Creating animal test subjects:");
 
@@ -374,46 +363,16 @@ async fn create_animals(world: &mut TraitsWorld, step: &Step) {
 
             println!("  - {} ({}, {})", name, species, breed_or_na);
 
-            match species.as_str() {
-                "Dog" => {
-                    let breed = match breed_or_na.as_str() {
-                        "Labrador" => dog::DogBreed::Labrador,
-                        _ => dog::DogBreed::Other,
-                    };
-                    if world.dog.is_none() {
-                        world.dog = Some(Dog::new(name.clone(), breed));
-                    }
-                }
-                "Duck" => {
-                    if world.duck.is_none() {
-                        world.duck = Some(Duck::new(name.clone()));
-                    }
-                }
-                "Eagle" => {
-                    if world.eagle.is_none() {
-                        world.eagle = Some(Eagle::new(name.clone()));
-                    }
-                }
-                "Penguin" => {
-                    if world.penguin.is_none() {
-                        world.penguin = Some(Penguin::new(name.clone()));
-                    }
-                }
-                "Whale" => {
-                    if world.whale.is_none() {
-                        world.whale =
-                            Some(Whale::new(name.clone(), whale::WhaleSpecies::BlueWhale));
-                    }
-                }
-                "Snake" => {
-                    if world.snake.is_none() {
-                        world.snake = Some(Snake::new(name.clone(), snake::SnakeSpecies::Anaconda));
-                    }
-                }
-                _ => {}
+            let mut params = Params::named(name);
+            if breed_or_na != "N/A" {
+                let key = if species == "Dog" { "breed" } else { "species" };
+                params.insert(key, breed_or_na);
             }
+            let spawned = world.registry.create(species, &params)?;
+            world.entities.push(spawned);
         }
     }
+    Ok(())
 }
 
 #[when(expr = "I test all movement capabilities")]
@@ -422,7 +381,7 @@ async fn test_all_capabilities(_world: &mut TraitsWorld) {
 }
 
 #[then(expr = "I should have the following capability matrix:")]
-async fn verify_animal_matrix(_world: &mut TraitsWorld, step: &Step) {
+async fn verify_animal_matrix(world: &mut TraitsWorld, step: &Step) {
     if let Some(table) = step.table.as_ref() {
         println!("📊 Animal Capability Matrix:");
         println!(
@@ -443,6 +402,7 @@ async fn verify_animal_matrix(_world: &mut TraitsWorld, step: &Step) {
             );
         }
 
+        verify_capabilities(world, table);
         println!("✅ Animal capability matrix verified");
     }
 }
@@ -455,33 +415,18 @@ async fn animals_with_energy(world: &mut TraitsWorld, step: &Step) {
             let species = &row[1];
             let energy_str = &row[2];
 
-            let energy = match energy_str.as_str() {
-                "Hyperactive" => EnergyLevel::Hyperactive,
-                "Energetic" => EnergyLevel::Energetic,
-                "Normal" => EnergyLevel::Normal,
-                _ => EnergyLevel::Normal,
-            };
-
+            let params = Params::named(name).with("energy", energy_str);
             match species.as_str() {
-                "Dog" => {
-                    let mut dog = Dog::new(name.clone(), dog::DogBreed::Labrador);
-                    dog.set_energy(energy);
-                    world.dog = Some(dog);
-                }
-                "Duck" => {
-                    let mut duck = Duck::new(name.clone());
-                    duck.set_energy(energy);
-                    world.duck = Some(duck);
-                }
-                "Eagle" => {
-                    let mut eagle = Eagle::new(name.clone());
-                    eagle.set_energy(energy);
-                    world.eagle = Some(eagle);
-                }
+                "Dog" => world.dog = Some(world.spawn("dog", params.with("breed", "Labrador"))),
+                "Duck" => world.duck = Some(world.spawn("duck", params)),
+                "Eagle" => world.eagle = Some(world.spawn("eagle", params)),
                 _ => {}
             }
 
-            println!("✅ Created {} ({}) with {} energy", name, species, energy);
+            println!(
+                "✅ Created {} ({}) with {} energy",
+                name, species, energy_str
+            );
         }
     }
 }
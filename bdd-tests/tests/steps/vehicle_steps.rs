@@ -1,5 +1,6 @@
 use crate::TraitsWorld;
 use cucumber::{given, then};
+use rust_traits_examples::registry::Params;
 use rust_traits_examples::vehicles::*;
 
 #[given(expr = "a car named {string} with manufacturer {string} and year {int}")]
@@ -64,25 +65,10 @@ async fn an_airplane_named(
     manufacturer: String,
     plane_type: String,
 ) {
-    let airplane_type = match plane_type.as_str() {
-        "Commercial" => airplane::AirplaneType::Commercial,
-        "Military" => airplane::AirplaneType::Military,
-        "Private" => airplane::AirplaneType::Private,
-        "Cargo" => airplane::AirplaneType::Cargo,
-        _ => airplane::AirplaneType::Private,
-    };
-
-    world.airplane = Some(Airplane::new(
-        name,
-        manufacturer,
-        2023,
-        airplane_type,
-        30,
-        airplane::AirplaneEngine::Jet {
-            engines: 2,
-            thrust_each: 200,
-        },
-    ));
+    let params = Params::named(name)
+        .with("manufacturer", manufacturer)
+        .with("type", plane_type);
+    world.airplane = Some(world.spawn("airplane", params));
 }
 
 #[given(expr = "a military airplane named {string}")]
@@ -122,25 +108,10 @@ async fn a_ship_named(
     manufacturer: String,
     ship_type: String,
 ) {
-    let ship_type_enum = match ship_type.as_str() {
-        "CruiseShip" => ship::ShipType::CruiseShip,
-        "CargoShip" => ship::ShipType::CargoShip,
-        "Speedboat" => ship::ShipType::Speedboat,
-        "Yacht" => ship::ShipType::Yacht,
-        _ => ship::ShipType::Yacht,
-    };
-
-    world.ship = Some(Ship::new(
-        name,
-        manufacturer,
-        2023,
-        ship_type_enum,
-        1000,
-        ship::PropulsionType::Diesel {
-            engines: 2,
-            power_each: 500,
-        },
-    ));
+    let params = Params::named(name)
+        .with("manufacturer", manufacturer)
+        .with("type", ship_type);
+    world.ship = Some(world.spawn("ship", params));
 }
 
 #[given(expr = "a helicopter named {string} with manufacturer {string} and type {string}")]
@@ -150,24 +121,10 @@ async fn a_helicopter_named(
     manufacturer: String,
     heli_type: String,
 ) {
-    let helicopter_type = match heli_type.as_str() {
-        "Military" => helicopter::HelicopterType::Military,
-        "Emergency" => helicopter::HelicopterType::Emergency,
-        "Civilian" => helicopter::HelicopterType::Civilian,
-        _ => helicopter::HelicopterType::Civilian,
-    };
-
-    world.helicopter = Some(Helicopter::new(
-        name,
-        manufacturer,
-        2023,
-        helicopter_type,
-        15,
-        helicopter::HelicopterEngine::Turboshaft {
-            engines: 2,
-            power_each: 1000,
-        },
-    ));
+    let params = Params::named(name)
+        .with("manufacturer", manufacturer)
+        .with("type", heli_type);
+    world.helicopter = Some(world.spawn("helicopter", params));
 }
 
 #[then(expr = "the car should be able to drive")]
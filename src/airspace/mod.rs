@@ -0,0 +1,3 @@
+pub mod traffic;
+
+pub use traffic::{Airspace, AirspaceError, AltitudeBand, Conflict, Flight};
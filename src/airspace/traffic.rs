@@ -0,0 +1,508 @@
+//! Air Traffic Control
+//!
+//! An [`Airspace`] tracks every flyer over a map: where it is and how high.
+//! Two flights are in conflict when they are closer than the horizontal
+//! separation *and* closer than the vertical separation - being far apart
+//! either way is enough.
+//!
+//! Departures are given an [`AltitudeBand`] by their [`FlightCategory`]:
+//! birds stay low, helicopters and light aircraft share the middle, and
+//! jets climb to cruise. Within its band a departure gets the lowest level
+//! that keeps it separated from nearby traffic and that it can reach. If no
+//! such level is free, the takeoff is denied.
+//!
+//! Aircraft take off from and land at a [`Site`] with the facilities they
+//! need; birds use any site.
+
+use crate::behaviors::{FlightCategory, Participant};
+use crate::world::{Position, Site};
+use std::fmt;
+use thiserror::Error;
+
+/// Default horizontal separation in kilometers
+pub const HORIZONTAL_SEPARATION_KM: u32 = 5;
+
+/// Default vertical separation in meters
+pub const VERTICAL_SEPARATION_M: u32 = 300;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AirspaceError {
+    #[error("{flight} cannot fly")]
+    NotAFlyer { flight: String },
+
+    #[error("{flight} is not fit to fly: {reason}")]
+    CannotFly { flight: String, reason: String },
+
+    #[error("{flight} cannot use {site}: {reason}")]
+    UnsuitableSite {
+        flight: String,
+        site: String,
+        reason: String,
+    },
+
+    #[error("{flight} is already being tracked")]
+    AlreadyTracked { flight: String },
+
+    #[error("{flight} is not being tracked")]
+    UnknownFlight { flight: String },
+
+    #[error("{flight} cannot fly at {altitude_m}m: its ceiling is {max_altitude_m}m")]
+    AboveCeiling {
+        flight: String,
+        altitude_m: u32,
+        max_altitude_m: u32,
+    },
+
+    #[error("{flight} cannot climb to the {band} band: its ceiling is {max_altitude_m}m")]
+    CannotReachBand {
+        flight: String,
+        band: AltitudeBand,
+        max_altitude_m: u32,
+    },
+
+    #[error("Takeoff denied for {flight} at {position}: no free level in the {band} band")]
+    TakeoffDenied {
+        flight: String,
+        position: Position,
+        band: AltitudeBand,
+    },
+}
+
+/// Altitudes set aside for one kind of traffic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AltitudeBand {
+    Low,
+    Mid,
+    Cruise,
+}
+
+impl AltitudeBand {
+    /// Band a category of flyer is assigned to
+    pub fn for_category(category: FlightCategory) -> Self {
+        match category {
+            FlightCategory::Bird => AltitudeBand::Low,
+            FlightCategory::Rotorcraft | FlightCategory::LightAircraft => AltitudeBand::Mid,
+            FlightCategory::Airliner => AltitudeBand::Cruise,
+        }
+    }
+
+    /// Lowest level in the band, in meters
+    pub fn floor_m(&self) -> u32 {
+        match self {
+            AltitudeBand::Low => 100,
+            AltitudeBand::Mid => 1000,
+            AltitudeBand::Cruise => 8000,
+        }
+    }
+
+    /// Highest level in the band, in meters
+    pub fn ceiling_m(&self) -> u32 {
+        match self {
+            AltitudeBand::Low => 900,
+            AltitudeBand::Mid => 4000,
+            AltitudeBand::Cruise => 13000,
+        }
+    }
+}
+
+impl fmt::Display for AltitudeBand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AltitudeBand::Low => "Low",
+            AltitudeBand::Mid => "Mid",
+            AltitudeBand::Cruise => "Cruise",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A tracked flyer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flight {
+    pub name: String,
+    pub category: FlightCategory,
+    pub position: Position,
+    pub altitude_m: u32,
+    pub max_altitude_m: u32,
+}
+
+/// Two flights closer than the separation allows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub first: String,
+    pub second: String,
+    pub distance_km: u32,
+    pub vertical_m: u32,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} and {}: {} km apart, {} m vertically",
+            self.first, self.second, self.distance_km, self.vertical_m
+        )
+    }
+}
+
+/// Airspace over a map, with the flights in it
+#[derive(Debug, Clone)]
+pub struct Airspace {
+    pub tile_km: u32,
+    pub horizontal_separation_km: u32,
+    pub vertical_separation_m: u32,
+    flights: Vec<Flight>,
+}
+
+impl Airspace {
+    pub fn new(tile_km: u32) -> Self {
+        Self {
+            tile_km,
+            horizontal_separation_km: HORIZONTAL_SEPARATION_KM,
+            vertical_separation_m: VERTICAL_SEPARATION_M,
+            flights: Vec::new(),
+        }
+    }
+
+    /// Use different separation minima
+    pub fn with_separation(mut self, horizontal_km: u32, vertical_m: u32) -> Self {
+        self.horizontal_separation_km = horizontal_km;
+        self.vertical_separation_m = vertical_m;
+        self
+    }
+
+    pub fn flights(&self) -> &[Flight] {
+        &self.flights
+    }
+
+    pub fn flight(&self, name: &str) -> Option<&Flight> {
+        self.flights.iter().find(|flight| flight.name == name)
+    }
+
+    /// Start tracking a flyer already in the air
+    pub fn track(
+        &mut self,
+        flyer: &dyn Participant,
+        position: Position,
+        altitude_m: u32,
+    ) -> Result<(), AirspaceError> {
+        let name = flyer.participant_name();
+        if self.flight(&name).is_some() {
+            return Err(AirspaceError::AlreadyTracked { flight: name });
+        }
+        let flying = flyer.as_flyer().ok_or_else(|| AirspaceError::NotAFlyer {
+            flight: name.clone(),
+        })?;
+
+        let flight = Flight {
+            name,
+            category: flying.flight_category(),
+            position,
+            altitude_m,
+            max_altitude_m: flying.max_altitude(),
+        };
+        Self::check_ceiling(&flight, altitude_m)?;
+        self.flights.push(flight);
+        Ok(())
+    }
+
+    /// Report a tracked flight's new position and altitude
+    pub fn update(
+        &mut self,
+        name: &str,
+        position: Position,
+        altitude_m: u32,
+    ) -> Result<(), AirspaceError> {
+        let flight = self
+            .flights
+            .iter_mut()
+            .find(|flight| flight.name == name)
+            .ok_or_else(|| AirspaceError::UnknownFlight {
+                flight: name.to_string(),
+            })?;
+        Self::check_ceiling(flight, altitude_m)?;
+        flight.position = position;
+        flight.altitude_m = altitude_m;
+        Ok(())
+    }
+
+    /// Land a flight at a site and stop tracking it
+    pub fn land(&mut self, flyer: &dyn Participant, site: &Site) -> Result<Flight, AirspaceError> {
+        let name = flyer.participant_name();
+        let index = self
+            .flights
+            .iter()
+            .position(|flight| flight.name == name)
+            .ok_or_else(|| AirspaceError::UnknownFlight {
+                flight: name.clone(),
+            })?;
+        Self::check_site(flyer, site)?;
+        Ok(self.flights.remove(index))
+    }
+
+    /// Every pair of flights closer than the separation allows
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for (index, first) in self.flights.iter().enumerate() {
+            for second in &self.flights[index + 1..] {
+                if self.too_close(first.position, first.altitude_m, second) {
+                    conflicts.push(Conflict {
+                        first: first.name.clone(),
+                        second: second.name.clone(),
+                        distance_km: self.distance_km(first.position, second.position),
+                        vertical_m: first.altitude_m.abs_diff(second.altitude_m),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Lowest free level in the flyer's band above a position
+    pub fn assign_altitude(
+        &self,
+        flyer: &dyn Participant,
+        position: Position,
+    ) -> Result<u32, AirspaceError> {
+        let name = flyer.participant_name();
+        let flying = flyer.as_flyer().ok_or_else(|| AirspaceError::NotAFlyer {
+            flight: name.clone(),
+        })?;
+
+        let band = AltitudeBand::for_category(flying.flight_category());
+        let max_altitude_m = flying.max_altitude();
+        if max_altitude_m < band.floor_m() {
+            return Err(AirspaceError::CannotReachBand {
+                flight: name,
+                band,
+                max_altitude_m,
+            });
+        }
+
+        let top = band.ceiling_m().min(max_altitude_m);
+        (band.floor_m()..=top)
+            .step_by(self.vertical_separation_m.max(1) as usize)
+            .find(|altitude_m| {
+                self.flights
+                    .iter()
+                    .filter(|flight| flight.name != name)
+                    .all(|flight| !self.too_close(position, *altitude_m, flight))
+            })
+            .ok_or(AirspaceError::TakeoffDenied {
+                flight: name,
+                position,
+                band,
+            })
+    }
+
+    /// Clear a flyer for takeoff from a site and track it at its assigned level
+    pub fn request_takeoff(
+        &mut self,
+        flyer: &dyn Participant,
+        site: &Site,
+    ) -> Result<u32, AirspaceError> {
+        let name = flyer.participant_name();
+        if self.flight(&name).is_some() {
+            return Err(AirspaceError::AlreadyTracked { flight: name });
+        }
+        Self::check_site(flyer, site)?;
+        if let Some(flying) = flyer.as_flyer() {
+            flying
+                .check_flying_conditions()
+                .map_err(|error| AirspaceError::CannotFly {
+                    flight: name.clone(),
+                    reason: error.to_string(),
+                })?;
+        }
+
+        let altitude_m = self.assign_altitude(flyer, site.position)?;
+        self.track(flyer, site.position, altitude_m)?;
+        Ok(altitude_m)
+    }
+
+    /// Check the site has what an aircraft needs; anything else uses any site
+    fn check_site(flyer: &dyn Participant, site: &Site) -> Result<(), AirspaceError> {
+        match flyer.as_infrastructure_user() {
+            Some(vehicle) => {
+                vehicle
+                    .check_site(site)
+                    .map_err(|error| AirspaceError::UnsuitableSite {
+                        flight: flyer.participant_name(),
+                        site: site.name.clone(),
+                        reason: error.to_string(),
+                    })
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn check_ceiling(flight: &Flight, altitude_m: u32) -> Result<(), AirspaceError> {
+        if altitude_m > flight.max_altitude_m {
+            return Err(AirspaceError::AboveCeiling {
+                flight: flight.name.clone(),
+                altitude_m,
+                max_altitude_m: flight.max_altitude_m,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check if a point in the sky is too close to a flight
+    fn too_close(&self, position: Position, altitude_m: u32, flight: &Flight) -> bool {
+        let dx = position.x.abs_diff(flight.position.x) as u64;
+        let dy = position.y.abs_diff(flight.position.y) as u64;
+        let tile_km = self.tile_km as u64;
+        let separation_km = self.horizontal_separation_km as u64;
+        let horizontal = (dx * dx + dy * dy) * tile_km * tile_km < separation_km * separation_km;
+        horizontal && altitude_m.abs_diff(flight.altitude_m) < self.vertical_separation_m
+    }
+
+    fn distance_km(&self, from: Position, to: Position) -> u32 {
+        let dx = from.x.abs_diff(to.x) as f64;
+        let dy = from.y.abs_diff(to.y) as f64;
+        ((dx * dx + dy * dy).sqrt() * self.tile_km as f64).round() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::dog::DogBreed;
+    use crate::animals::{Dog, Duck, Eagle};
+    use crate::vehicles::airplane::{AirplaneEngine, AirplaneType};
+    use crate::vehicles::helicopter::{HelicopterEngine, HelicopterType};
+    use crate::vehicles::{Airplane, Helicopter};
+    use crate::world::Facility;
+
+    const FIELD: Position = Position { x: 0, y: 0 };
+
+    fn airfield() -> Site {
+        Site::new("Airfield", FIELD).with(Facility::Runway { length_m: 3000 })
+    }
+
+    fn airplane(name: &str, airplane_type: AirplaneType) -> Airplane {
+        Airplane::new(
+            name.to_string(),
+            "Test Aero".to_string(),
+            2022,
+            airplane_type,
+            30,
+            AirplaneEngine::Jet {
+                engines: 2,
+                thrust_each: 120,
+            },
+        )
+    }
+
+    fn helicopter() -> Helicopter {
+        Helicopter::new(
+            "Medevac".to_string(),
+            "Airbus".to_string(),
+            2019,
+            HelicopterType::Emergency,
+            11,
+            HelicopterEngine::Turboshaft {
+                engines: 2,
+                power_each: 600,
+            },
+        )
+    }
+
+    #[test]
+    fn test_bands_by_type() {
+        let mut airspace = Airspace::new(1);
+
+        let eagle = Eagle::new("Aquila".to_string());
+        assert_eq!(airspace.request_takeoff(&eagle, &airfield()), Ok(100));
+
+        let helicopter = helicopter();
+        assert_eq!(airspace.request_takeoff(&helicopter, &airfield()), Ok(1000));
+
+        // Light aircraft share the middle band, one level above the helicopter
+        let trainer = airplane("Cessna", AirplaneType::Private);
+        assert_eq!(airspace.request_takeoff(&trainer, &airfield()), Ok(1300));
+
+        // An airliner needs a runway, not a helipad
+        let airliner = airplane("Flight 101", AirplaneType::Commercial);
+        let rooftop = Site::new("Rooftop", Position::new(20, 0)).with(Facility::Helipad);
+        assert!(matches!(
+            airspace.request_takeoff(&airliner, &rooftop),
+            Err(AirspaceError::UnsuitableSite { .. })
+        ));
+        assert_eq!(airspace.request_takeoff(&airliner, &airfield()), Ok(8000));
+
+        assert!(airspace.conflicts().is_empty());
+        assert_eq!(airspace.flights().len(), 4);
+        assert_eq!(
+            airspace.flight("Flight 101").unwrap().category,
+            FlightCategory::Airliner
+        );
+    }
+
+    #[test]
+    fn test_takeoff_denied_when_the_band_is_full() {
+        let mut airspace = Airspace::new(1);
+        for (index, altitude_m) in [100, 400, 700].into_iter().enumerate() {
+            let duck = Duck::new(format!("Duck {}", index));
+            assert_eq!(airspace.request_takeoff(&duck, &airfield()), Ok(altitude_m));
+        }
+
+        let straggler = Duck::new("Straggler".to_string());
+        assert!(matches!(
+            airspace.request_takeoff(&straggler, &airfield()),
+            Err(AirspaceError::TakeoffDenied {
+                band: AltitudeBand::Low,
+                ..
+            })
+        ));
+        assert!(airspace.flight("Straggler").is_none());
+
+        // Far enough away, the low band is empty
+        assert_eq!(
+            airspace.assign_altitude(&straggler, Position::new(10, 0)),
+            Ok(100)
+        );
+
+        // Once a duck lands its level is free again
+        let duck = Duck::new("Duck 1".to_string());
+        airspace.land(&duck, &airfield()).unwrap();
+        assert_eq!(airspace.request_takeoff(&straggler, &airfield()), Ok(400));
+    }
+
+    #[test]
+    fn test_conflicts_are_detected() {
+        let mut airspace = Airspace::new(2);
+        let first = airplane("Flight 101", AirplaneType::Commercial);
+        let second = airplane("Flight 202", AirplaneType::Cargo);
+        airspace.track(&first, FIELD, 10_000).unwrap();
+        airspace
+            .track(&second, Position::new(10, 0), 10_100)
+            .unwrap();
+        assert!(airspace.conflicts().is_empty());
+
+        // Closing in at nearly the same level
+        airspace
+            .update("Flight 202", Position::new(2, 0), 10_100)
+            .unwrap();
+        let conflicts = airspace.conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].distance_km, 4);
+        assert_eq!(conflicts[0].vertical_m, 100);
+
+        // Climbing away restores separation
+        airspace
+            .update("Flight 202", Position::new(2, 0), 10_400)
+            .unwrap();
+        assert!(airspace.conflicts().is_empty());
+
+        assert!(matches!(
+            airspace.update("Flight 202", FIELD, 30_000),
+            Err(AirspaceError::AboveCeiling { .. })
+        ));
+        let dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        assert!(matches!(
+            airspace.track(&dog, FIELD, 0),
+            Err(AirspaceError::NotAFlyer { .. })
+        ));
+    }
+}
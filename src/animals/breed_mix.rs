@@ -0,0 +1,197 @@
+//! Mixed Breeds
+//!
+//! A [`BreedMix`] describes a dog by the share of each breed in its
+//! ancestry, such as "50% Labrador, 50% Husky". Its profile blends the
+//! parent breed profiles by those shares: a Labrador-Husky mix swims as
+//! deep as both parents, walks at their average pace and tolerates cold
+//! halfway between the Labrador and the Husky.
+
+use crate::animals::dog::DogBreed;
+use crate::animals::species::{CatalogError, SpeciesCatalog, SpeciesKind, SpeciesProfile};
+use crate::core::ThermalRange;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MixError {
+    #[error("A mix needs at least two parent breeds")]
+    TooFewParents,
+
+    #[error("Ancestry adds up to {total}%, not 100%")]
+    SharesDontAddUp { total: u32 },
+
+    #[error("{breed} has no share of the ancestry")]
+    ZeroShare { breed: String },
+
+    #[error("{breed} is listed more than once")]
+    DuplicateParent { breed: String },
+
+    #[error("Invalid ancestry '{0}', expected something like '50% Labrador, 50% Husky'")]
+    InvalidSpec(String),
+
+    #[error(transparent)]
+    Catalog(#[from] CatalogError),
+}
+
+/// Ancestry of a mixed-breed dog as percentages of its parent breeds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreedMix {
+    parents: Vec<(SpeciesProfile, u8)>,
+}
+
+impl BreedMix {
+    /// Mix breed profiles, such as ones from a species catalog; shares must add up to 100
+    pub fn new(parents: Vec<(SpeciesProfile, u8)>) -> Result<Self, MixError> {
+        if parents.len() < 2 {
+            return Err(MixError::TooFewParents);
+        }
+        for (index, (profile, share)) in parents.iter().enumerate() {
+            if *share == 0 {
+                return Err(MixError::ZeroShare {
+                    breed: profile.name.clone(),
+                });
+            }
+            if parents[..index].iter().any(|(p, _)| p.name == profile.name) {
+                return Err(MixError::DuplicateParent {
+                    breed: profile.name.clone(),
+                });
+            }
+        }
+        let total = parents.iter().map(|(_, share)| *share as u32).sum();
+        if total != 100 {
+            return Err(MixError::SharesDontAddUp { total });
+        }
+
+        Ok(Self { parents })
+    }
+
+    /// Mix compiled-in breeds
+    pub fn of(parents: &[(DogBreed, u8)]) -> Result<Self, MixError> {
+        Self::new(
+            parents
+                .iter()
+                .map(|(breed, share)| (breed.profile(), *share))
+                .collect(),
+        )
+    }
+
+    /// Read a mix written like its `Display` output, looking the breeds up in a catalog
+    pub fn parse(spec: &str, catalog: &SpeciesCatalog) -> Result<Self, MixError> {
+        let invalid = || MixError::InvalidSpec(spec.to_string());
+
+        let parents = spec
+            .split(',')
+            .map(|part| {
+                let (share, breed) = part.trim().split_once('%').ok_or_else(invalid)?;
+                let share = share.trim().parse().map_err(|_| invalid())?;
+                let breed = breed.trim();
+                let profile = catalog.find(SpeciesKind::Dog, breed).ok_or_else(|| {
+                    CatalogError::UnknownSpecies {
+                        kind: SpeciesKind::Dog,
+                        name: breed.to_string(),
+                    }
+                })?;
+                Ok((profile.clone(), share))
+            })
+            .collect::<Result<_, MixError>>()?;
+        Self::new(parents)
+    }
+
+    /// Parent breeds and their shares in percent
+    pub fn parents(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.parents
+            .iter()
+            .map(|(profile, share)| (profile.name.as_str(), *share))
+    }
+
+    /// Profile with every number blended by ancestry
+    pub fn profile(&self) -> SpeciesProfile {
+        let blend = |value: fn(&SpeciesProfile) -> i64| {
+            let total: i64 = self
+                .parents
+                .iter()
+                .map(|(profile, share)| value(profile) * *share as i64)
+                .sum();
+            (total + 50).div_euclid(100) // Round to the nearest whole number
+        };
+
+        SpeciesProfile {
+            name: self.to_string(),
+            weight_kg: blend(|p| p.weight_kg as i64) as u32,
+            walking_speed: blend(|p| p.walking_speed as i64) as u32,
+            swimming_speed: blend(|p| p.swimming_speed as i64) as u32,
+            max_depth: blend(|p| p.max_depth as i64) as u32,
+            breath_hold_seconds: blend(|p| p.breath_hold_seconds as i64) as u32,
+            thermal_range: ThermalRange::new(
+                blend(|p| p.thermal_range.min_c as i64) as i32,
+                blend(|p| p.thermal_range.max_c as i64) as i32,
+            ),
+            pulling_ratio_percent: blend(|p| p.pulling_ratio_percent as i64) as u32,
+            scent_range_m: blend(|p| p.scent_range_m as i64) as u32,
+        }
+    }
+}
+
+impl fmt::Display for BreedMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self
+            .parents()
+            .map(|(breed, share)| format!("{}% {}", share, breed))
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::Dog;
+    use crate::behaviors::{Swimming, Walking};
+    use crate::core::ThermalTolerance;
+
+    #[test]
+    fn test_mix_blends_parent_profiles() {
+        let mix = BreedMix::of(&[(DogBreed::Labrador, 50), (DogBreed::Husky, 50)]).unwrap();
+        let dog = Dog::mixed("Luna".to_string(), mix);
+
+        assert_eq!(dog.breed, DogBreed::Other);
+        assert_eq!(dog.profile.name, "50% Labrador, 50% Husky");
+        assert_eq!(dog.max_depth(), 10);
+        assert_eq!(dog.thermal_range(), ThermalRange::new(-27, 29)); // Warmer coat than a Labrador
+
+        let mix = BreedMix::of(&[(DogBreed::Greyhound, 75), (DogBreed::Bulldog, 25)]).unwrap();
+        let dog = Dog::mixed("Dash".to_string(), mix);
+        assert_eq!(dog.walking_speed(), 10);
+        assert_eq!(dog.max_depth(), 3);
+    }
+
+    #[test]
+    fn test_parse_mix_from_catalog() {
+        let catalog = SpeciesCatalog::parse(include_str!("../../data/species.toml")).unwrap();
+        let mix = BreedMix::parse("60% Newfoundland, 40% golden retriever", &catalog).unwrap();
+        assert_eq!(mix.to_string(), "60% Newfoundland, 40% Golden Retriever");
+        assert_eq!(mix.profile().max_depth, 13);
+        assert_eq!(mix.profile().pulling_ratio_percent, 122); // 60% of a cart dog
+
+        assert_eq!(
+            BreedMix::parse("60% Labrador, 30% Husky", &catalog),
+            Err(MixError::SharesDontAddUp { total: 90 })
+        );
+        assert_eq!(
+            BreedMix::parse("100% Labrador", &catalog),
+            Err(MixError::TooFewParents)
+        );
+        assert!(matches!(
+            BreedMix::parse("50% Labrador, 50% Labrador", &catalog),
+            Err(MixError::DuplicateParent { .. })
+        ));
+        assert!(matches!(
+            BreedMix::parse("half Labrador, half Husky", &catalog),
+            Err(MixError::InvalidSpec(_))
+        ));
+        assert!(matches!(
+            BreedMix::parse("50% Labrador, 50% Wolf", &catalog),
+            Err(MixError::Catalog(_))
+        ));
+    }
+}
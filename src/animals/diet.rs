@@ -0,0 +1,413 @@
+//! Feeding and Diet
+//!
+//! Besides resting, animals recover energy by eating. Every species has a
+//! [`Diet`] that decides how much of a [`Food`] item's energy it can use:
+//! a penguin gets everything out of fish but little out of meat, and cannot
+//! eat plants at all.
+//!
+//! Food is not instant energy. Each meal sits in the [`Stomach`] for a
+//! digestion time that depends on the kind of food, and its energy is only
+//! released once digested. Eating beyond the stomach's capacity leaves the
+//! animal overfed, which slows it down until enough has been digested.
+
+use crate::animals::Animal;
+use crate::core::{EnergyLevel, HasEnergy};
+use std::fmt;
+use thiserror::Error;
+
+/// Speed penalty while overfed
+const OVERFED_SPEED_PENALTY_PERCENT: u32 = 30;
+
+/// How far past its capacity an animal can be made to eat, in percent
+const MAX_FILL_PERCENT: u32 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FoodKind {
+    Fish,
+    Meat,
+    Insects,
+    Seeds,
+    Plants,
+    Kibble,
+}
+
+impl FoodKind {
+    /// Minutes before the energy of this food becomes available
+    pub fn digestion_minutes(&self) -> u32 {
+        match self {
+            FoodKind::Insects => 20,
+            FoodKind::Fish => 30,
+            FoodKind::Kibble => 40,
+            FoodKind::Seeds => 45,
+            FoodKind::Meat => 60,
+            FoodKind::Plants => 90, // Fibre takes a while
+        }
+    }
+}
+
+impl fmt::Display for FoodKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FoodKind::Fish => "Fish",
+            FoodKind::Meat => "Meat",
+            FoodKind::Insects => "Insects",
+            FoodKind::Seeds => "Seeds",
+            FoodKind::Plants => "Plants",
+            FoodKind::Kibble => "Kibble",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A portion of food
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Food {
+    pub kind: FoodKind,
+    /// Energy points (see [`EnergyLevel::to_points`]) for a perfect diet match
+    pub energy_points: u32,
+}
+
+impl Food {
+    pub fn new(kind: FoodKind, energy_points: u32) -> Self {
+        Self {
+            kind,
+            energy_points,
+        }
+    }
+}
+
+impl fmt::Display for Food {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({} points)", self.kind, self.energy_points)
+    }
+}
+
+/// What a species eats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Diet {
+    /// Fish eaters
+    Piscivore,
+    /// Meat eaters
+    Carnivore,
+    /// Plant eaters
+    Herbivore,
+    /// Insect eaters
+    Insectivore,
+    /// Mixed diet
+    Omnivore,
+}
+
+impl Diet {
+    /// Share of a food's energy this diet can use, in percent (0 = inedible)
+    pub fn match_percent(&self, kind: FoodKind) -> u32 {
+        match (self, kind) {
+            (Diet::Piscivore, FoodKind::Fish) => 100,
+            (Diet::Piscivore, FoodKind::Meat) => 25,
+            (Diet::Carnivore, FoodKind::Meat) => 100,
+            (Diet::Carnivore, FoodKind::Fish) => 75,
+            (Diet::Carnivore, FoodKind::Insects) => 25,
+            (Diet::Herbivore, FoodKind::Plants | FoodKind::Seeds) => 100,
+            (Diet::Insectivore, FoodKind::Insects) => 100,
+            (Diet::Insectivore, FoodKind::Seeds) => 25,
+            (Diet::Omnivore, FoodKind::Meat | FoodKind::Kibble) => 100,
+            (Diet::Omnivore, FoodKind::Fish) => 75,
+            (Diet::Omnivore, FoodKind::Seeds | FoodKind::Plants) => 50,
+            (Diet::Omnivore, FoodKind::Insects) => 25,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Diet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Diet::Piscivore => "Piscivore",
+            Diet::Carnivore => "Carnivore",
+            Diet::Herbivore => "Herbivore",
+            Diet::Insectivore => "Insectivore",
+            Diet::Omnivore => "Omnivore",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FeedingError {
+    #[error("{animal} cannot eat {food}: it is a {diet}")]
+    Inedible {
+        animal: String,
+        food: FoodKind,
+        diet: Diet,
+    },
+
+    #[error("{animal} is too full to eat: {content} of {capacity} points still digesting")]
+    TooFull {
+        animal: String,
+        content: u32,
+        capacity: u32,
+    },
+
+    #[error("Nothing to serve {animal}: the feeding station has an empty menu")]
+    EmptyMenu { animal: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Meal {
+    energy_points: u32,
+    minutes_left: u32,
+}
+
+/// Food waiting to be digested
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stomach {
+    capacity_points: u32,
+    meals: Vec<Meal>,
+}
+
+impl Stomach {
+    /// Create an empty stomach
+    pub fn new(capacity_points: u32) -> Self {
+        Self {
+            capacity_points,
+            meals: Vec::new(),
+        }
+    }
+
+    pub fn capacity_points(&self) -> u32 {
+        self.capacity_points
+    }
+
+    /// Energy points still being digested
+    pub fn content_points(&self) -> u32 {
+        self.meals.iter().map(|meal| meal.energy_points).sum()
+    }
+
+    /// Check if the stomach holds more than its capacity
+    pub fn is_overfed(&self) -> bool {
+        self.content_points() > self.capacity_points
+    }
+
+    /// Check if the stomach can take this many more points at all
+    pub fn has_room(&self, energy_points: u32) -> bool {
+        self.content_points() + energy_points <= self.capacity_points * MAX_FILL_PERCENT / 100
+    }
+
+    /// Start digesting a meal
+    pub fn swallow(&mut self, energy_points: u32, digestion_minutes: u32) {
+        self.meals.push(Meal {
+            energy_points,
+            minutes_left: digestion_minutes,
+        });
+    }
+
+    /// Let time pass, returning the energy points of fully digested meals
+    pub fn digest(&mut self, minutes: u32) -> u32 {
+        let mut released = 0;
+        self.meals.retain_mut(|meal| {
+            if meal.minutes_left <= minutes {
+                released += meal.energy_points;
+                false
+            } else {
+                meal.minutes_left -= minutes;
+                true
+            }
+        });
+        released
+    }
+
+    /// Speed while digesting - reduced when overfed (never below 1)
+    pub fn sated_speed(&self, base_speed: u32) -> u32 {
+        if !self.is_overfed() {
+            return base_speed;
+        }
+        (base_speed * (100 - OVERFED_SPEED_PENALTY_PERCENT) / 100).max(1)
+    }
+}
+
+/// Animals that eat to recover energy
+pub trait Feeding: Animal + HasEnergy {
+    /// What this species eats
+    fn diet(&self) -> Diet;
+
+    /// Get the stomach
+    fn stomach(&self) -> &Stomach;
+
+    /// Get mutable access to the stomach
+    fn stomach_mut(&mut self) -> &mut Stomach;
+
+    /// Check if the animal ate more than it can comfortably hold
+    fn is_overfed(&self) -> bool {
+        self.stomach().is_overfed()
+    }
+
+    /// Eat a portion; its energy becomes available once digested
+    fn feed(&mut self, food: Food) -> Result<String, FeedingError> {
+        let match_percent = self.diet().match_percent(food.kind);
+        if match_percent == 0 {
+            return Err(FeedingError::Inedible {
+                animal: self.name(),
+                food: food.kind,
+                diet: self.diet(),
+            });
+        }
+
+        let energy_points = food.energy_points * match_percent / 100;
+        if !self.stomach().has_room(energy_points) {
+            return Err(FeedingError::TooFull {
+                animal: self.name(),
+                content: self.stomach().content_points(),
+                capacity: self.stomach().capacity_points(),
+            });
+        }
+
+        let minutes = food.kind.digestion_minutes();
+        self.stomach_mut().swallow(energy_points, minutes);
+        Ok(format!(
+            "{} eats {} ({}% diet match), digesting for {} minutes",
+            self.name(),
+            food.kind,
+            match_percent,
+            minutes
+        ))
+    }
+
+    /// Let time pass and turn digested food into energy
+    fn digest(&mut self, minutes: u32) -> EnergyLevel {
+        let released = self.stomach_mut().digest(minutes);
+        if released > 0 {
+            let points = (self.energy().to_points() as u32 + released).min(100);
+            let level = EnergyLevel::from_points(points as u8);
+            // Food never makes an animal more tired than before
+            if level > self.energy() {
+                self.set_energy(level);
+            }
+        }
+        self.energy()
+    }
+}
+
+/// A place where competitors can eat between legs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedingStation {
+    pub menu: Vec<Food>,
+    /// Minutes competitors spend at the station
+    pub break_minutes: u32,
+}
+
+impl FeedingStation {
+    pub fn new(menu: Vec<Food>, break_minutes: u32) -> Self {
+        Self {
+            menu,
+            break_minutes,
+        }
+    }
+
+    /// Let an animal eat the best item on the menu for its diet, then digest
+    /// for the length of the break
+    pub fn serve<T: Feeding>(&self, animal: &mut T) -> Result<String, FeedingError> {
+        let diet = animal.diet();
+        let first = self.menu.first().ok_or_else(|| FeedingError::EmptyMenu {
+            animal: animal.name(),
+        })?;
+        let food = self
+            .menu
+            .iter()
+            .filter(|food| diet.match_percent(food.kind) > 0)
+            .max_by_key(|food| food.energy_points * diet.match_percent(food.kind))
+            .ok_or_else(|| FeedingError::Inedible {
+                animal: animal.name(),
+                food: first.kind,
+                diet,
+            })?;
+
+        let message = animal.feed(*food)?;
+        animal.digest(self.break_minutes);
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::{Dog, Penguin, dog::DogBreed};
+    use crate::behaviors::Walking;
+
+    #[test]
+    fn test_energy_arrives_after_digestion() {
+        let mut penguin = Penguin::new("Pingu".to_string());
+        penguin.set_energy(EnergyLevel::Tired);
+
+        penguin.feed(Food::new(FoodKind::Fish, 30)).unwrap();
+        assert_eq!(penguin.digest(10), EnergyLevel::Tired); // Still digesting
+        assert_eq!(penguin.digest(20), EnergyLevel::Normal); // 40 + 30 points
+    }
+
+    #[test]
+    fn test_diet_match_scales_energy() {
+        let mut penguin = Penguin::new("Pingu".to_string());
+        assert!(matches!(
+            penguin.feed(Food::new(FoodKind::Plants, 50)),
+            Err(FeedingError::Inedible { .. })
+        ));
+
+        penguin.set_energy(EnergyLevel::Tired);
+        penguin.feed(Food::new(FoodKind::Meat, 40)).unwrap();
+        assert_eq!(penguin.digest(60), EnergyLevel::Tired); // Only 10 of 40 points
+    }
+
+    #[test]
+    fn test_overfeeding_slows_down() {
+        let mut dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        let speed = dog.walking_speed();
+
+        dog.feed(Food::new(FoodKind::Meat, 40)).unwrap();
+        dog.feed(Food::new(FoodKind::Kibble, 40)).unwrap();
+        assert!(dog.is_overfed());
+        assert!(dog.walking_speed() < speed);
+        assert!(matches!(
+            dog.feed(Food::new(FoodKind::Meat, 60)),
+            Err(FeedingError::TooFull { .. })
+        ));
+
+        dog.digest(60);
+        assert!(!dog.is_overfed());
+        assert_eq!(dog.walking_speed(), speed);
+    }
+
+    #[test]
+    fn test_feeding_station_picks_matching_food() {
+        let station = FeedingStation::new(
+            vec![
+                Food::new(FoodKind::Plants, 60),
+                Food::new(FoodKind::Fish, 40),
+            ],
+            30,
+        );
+        let mut penguin = Penguin::new("Pingu".to_string());
+        penguin.set_energy(EnergyLevel::Tired);
+
+        let message = station.serve(&mut penguin).unwrap();
+        assert!(message.contains("Fish"));
+        assert_eq!(penguin.energy(), EnergyLevel::Energetic); // 40 + 40 points
+    }
+
+    #[test]
+    fn test_feeding_station_without_food() {
+        let mut penguin = Penguin::new("Pingu".to_string());
+        assert_eq!(
+            FeedingStation::new(Vec::new(), 30).serve(&mut penguin),
+            Err(FeedingError::EmptyMenu {
+                animal: "Pingu".to_string()
+            })
+        );
+
+        let plants = FeedingStation::new(vec![Food::new(FoodKind::Plants, 60)], 30);
+        assert!(matches!(
+            plants.serve(&mut penguin),
+            Err(FeedingError::Inedible {
+                food: FoodKind::Plants,
+                ..
+            })
+        ));
+    }
+}
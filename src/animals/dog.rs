@@ -1,9 +1,19 @@
 use crate::animals::Animal;
-use crate::behaviors::{LandMove, Walking};
-use crate::behaviors::{moving::Moving, swimming::Swimming};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::animals::breed_mix::BreedMix;
+use crate::animals::diet::{Diet, Feeding, Stomach};
+use crate::animals::species::SpeciesProfile;
+use crate::behaviors::{LandMove, Pulling, Sensing, Sensor, SensorKind, Walking};
+use crate::behaviors::{
+    moving::{Moving, MovingError},
+    participant::Participant,
+    swimming::Swimming,
+};
+use crate::core::{
+    AirSupply, DiveState, EnergyLevel, HasEnergy, HasHealth, Health, Mishap, ThermalRange,
+    ThermalTolerance,
+};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DogBreed {
     BorderCollie,
     GoldenRetriever,
@@ -31,21 +41,148 @@ impl std::fmt::Display for DogBreed {
     }
 }
 
+impl DogBreed {
+    pub const ALL: [DogBreed; 8] = [
+        DogBreed::BorderCollie,
+        DogBreed::GoldenRetriever,
+        DogBreed::Labrador,
+        DogBreed::Bulldog,
+        DogBreed::Greyhound,
+        DogBreed::Husky,
+        DogBreed::SaintBernard,
+        DogBreed::Other,
+    ];
+
+    /// Look up a breed by its display name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|breed| breed.to_string() == name)
+    }
+
+    pub fn typical_weight_kg(&self) -> u32 {
+        match self {
+            DogBreed::SaintBernard => 75, // Giant breed
+            DogBreed::GoldenRetriever | DogBreed::Labrador => 32,
+            DogBreed::Greyhound => 30,
+            DogBreed::Husky | DogBreed::Bulldog => 23,
+            DogBreed::BorderCollie => 20,
+            DogBreed::Other => 25,
+        }
+    }
+
+    /// Temperatures the breed is comfortable in
+    pub fn thermal_range(&self) -> ThermalRange {
+        match self {
+            DogBreed::Husky => ThermalRange::new(-40, 25), // Double coat
+            DogBreed::SaintBernard => ThermalRange::new(-30, 25),
+            DogBreed::Greyhound => ThermalRange::new(-5, 35), // Thin coat
+            DogBreed::Bulldog => ThermalRange::new(0, 28),    // Short muzzle
+            _ => ThermalRange::new(-15, 32),
+        }
+    }
+
+    pub fn walking_speed(&self) -> u32 {
+        match self {
+            DogBreed::Greyhound => 12, // Built for speed
+            DogBreed::Bulldog => 4,
+            _ => 7,
+        }
+    }
+
+    pub fn max_diving_depth(&self) -> u32 {
+        match self {
+            DogBreed::BorderCollie
+            | DogBreed::GoldenRetriever
+            | DogBreed::Labrador
+            | DogBreed::Husky
+            | DogBreed::SaintBernard => 10,
+            DogBreed::Bulldog => 2,
+            DogBreed::Greyhound => 3,
+            DogBreed::Other => 5,
+        }
+    }
+
+    /// Load the breed pulls in harness, in percent of its own weight
+    pub fn pulling_ratio_percent(&self) -> u32 {
+        match self {
+            DogBreed::Husky => 200,        // Bred to pull sleds
+            DogBreed::SaintBernard => 120, // Old draft dog
+            DogBreed::Greyhound | DogBreed::Bulldog => 40,
+            _ => 80,
+        }
+    }
+
+    /// How far the breed picks up a person's trail, in meters
+    pub fn scent_range_m(&self) -> u32 {
+        match self {
+            DogBreed::SaintBernard => 1000, // Finds people buried under snow
+            DogBreed::BorderCollie | DogBreed::GoldenRetriever | DogBreed::Labrador => 600,
+            _ => 400,
+        }
+    }
+
+    /// Compiled-in numbers for this breed
+    pub fn profile(&self) -> SpeciesProfile {
+        SpeciesProfile {
+            name: self.to_string(),
+            weight_kg: self.typical_weight_kg(),
+            walking_speed: self.walking_speed(),
+            swimming_speed: 3,       // Doggy paddle
+            breath_hold_seconds: 30, // Dogs only duck under briefly
+            max_depth: self.max_diving_depth(),
+            thermal_range: self.thermal_range(),
+            pulling_ratio_percent: self.pulling_ratio_percent(),
+            scent_range_m: self.scent_range_m(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Dog {
     pub name: String,
     pub breed: DogBreed,
+    pub profile: SpeciesProfile,
+    /// Parent breeds of a mixed-breed dog
+    pub ancestry: Option<BreedMix>,
     pub energy: EnergyLevel,
+    pub dive_state: DiveState,
+    pub stomach: Stomach,
+    pub health: Health,
 }
 
 impl Dog {
     pub fn new(name: String, breed: DogBreed) -> Self {
+        Self::with_profile(name, breed.profile())
+    }
+
+    /// Create a mixed-breed dog with a profile blended from its ancestry
+    pub fn mixed(name: String, ancestry: BreedMix) -> Self {
+        let profile = ancestry.profile();
+        Self {
+            ancestry: Some(ancestry),
+            ..Self::with_profile(name, profile)
+        }
+    }
+
+    /// Create a dog from a breed profile, such as one loaded from a species file
+    pub fn with_profile(name: String, profile: SpeciesProfile) -> Self {
         Self {
             name,
-            breed,
+            breed: DogBreed::from_name(&profile.name).unwrap_or(DogBreed::Other),
+            profile,
+            ancestry: None,
             energy: EnergyLevel::Energetic,
+            dive_state: DiveState::at_surface(),
+            stomach: Stomach::new(60),
+            health: Health::new(),
         }
     }
+
+    /// Breed as shown to people - the mix for mixed breeds
+    pub fn breed_name(&self) -> &str {
+        &self.profile.name
+    }
 }
 
 impl Animal for Dog {
@@ -57,6 +194,41 @@ impl Animal for Dog {
     }
 }
 
+impl Participant for Dog {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.profile.weight_kg
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_walker(&self) -> Option<&dyn Walking> {
+        Some(self)
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        Some(self)
+    }
+
+    fn as_sensor(&self) -> Option<&dyn Sensing> {
+        Some(self)
+    }
+}
+
+impl Sensing for Dog {
+    fn sensors(&self) -> Vec<Sensor> {
+        vec![
+            Sensor::unaided(),
+            Sensor::new(SensorKind::Scent, self.profile.scent_range_m),
+        ]
+    }
+}
+
 impl HasEnergy for Dog {
     fn energy(&self) -> EnergyLevel {
         self.energy
@@ -66,21 +238,85 @@ impl HasEnergy for Dog {
     }
 }
 
-impl Moving for Dog {}
+impl Feeding for Dog {
+    fn diet(&self) -> Diet {
+        Diet::Omnivore
+    }
+
+    fn stomach(&self) -> &Stomach {
+        &self.stomach
+    }
+
+    fn stomach_mut(&mut self) -> &mut Stomach {
+        &mut self.stomach
+    }
+}
+
+impl ThermalTolerance for Dog {
+    fn thermal_range(&self) -> ThermalRange {
+        self.profile.thermal_range
+    }
+}
+
+impl HasHealth for Dog {
+    fn health(&self) -> &Health {
+        &self.health
+    }
+
+    fn health_mut(&mut self) -> &mut Health {
+        &mut self.health
+    }
+}
+
+impl Moving for Dog {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn check_movement(&self) -> Result<(), MovingError> {
+        Ok(self.check_health()?)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.injure(mishap);
+    }
+}
 impl LandMove for Dog {}
-impl Walking for Dog {}
+impl Walking for Dog {
+    fn walking_speed(&self) -> u32 {
+        self.health
+            .limit(self.stomach.sated_speed(self.profile.walking_speed))
+    }
+}
+
+impl Pulling for Dog {
+    fn max_pull_kg(&self) -> u32 {
+        self.health
+            .limit(self.profile.weight_kg * self.profile.pulling_ratio_percent / 100)
+    }
+}
 
 impl Swimming for Dog {
+    fn swimming_speed(&self) -> u32 {
+        self.health
+            .limit(self.stomach.sated_speed(self.profile.swimming_speed))
+    }
+
     fn max_depth(&self) -> u32 {
-        match self.breed {
-            DogBreed::BorderCollie
-            | DogBreed::GoldenRetriever
-            | DogBreed::Labrador
-            | DogBreed::Husky
-            | DogBreed::SaintBernard => 10,
-            DogBreed::Bulldog => 2,
-            DogBreed::Greyhound => 3,
-            DogBreed::Other => 5,
+        self.health.limit(self.profile.max_depth)
+    }
+
+    fn dive_state(&self) -> DiveState {
+        self.dive_state
+    }
+
+    fn set_dive_state(&mut self, state: DiveState) {
+        self.dive_state = state;
+    }
+
+    fn air_supply(&self) -> AirSupply {
+        AirSupply::Breath {
+            max_seconds: self.profile.breath_hold_seconds,
         }
     }
 }
@@ -1,12 +1,25 @@
 use crate::animals::Animal;
+use crate::animals::diet::{Diet, Feeding, Stomach};
 use crate::behaviors::LandMove;
-use crate::behaviors::{flying::Flying, moving::Moving, swimming::Swimming, walking::Walking};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::{
+    flying::Flying,
+    moving::{Moving, MovingError},
+    participant::Participant,
+    swimming::Swimming,
+    walking::Walking,
+};
+use crate::core::{
+    AirSupply, DiveState, EnergyLevel, HasEnergy, HasHealth, Health, Mishap, ThermalRange,
+    ThermalTolerance,
+};
 
 #[derive(Debug)]
 pub struct Duck {
     name: String,
     energy: EnergyLevel,
+    dive_state: DiveState,
+    stomach: Stomach,
+    health: Health,
 }
 
 impl Duck {
@@ -14,6 +27,9 @@ impl Duck {
         Self {
             name,
             energy: EnergyLevel::Normal,
+            dive_state: DiveState::at_surface(),
+            stomach: Stomach::new(30),
+            health: Health::new(),
         }
     }
 }
@@ -28,6 +44,32 @@ impl Animal for Duck {
     }
 }
 
+impl Participant for Duck {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        1
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_walker(&self) -> Option<&dyn Walking> {
+        Some(self)
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        Some(self)
+    }
+
+    fn as_flyer(&self) -> Option<&dyn Flying> {
+        Some(self)
+    }
+}
+
 impl HasEnergy for Duck {
     fn energy(&self) -> EnergyLevel {
         self.energy
@@ -38,19 +80,85 @@ impl HasEnergy for Duck {
     }
 }
 
+impl Feeding for Duck {
+    fn diet(&self) -> Diet {
+        Diet::Omnivore
+    }
+
+    fn stomach(&self) -> &Stomach {
+        &self.stomach
+    }
+
+    fn stomach_mut(&mut self) -> &mut Stomach {
+        &mut self.stomach
+    }
+}
+
 // use land move trait in walk
 
-impl Moving for Duck {}
+impl ThermalTolerance for Duck {
+    fn thermal_range(&self) -> ThermalRange {
+        ThermalRange::new(-20, 35) // Waterproof down
+    }
+}
+
+impl HasHealth for Duck {
+    fn health(&self) -> &Health {
+        &self.health
+    }
+
+    fn health_mut(&mut self) -> &mut Health {
+        &mut self.health
+    }
+}
+
+impl Moving for Duck {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn check_movement(&self) -> Result<(), MovingError> {
+        Ok(self.check_health()?)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.injure(mishap);
+    }
+}
 impl LandMove for Duck {}
-impl Walking for Duck {}
+impl Walking for Duck {
+    fn walking_speed(&self) -> u32 {
+        self.health.limit(self.stomach.sated_speed(2)) // Waddling
+    }
+}
 impl Swimming for Duck {
+    fn swimming_speed(&self) -> u32 {
+        self.health.limit(self.stomach.sated_speed(3)) // Paddling
+    }
+
     fn max_depth(&self) -> u32 {
-        5 // Ducks don't dive too deep
+        self.health.limit(5) // Ducks don't dive too deep
+    }
+
+    fn dive_state(&self) -> DiveState {
+        self.dive_state
+    }
+
+    fn set_dive_state(&mut self, state: DiveState) {
+        self.dive_state = state;
+    }
+
+    fn air_supply(&self) -> AirSupply {
+        AirSupply::Breath { max_seconds: 60 } // Short foraging dives
     }
 }
 
 impl Flying for Duck {
     fn max_altitude(&self) -> u32 {
-        1000 // Moderate flying height
+        self.health.limit(1000) // Moderate flying height
+    }
+
+    fn flying_speed(&self) -> u32 {
+        self.health.limit(self.stomach.sated_speed(70)) // Fast migratory flyer
     }
 }
@@ -1,12 +1,23 @@
 use crate::animals::Animal;
+use crate::animals::diet::{Diet, Feeding, Stomach};
 use crate::behaviors::LandMove;
-use crate::behaviors::{flying::Flying, moving::Moving, walking::Walking};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::{
+    flying::Flying,
+    moving::{Moving, MovingError},
+    participant::Participant,
+    sensing::{Sensing, Sensor, SensorKind},
+    walking::Walking,
+};
+use crate::core::{
+    EnergyLevel, HasEnergy, HasHealth, Health, Mishap, ThermalRange, ThermalTolerance,
+};
 
 #[derive(Debug)]
 pub struct Eagle {
     name: String,
     energy: EnergyLevel,
+    stomach: Stomach,
+    health: Health,
 }
 
 impl Eagle {
@@ -14,6 +25,8 @@ impl Eagle {
         Self {
             name,
             energy: EnergyLevel::Energetic, // Eagles start with high energy
+            stomach: Stomach::new(50),
+            health: Health::new(),
         }
     }
 }
@@ -28,6 +41,39 @@ impl Animal for Eagle {
     }
 }
 
+impl Participant for Eagle {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        5
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_walker(&self) -> Option<&dyn Walking> {
+        Some(self)
+    }
+
+    fn as_flyer(&self) -> Option<&dyn Flying> {
+        Some(self)
+    }
+
+    fn as_sensor(&self) -> Option<&dyn Sensing> {
+        Some(self)
+    }
+}
+
+impl Sensing for Eagle {
+    fn sensors(&self) -> Vec<Sensor> {
+        // Spots a rabbit from three kilometers away
+        vec![Sensor::new(SensorKind::Vision, 3000)]
+    }
+}
+
 impl HasEnergy for Eagle {
     fn energy(&self) -> EnergyLevel {
         self.energy
@@ -38,11 +84,61 @@ impl HasEnergy for Eagle {
     }
 }
 
-impl Moving for Eagle {}
+impl Feeding for Eagle {
+    fn diet(&self) -> Diet {
+        Diet::Carnivore
+    }
+
+    fn stomach(&self) -> &Stomach {
+        &self.stomach
+    }
+
+    fn stomach_mut(&mut self) -> &mut Stomach {
+        &mut self.stomach
+    }
+}
+
+impl ThermalTolerance for Eagle {
+    fn thermal_range(&self) -> ThermalRange {
+        ThermalRange::new(-30, 35)
+    }
+}
+
+impl HasHealth for Eagle {
+    fn health(&self) -> &Health {
+        &self.health
+    }
+
+    fn health_mut(&mut self) -> &mut Health {
+        &mut self.health
+    }
+}
+
+impl Moving for Eagle {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn check_movement(&self) -> Result<(), MovingError> {
+        Ok(self.check_health()?)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.injure(mishap);
+    }
+}
 impl LandMove for Eagle {}
-impl Walking for Eagle {}
+impl Walking for Eagle {
+    fn walking_speed(&self) -> u32 {
+        self.health.limit(self.stomach.sated_speed(1)) // Eagles hop rather than walk
+    }
+}
 impl Flying for Eagle {
     fn max_altitude(&self) -> u32 {
-        3000 // Eagles can fly very high
+        self.health.limit(3000) // Eagles can fly very high
+    }
+
+    fn flying_speed(&self) -> u32 {
+        self.health.limit(self.stomach.sated_speed(60)) // Soaring cruise
     }
 }
@@ -0,0 +1,493 @@
+//! Frogs and Metamorphosis
+//!
+//! A frog's capabilities change over its life. The life stage is a type
+//! parameter, just like a vehicle's engine state, so a `Frog<Tadpole>` only
+//! implements [`Swimming`] while a `Frog<Adult>` also implements
+//! [`LandMove`], [`Walking`] and [`Jumping`]:
+//!
+//! ```text
+//! Tadpole --grow(days)--> ... --metamorphose()--> Adult
+//! ```
+//!
+//! The default stage is `Adult`, so `Frog::new(...)` creates a grown frog.
+//! When the stage is only known at runtime - a frog growing up during a
+//! simulation - [`AnyFrog`] holds either stage. Its [`Participant`]
+//! accessors report what the frog can do right now, so `as_walker()` is
+//! `None` until the metamorphosis is complete.
+
+use crate::animals::Animal;
+use crate::animals::diet::{Diet, Feeding, Stomach};
+use crate::behaviors::{
+    Jumping, LandMove, Walking,
+    moving::{Moving, MovingError},
+    participant::Participant,
+    swimming::Swimming,
+};
+use crate::core::{
+    AirSupply, DiveState, EnergyLevel, HasEnergy, HasHealth, Health, Mishap, ThermalRange,
+    ThermalTolerance,
+};
+use std::fmt;
+use std::marker::PhantomData;
+use thiserror::Error;
+
+/// Days a tadpole grows before it can metamorphose
+pub const METAMORPHOSIS_DAYS: u32 = 60;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Marker trait for the stages of a frog's life
+pub trait LifeStage: sealed::Sealed + fmt::Debug {
+    /// Display name of this stage
+    const NAME: &'static str;
+    /// What frogs eat at this stage
+    const DIET: Diet;
+    /// How frogs breathe underwater at this stage
+    const AIR_SUPPLY: AirSupply;
+    const SWIMMING_SPEED: u32;
+    const MAX_DEPTH: u32;
+}
+
+/// Gills and a tail, no legs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tadpole;
+
+/// Lungs and four legs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adult;
+
+impl sealed::Sealed for Tadpole {}
+impl sealed::Sealed for Adult {}
+
+impl LifeStage for Tadpole {
+    const NAME: &'static str = "Tadpole";
+    const DIET: Diet = Diet::Herbivore; // Grazes on algae
+    const AIR_SUPPLY: AirSupply = AirSupply::Gills;
+    const SWIMMING_SPEED: u32 = 1;
+    const MAX_DEPTH: u32 = 1;
+}
+
+impl LifeStage for Adult {
+    const NAME: &'static str = "Adult";
+    const DIET: Diet = Diet::Insectivore;
+    const AIR_SUPPLY: AirSupply = AirSupply::Breath { max_seconds: 900 }; // Breathes through its skin too
+    const SWIMMING_SPEED: u32 = 2;
+    const MAX_DEPTH: u32 = 3;
+}
+
+/// Returned when a tadpole cannot metamorphose; gives the tadpole back
+#[derive(Error, Debug)]
+#[error("{name} cannot metamorphose yet: {reason}")]
+pub struct MetamorphosisError {
+    pub name: String,
+    pub reason: String,
+    pub tadpole: Box<Frog<Tadpole>>,
+}
+
+#[derive(Debug)]
+pub struct Frog<S = Adult> {
+    name: String,
+    energy: EnergyLevel,
+    age_days: u32,
+    dive_state: DiveState,
+    stomach: Stomach,
+    health: Health,
+    _stage: PhantomData<S>,
+}
+
+impl Frog {
+    /// Create a grown frog
+    pub fn new(name: String) -> Self {
+        Self::born(name, METAMORPHOSIS_DAYS)
+    }
+}
+
+impl Frog<Tadpole> {
+    /// Hatch a tadpole from its egg
+    pub fn hatch(name: String) -> Self {
+        Self::born(name, 0)
+    }
+
+    /// Days of growth still needed before the metamorphosis
+    pub fn days_to_metamorphosis(&self) -> u32 {
+        METAMORPHOSIS_DAYS.saturating_sub(self.age_days)
+    }
+
+    /// Grow legs and lose the tail - costs one energy level
+    pub fn metamorphose(self) -> Result<Frog<Adult>, MetamorphosisError> {
+        let reason = if self.days_to_metamorphosis() > 0 {
+            Some(format!(
+                "{} more days of growth needed",
+                self.days_to_metamorphosis()
+            ))
+        } else if self.energy < EnergyLevel::Tired {
+            Some(format!("too weak ({})", self.energy))
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            return Err(MetamorphosisError {
+                name: self.name.clone(),
+                reason,
+                tadpole: Box::new(self),
+            });
+        }
+
+        let mut adult = self.into_stage();
+        adult.consume_energy();
+        Ok(adult)
+    }
+}
+
+impl<S: LifeStage> Frog<S> {
+    fn born(name: String, age_days: u32) -> Self {
+        Self {
+            name,
+            energy: EnergyLevel::Normal,
+            age_days,
+            dive_state: DiveState::at_surface(),
+            stomach: Stomach::new(20),
+            health: Health::new(),
+            _stage: PhantomData,
+        }
+    }
+
+    pub fn age_days(&self) -> u32 {
+        self.age_days
+    }
+
+    /// Get the display name of the current life stage
+    pub fn stage_name(&self) -> &'static str {
+        S::NAME
+    }
+
+    /// Let days pass
+    pub fn grow(&mut self, days: u32) {
+        self.age_days = self.age_days.saturating_add(days);
+    }
+
+    fn into_stage<T: LifeStage>(self) -> Frog<T> {
+        Frog {
+            name: self.name,
+            energy: self.energy,
+            age_days: self.age_days,
+            dive_state: self.dive_state,
+            stomach: self.stomach,
+            health: self.health,
+            _stage: PhantomData,
+        }
+    }
+}
+
+impl<S: LifeStage> Animal for Frog<S> {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn species(&self) -> &'static str {
+        "Frog"
+    }
+
+    fn description(&self) -> String {
+        format!("{} is a {} ({})", self.name(), self.species(), S::NAME)
+    }
+}
+
+impl Participant for Frog<Tadpole> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        0 // A few grams
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        Some(self)
+    }
+}
+
+impl Participant for Frog<Adult> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        1
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_walker(&self) -> Option<&dyn Walking> {
+        Some(self)
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        Some(self)
+    }
+
+    fn as_jumper(&self) -> Option<&dyn Jumping> {
+        Some(self)
+    }
+}
+
+impl<S: LifeStage> HasEnergy for Frog<S> {
+    fn energy(&self) -> EnergyLevel {
+        self.energy
+    }
+
+    fn set_energy(&mut self, level: EnergyLevel) {
+        self.energy = level;
+    }
+}
+
+impl<S: LifeStage> Feeding for Frog<S> {
+    fn diet(&self) -> Diet {
+        S::DIET
+    }
+
+    fn stomach(&self) -> &Stomach {
+        &self.stomach
+    }
+
+    fn stomach_mut(&mut self) -> &mut Stomach {
+        &mut self.stomach
+    }
+}
+
+impl<S: LifeStage> ThermalTolerance for Frog<S> {
+    fn thermal_range(&self) -> ThermalRange {
+        ThermalRange::new(5, 30)
+    }
+
+    fn is_ectothermic(&self) -> bool {
+        true // Cold-blooded
+    }
+}
+
+impl<S: LifeStage> HasHealth for Frog<S> {
+    fn health(&self) -> &Health {
+        &self.health
+    }
+
+    fn health_mut(&mut self) -> &mut Health {
+        &mut self.health
+    }
+}
+
+impl<S: LifeStage> Moving for Frog<S> {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn check_movement(&self) -> Result<(), MovingError> {
+        Ok(self.check_health()?)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.injure(mishap);
+    }
+}
+
+impl<S: LifeStage> Swimming for Frog<S> {
+    fn max_depth(&self) -> u32 {
+        self.health.limit(S::MAX_DEPTH)
+    }
+
+    fn swimming_speed(&self) -> u32 {
+        self.health
+            .limit(self.stomach.sated_speed(S::SWIMMING_SPEED))
+    }
+
+    fn dive_state(&self) -> DiveState {
+        self.dive_state
+    }
+
+    fn set_dive_state(&mut self, state: DiveState) {
+        self.dive_state = state;
+    }
+
+    fn air_supply(&self) -> AirSupply {
+        S::AIR_SUPPLY
+    }
+}
+
+impl LandMove for Frog<Adult> {}
+impl Walking for Frog<Adult> {
+    fn walking_speed(&self) -> u32 {
+        self.health.limit(self.stomach.sated_speed(1)) // Mostly hops
+    }
+}
+
+impl Jumping for Frog<Adult> {
+    fn jump_distance_cm(&self) -> u32 {
+        self.health.limit(150) // Over ten times its body length
+    }
+}
+
+/// A frog whose life stage is only known at runtime
+#[derive(Debug)]
+pub enum AnyFrog {
+    Tadpole(Frog<Tadpole>),
+    Adult(Frog<Adult>),
+}
+
+impl AnyFrog {
+    /// Hatch a tadpole from its egg
+    pub fn hatch(name: String) -> Self {
+        AnyFrog::Tadpole(Frog::hatch(name))
+    }
+
+    pub fn stage_name(&self) -> &'static str {
+        match self {
+            AnyFrog::Tadpole(frog) => frog.stage_name(),
+            AnyFrog::Adult(frog) => frog.stage_name(),
+        }
+    }
+
+    pub fn is_adult(&self) -> bool {
+        matches!(self, AnyFrog::Adult(_))
+    }
+
+    /// Let days pass; a tadpole metamorphoses as soon as it is able to
+    pub fn grow(self, days: u32) -> Self {
+        match self {
+            AnyFrog::Tadpole(mut tadpole) => {
+                tadpole.grow(days);
+                match tadpole.metamorphose() {
+                    Ok(adult) => AnyFrog::Adult(adult),
+                    Err(error) => AnyFrog::Tadpole(*error.tadpole),
+                }
+            }
+            AnyFrog::Adult(mut adult) => {
+                adult.grow(days);
+                AnyFrog::Adult(adult)
+            }
+        }
+    }
+
+    /// Get the grown frog, if the metamorphosis is complete
+    pub fn as_adult_mut(&mut self) -> Option<&mut Frog<Adult>> {
+        match self {
+            AnyFrog::Adult(frog) => Some(frog),
+            AnyFrog::Tadpole(_) => None,
+        }
+    }
+
+    /// Get the frog as a participant with the capabilities of its stage
+    pub fn as_participant(&self) -> &dyn Participant {
+        match self {
+            AnyFrog::Tadpole(frog) => frog,
+            AnyFrog::Adult(frog) => frog,
+        }
+    }
+}
+
+impl HasEnergy for AnyFrog {
+    fn energy(&self) -> EnergyLevel {
+        self.as_participant().energy()
+    }
+
+    fn set_energy(&mut self, level: EnergyLevel) {
+        match self {
+            AnyFrog::Tadpole(frog) => frog.set_energy(level),
+            AnyFrog::Adult(frog) => frog.set_energy(level),
+        }
+    }
+}
+
+impl Participant for AnyFrog {
+    fn participant_name(&self) -> String {
+        self.as_participant().participant_name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.as_participant().weight_kg()
+    }
+
+    fn as_walker(&self) -> Option<&dyn Walking> {
+        self.as_participant().as_walker()
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        self.as_participant().as_swimmer()
+    }
+
+    fn as_jumper(&self) -> Option<&dyn Jumping> {
+        self.as_participant().as_jumper()
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        self.as_participant().as_thermal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::diet::{Food, FoodKind};
+    use crate::behaviors::TravelMode;
+
+    #[test]
+    fn test_tadpole_only_swims() {
+        let mut tadpole = Frog::hatch("Polly".to_string());
+        assert_eq!(tadpole.travel_modes(), vec![TravelMode::Swim]);
+        assert!(tadpole.as_jumper().is_none());
+        assert_eq!(tadpole.air_supply(), AirSupply::Gills);
+        assert!(tadpole.swim().is_ok());
+
+        assert!(tadpole.feed(Food::new(FoodKind::Plants, 10)).is_ok());
+        assert!(tadpole.feed(Food::new(FoodKind::Insects, 10)).is_err());
+    }
+
+    #[test]
+    fn test_metamorphosis_needs_growth() {
+        let mut tadpole = Frog::hatch("Polly".to_string());
+        tadpole.grow(45);
+
+        let error = tadpole.metamorphose().unwrap_err();
+        assert!(error.reason.contains("15 more days"));
+
+        let mut tadpole = *error.tadpole;
+        tadpole.grow(15);
+        let mut frog = tadpole.metamorphose().unwrap();
+        assert_eq!(frog.stage_name(), "Adult");
+        assert_eq!(frog.energy(), EnergyLevel::Tired);
+        assert_eq!(frog.diet(), Diet::Insectivore);
+
+        assert_eq!(
+            frog.travel_modes(),
+            vec![TravelMode::Walk, TravelMode::Swim]
+        );
+        assert!(frog.can_clear(100));
+        assert!(frog.walk().is_ok());
+    }
+
+    #[test]
+    fn test_runtime_stage_changes_capabilities() {
+        let mut frog = AnyFrog::hatch("Polly".to_string());
+        assert!(frog.as_walker().is_none());
+        assert!(frog.as_adult_mut().is_none());
+
+        frog = frog.grow(30);
+        assert_eq!(frog.stage_name(), "Tadpole");
+
+        frog = frog.grow(30);
+        assert!(frog.is_adult());
+        assert!(frog.as_walker().is_some());
+        assert!(frog.as_jumper().is_some());
+
+        frog.set_energy(EnergyLevel::Energetic);
+        let adult = frog.as_adult_mut().unwrap();
+        assert_eq!(adult.jump().unwrap(), "Entity jumps 150 cm");
+    }
+}
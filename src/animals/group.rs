@@ -0,0 +1,408 @@
+//! Packs, Flocks and Pods
+//!
+//! A [`Group`] moves its members together at the pace of the weakest one:
+//! if any member cannot make a move, nobody moves and everyone keeps their
+//! energy. After each move, members that collapsed are split off.
+//!
+//! A [`Formation`] lets followers save energy behind the strongest member,
+//! who takes the lead for that move. Savings accumulate as a percentage of
+//! the energy spent and are paid back one level at a time.
+
+use crate::animals::{Animal, Dog, Duck, Whale};
+use crate::behaviors::{Flying, Swimming, TravelMode, Walking};
+use crate::core::{EnergyLevel, HasEnergy, HasHealth};
+use std::fmt;
+use thiserror::Error;
+
+/// A pack of dogs
+pub type Pack = Group<Dog>;
+
+/// A flock of ducks
+pub type Flock = Group<Duck>;
+
+/// A pod of whales
+pub type Pod = Group<Whale>;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum GroupError {
+    #[error("{group} has no members")]
+    Empty { group: String },
+
+    #[error("{group} cannot move: {member} cannot keep up ({reason})")]
+    MemberCannotKeepUp {
+        group: String,
+        member: String,
+        reason: String,
+    },
+}
+
+/// How a group arranges itself while moving
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Formation {
+    /// Everyone for themselves
+    #[default]
+    Loose,
+    /// Birds riding the leader's wingtip vortices
+    VFormation,
+    /// Walking or swimming in the leader's slipstream
+    Drafting,
+}
+
+impl Formation {
+    /// Share of the energy a follower saves when moving in this formation
+    pub fn saving_percent(&self, mode: TravelMode) -> u32 {
+        match (self, mode) {
+            (Formation::VFormation, TravelMode::Fly) => 25,
+            (Formation::Drafting, TravelMode::Walk | TravelMode::Swim) => 20,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Formation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Formation::Loose => "Loose",
+            Formation::VFormation => "V-Formation",
+            Formation::Drafting => "Drafting",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What happened during a group move
+#[derive(Debug)]
+pub struct GroupMove<T> {
+    /// Each member's own result, in group order
+    pub results: Vec<(String, String)>,
+    /// Members that collapsed and were split off from the group
+    pub split_off: Vec<T>,
+}
+
+impl<T: Animal> GroupMove<T> {
+    /// Names of the members that were split off
+    pub fn split_off_names(&self) -> Vec<String> {
+        self.split_off.iter().map(|member| member.name()).collect()
+    }
+}
+
+#[derive(Debug)]
+struct Member<T> {
+    animal: T,
+    /// Accumulated formation savings in percent of an energy level
+    saved: u32,
+}
+
+/// Animals that move together
+#[derive(Debug)]
+pub struct Group<T> {
+    name: String,
+    formation: Formation,
+    members: Vec<Member<T>>,
+}
+
+impl<T: Animal + HasEnergy> Group<T> {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            formation: Formation::Loose,
+            members: Vec::new(),
+        }
+    }
+
+    /// Set the formation
+    pub fn with_formation(mut self, formation: Formation) -> Self {
+        self.formation = formation;
+        self
+    }
+
+    /// Add a member
+    pub fn with_member(mut self, member: T) -> Self {
+        self.add(member);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn formation(&self) -> Formation {
+        self.formation
+    }
+
+    pub fn set_formation(&mut self, formation: Formation) {
+        self.formation = formation;
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn add(&mut self, member: T) {
+        self.members.push(Member {
+            animal: member,
+            saved: 0,
+        });
+    }
+
+    /// Remove a member by name
+    pub fn remove(&mut self, name: &str) -> Option<T> {
+        let index = self
+            .members
+            .iter()
+            .position(|member| member.animal.name() == name)?;
+        Some(self.members.remove(index).animal)
+    }
+
+    /// Get the members in group order
+    pub fn members(&self) -> impl Iterator<Item = &T> {
+        self.members.iter().map(|member| &member.animal)
+    }
+
+    /// Get the member with the least energy
+    pub fn weakest(&self) -> Option<&T> {
+        self.members().min_by_key(|member| member.energy())
+    }
+
+    /// Energy of the group, which is that of its weakest member
+    pub fn energy(&self) -> Option<EnergyLevel> {
+        self.weakest().map(|member| member.energy())
+    }
+
+    /// Split off every member that has collapsed
+    pub fn split_off_collapsed(&mut self) -> Vec<T> {
+        let (collapsed, remaining) = std::mem::take(&mut self.members)
+            .into_iter()
+            .partition(|member| member.animal.energy() == EnergyLevel::Collapsed);
+        self.members = remaining;
+        collapsed.into_iter().map(|member| member.animal).collect()
+    }
+
+    /// Move every member, or nobody if any member fails
+    fn move_together<E: fmt::Display>(
+        &mut self,
+        mode: TravelMode,
+        mut action: impl FnMut(&mut T) -> Result<String, E>,
+    ) -> Result<GroupMove<T>, GroupError> {
+        if self.members.is_empty() {
+            return Err(GroupError::Empty {
+                group: self.name.clone(),
+            });
+        }
+
+        let before: Vec<EnergyLevel> = self.members().map(|member| member.energy()).collect();
+        let mut results = Vec::with_capacity(self.members.len());
+        for index in 0..self.members.len() {
+            let member = &mut self.members[index].animal;
+            match action(member) {
+                Ok(message) => results.push((member.name(), message)),
+                Err(error) => {
+                    let member = member.name();
+                    // Nobody moves: restore everyone who already went ahead
+                    for (member, energy) in self.members.iter_mut().zip(&before) {
+                        member.animal.set_energy(*energy);
+                    }
+                    return Err(GroupError::MemberCannotKeepUp {
+                        group: self.name.clone(),
+                        member,
+                        reason: error.to_string(),
+                    });
+                }
+            }
+        }
+
+        self.apply_formation(mode, &before);
+
+        Ok(GroupMove {
+            results,
+            split_off: self.split_off_collapsed(),
+        })
+    }
+
+    /// Pay back formation savings to everyone except the leader
+    fn apply_formation(&mut self, mode: TravelMode, before: &[EnergyLevel]) {
+        let saving_percent = self.formation.saving_percent(mode);
+        if saving_percent == 0 {
+            return;
+        }
+
+        // The strongest member takes the lead (the first one on ties)
+        let leader = before
+            .iter()
+            .enumerate()
+            .max_by_key(|(index, energy)| (**energy, std::cmp::Reverse(*index)))
+            .map(|(index, _)| index);
+
+        for (index, member) in self.members.iter_mut().enumerate() {
+            if Some(index) == leader {
+                continue;
+            }
+            let spent = (before[index] as u32).saturating_sub(member.animal.energy() as u32);
+            member.saved += spent * saving_percent;
+            while member.saved >= 100 {
+                member.saved -= 100;
+                member.animal.rest();
+            }
+        }
+    }
+}
+
+impl<T: Animal + HasHealth> Group<T> {
+    /// Let every member rest, recovering energy and healing injuries
+    pub fn rest(&mut self) {
+        for member in &mut self.members {
+            member.animal.recover();
+        }
+    }
+}
+
+impl<T: Animal + Walking> Group<T> {
+    /// Walking speed of the group - the slowest member's pace
+    pub fn walking_speed(&self) -> Option<u32> {
+        self.members().map(|member| member.walking_speed()).min()
+    }
+
+    pub fn walk(&mut self) -> Result<GroupMove<T>, GroupError> {
+        self.move_together(TravelMode::Walk, |member| member.walk())
+    }
+}
+
+impl<T: Animal + Swimming> Group<T> {
+    /// Swimming speed of the group - the slowest member's pace
+    pub fn swimming_speed(&self) -> Option<u32> {
+        self.members().map(|member| member.swimming_speed()).min()
+    }
+
+    pub fn swim(&mut self) -> Result<GroupMove<T>, GroupError> {
+        self.move_together(TravelMode::Swim, |member| member.swim())
+    }
+}
+
+impl<T: Animal + Flying> Group<T> {
+    /// Flying speed of the group - the slowest member's pace
+    pub fn flying_speed(&self) -> Option<u32> {
+        self.members().map(|member| member.flying_speed()).min()
+    }
+
+    pub fn fly(&mut self) -> Result<GroupMove<T>, GroupError> {
+        self.move_together(TravelMode::Fly, |member| member.fly())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::dog::DogBreed;
+    use crate::animals::whale::WhaleSpecies;
+    use crate::core::{Mishap, Terrain};
+
+    fn dog(name: &str, breed: DogBreed, energy: EnergyLevel) -> Dog {
+        let mut dog = Dog::new(name.to_string(), breed);
+        dog.set_energy(energy);
+        dog
+    }
+
+    #[test]
+    fn test_pack_moves_at_weakest_pace() {
+        let mut pack = Pack::new("Pack".to_string())
+            .with_member(dog("Flash", DogBreed::Greyhound, EnergyLevel::Energetic))
+            .with_member(dog("Tank", DogBreed::Bulldog, EnergyLevel::Normal));
+
+        assert_eq!(pack.walking_speed(), Some(4));
+        assert_eq!(pack.energy(), Some(EnergyLevel::Normal));
+
+        let moved = pack.walk().unwrap();
+        assert_eq!(moved.results.len(), 2);
+        assert_eq!(pack.energy(), Some(EnergyLevel::Tired));
+    }
+
+    #[test]
+    fn test_resting_heals_the_pack() {
+        let mut pack = Pack::new("Pack".to_string()).with_member(dog(
+            "Flash",
+            DogBreed::Greyhound,
+            EnergyLevel::Tired,
+        ));
+        pack.members[0]
+            .animal
+            .injure(Mishap::Terrain(Terrain::Rocky));
+        assert!(pack.members().all(|dog| dog.is_injured()));
+
+        pack.rest();
+        assert!(pack.members().all(|dog| !dog.is_injured()));
+        assert_eq!(pack.energy(), Some(EnergyLevel::Normal));
+    }
+
+    #[test]
+    fn test_nobody_moves_if_one_member_cannot() {
+        let mut pod = Pod::new("Pod".to_string())
+            .with_member(Whale::new("Big".to_string(), WhaleSpecies::BlueWhale))
+            .with_member(Whale::new("Calf".to_string(), WhaleSpecies::Orca));
+        pod.members[1].animal.set_energy(EnergyLevel::Exhausted);
+        let big_energy = pod.members().next().unwrap().energy();
+
+        let error = pod.swim().unwrap_err();
+        assert!(matches!(
+            error,
+            GroupError::MemberCannotKeepUp { ref member, .. } if member == "Calf"
+        ));
+        assert_eq!(pod.members().next().unwrap().energy(), big_energy);
+    }
+
+    #[test]
+    fn test_collapsed_members_are_split_off() {
+        let mut pack = Pack::new("Pack".to_string())
+            .with_member(dog("Rex", DogBreed::Labrador, EnergyLevel::Normal))
+            .with_member(dog("Old", DogBreed::Labrador, EnergyLevel::Exhausted));
+
+        let moved = pack.walk().unwrap();
+        assert_eq!(moved.split_off_names(), vec!["Old".to_string()]);
+        assert_eq!(pack.len(), 1);
+
+        let empty = Pack::new("Nobody".to_string()).walk();
+        assert!(matches!(empty, Err(GroupError::Empty { .. })));
+    }
+
+    #[test]
+    fn test_v_formation_saves_followers_energy() {
+        let ducks = || {
+            (0..3).fold(Flock::new("Flock".to_string()), |flock, index| {
+                let mut duck = Duck::new(format!("Duck {}", index));
+                duck.set_energy(EnergyLevel::Hyperactive);
+                flock.with_member(duck)
+            })
+        };
+
+        let fly_twice = |flock: &mut Flock| {
+            flock.fly().unwrap();
+            flock.rest();
+            flock.rest();
+            flock.fly().unwrap();
+        };
+
+        let mut loose = ducks();
+        fly_twice(&mut loose);
+        assert!(
+            loose
+                .members()
+                .all(|duck| duck.energy() == EnergyLevel::Exhausted)
+        );
+
+        // Each flight costs three levels; followers save 75% of a level per flight
+        let mut flock = ducks().with_formation(Formation::VFormation);
+        fly_twice(&mut flock);
+        let energies: Vec<EnergyLevel> = flock.members().map(|duck| duck.energy()).collect();
+        assert_eq!(
+            energies,
+            vec![
+                EnergyLevel::Exhausted, // Leader
+                EnergyLevel::Tired,
+                EnergyLevel::Tired,
+            ]
+        );
+    }
+}
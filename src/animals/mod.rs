@@ -1,15 +1,25 @@
 pub mod animal;
+pub mod breed_mix;
+pub mod diet;
 pub mod dog;
 pub mod duck;
 pub mod eagle;
+pub mod frog;
+pub mod group;
 pub mod penguin;
 pub mod snake;
+pub mod species;
 pub mod whale;
 
 pub use animal::Animal;
+pub use breed_mix::{BreedMix, MixError};
+pub use diet::{Diet, Feeding, FeedingError, FeedingStation, Food, FoodKind, Stomach};
 pub use dog::Dog;
 pub use duck::Duck;
 pub use eagle::Eagle;
+pub use frog::{AnyFrog, Frog};
+pub use group::{Flock, Formation, Group, GroupError, GroupMove, Pack, Pod};
 pub use penguin::Penguin;
 pub use snake::Snake;
+pub use species::{CatalogError, SpeciesCatalog, SpeciesKind, SpeciesProfile};
 pub use whale::Whale;
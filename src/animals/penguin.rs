@@ -1,12 +1,24 @@
 use crate::animals::Animal;
+use crate::animals::diet::{Diet, Feeding, Stomach};
 use crate::behaviors::LandMove;
-use crate::behaviors::{moving::Moving, swimming::Swimming, walking::Walking};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::{
+    moving::{Moving, MovingError},
+    participant::Participant,
+    swimming::Swimming,
+    walking::Walking,
+};
+use crate::core::{
+    AirSupply, DiveState, EnergyLevel, HasEnergy, HasHealth, Health, Mishap, ThermalRange,
+    ThermalTolerance,
+};
 
 #[derive(Debug)]
 pub struct Penguin {
     name: String,
     energy: EnergyLevel,
+    dive_state: DiveState,
+    stomach: Stomach,
+    health: Health,
 }
 
 impl Penguin {
@@ -14,6 +26,9 @@ impl Penguin {
         Self {
             name,
             energy: EnergyLevel::Normal,
+            dive_state: DiveState::at_surface(),
+            stomach: Stomach::new(50),
+            health: Health::new(),
         }
     }
 }
@@ -28,6 +43,28 @@ impl Animal for Penguin {
     }
 }
 
+impl Participant for Penguin {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        25
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_walker(&self) -> Option<&dyn Walking> {
+        Some(self)
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        Some(self)
+    }
+}
+
 impl HasEnergy for Penguin {
     fn energy(&self) -> EnergyLevel {
         self.energy
@@ -38,12 +75,74 @@ impl HasEnergy for Penguin {
     }
 }
 
-impl Moving for Penguin {}
+impl Feeding for Penguin {
+    fn diet(&self) -> Diet {
+        Diet::Piscivore
+    }
+
+    fn stomach(&self) -> &Stomach {
+        &self.stomach
+    }
+
+    fn stomach_mut(&mut self) -> &mut Stomach {
+        &mut self.stomach
+    }
+}
+
+impl ThermalTolerance for Penguin {
+    fn thermal_range(&self) -> ThermalRange {
+        ThermalRange::new(-40, 15) // Cold-adapted, overheats easily
+    }
+}
+
+impl HasHealth for Penguin {
+    fn health(&self) -> &Health {
+        &self.health
+    }
+
+    fn health_mut(&mut self) -> &mut Health {
+        &mut self.health
+    }
+}
+
+impl Moving for Penguin {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn check_movement(&self) -> Result<(), MovingError> {
+        Ok(self.check_health()?)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.injure(mishap);
+    }
+}
 impl LandMove for Penguin {}
-impl Walking for Penguin {}
+impl Walking for Penguin {
+    fn walking_speed(&self) -> u32 {
+        self.health.limit(self.stomach.sated_speed(2)) // Waddling
+    }
+}
 
 impl Swimming for Penguin {
     fn max_depth(&self) -> u32 {
-        500 // Penguins are excellent divers
+        self.health.limit(500) // Penguins are excellent divers
+    }
+
+    fn swimming_speed(&self) -> u32 {
+        self.health.limit(self.stomach.sated_speed(10)) // Much faster in water than on land
+    }
+
+    fn dive_state(&self) -> DiveState {
+        self.dive_state
+    }
+
+    fn set_dive_state(&mut self, state: DiveState) {
+        self.dive_state = state;
+    }
+
+    fn air_supply(&self) -> AirSupply {
+        AirSupply::Breath { max_seconds: 1200 } // Up to 20 minutes underwater
     }
 }
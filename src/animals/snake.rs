@@ -1,22 +1,84 @@
 use crate::animals::Animal;
-use crate::behaviors::{moving::Moving, swimming::Swimming};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::animals::diet::{Diet, Feeding, Stomach};
+use crate::animals::species::SpeciesProfile;
+use crate::behaviors::{
+    moving::{Moving, MovingError},
+    participant::Participant,
+    swimming::Swimming,
+};
+use crate::core::{
+    AirSupply, DiveState, EnergyLevel, HasEnergy, HasHealth, Health, Mishap, ThermalRange,
+    ThermalTolerance,
+};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SnakeSpecies {
     Python,
     Anaconda,
     Viper,
     Cobra,
+    Other,
 }
 
 impl SnakeSpecies {
+    pub const ALL: [SnakeSpecies; 5] = [
+        SnakeSpecies::Python,
+        SnakeSpecies::Anaconda,
+        SnakeSpecies::Viper,
+        SnakeSpecies::Cobra,
+        SnakeSpecies::Other,
+    ];
+
+    /// Look up a species by its display name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|species| species.to_string() == name)
+    }
+
+    pub fn typical_weight_kg(&self) -> u32 {
+        match self {
+            SnakeSpecies::Anaconda => 70, // Heaviest snake
+            SnakeSpecies::Python => 50,
+            SnakeSpecies::Cobra => 6,
+            SnakeSpecies::Viper => 2,
+            SnakeSpecies::Other => 10,
+        }
+    }
+
     pub fn swimming_depth(&self) -> u32 {
         match self {
             SnakeSpecies::Anaconda => 80, // Excellent swimmer, semi-aquatic
             SnakeSpecies::Python => 25,   // Good swimmer when needed
             SnakeSpecies::Cobra => 8,     // Basic swimming ability
             SnakeSpecies::Viper => 0,     // Cannot swim effectively
+            SnakeSpecies::Other => 10,
+        }
+    }
+
+    /// How long this species can stay underwater, in seconds
+    pub fn breath_hold_seconds(&self) -> u32 {
+        match self {
+            SnakeSpecies::Anaconda => 600, // Ambushes prey from underwater
+            SnakeSpecies::Python => 1200,  // Slow metabolism, long breath-holds
+            SnakeSpecies::Cobra => 120,    // Short crossings only
+            SnakeSpecies::Viper => 30,     // Avoids water
+            SnakeSpecies::Other => 120,
+        }
+    }
+
+    /// Compiled-in numbers for this species
+    pub fn profile(&self) -> SpeciesProfile {
+        SpeciesProfile {
+            name: self.to_string(),
+            weight_kg: self.typical_weight_kg(),
+            walking_speed: 0,
+            swimming_speed: 2, // Slow undulating swim
+            breath_hold_seconds: self.breath_hold_seconds(),
+            max_depth: self.swimming_depth(),
+            thermal_range: ThermalRange::new(20, 40),
+            pulling_ratio_percent: 0,
+            scent_range_m: 0,
         }
     }
 }
@@ -28,6 +90,7 @@ impl std::fmt::Display for SnakeSpecies {
             SnakeSpecies::Anaconda => "Anaconda",
             SnakeSpecies::Viper => "Viper",
             SnakeSpecies::Cobra => "Cobra",
+            SnakeSpecies::Other => "Other",
         };
         write!(f, "{}", name)
     }
@@ -37,15 +100,28 @@ impl std::fmt::Display for SnakeSpecies {
 pub struct Snake {
     pub name: String,
     pub species: SnakeSpecies,
+    pub profile: SpeciesProfile,
     pub energy: EnergyLevel,
+    pub dive_state: DiveState,
+    pub stomach: Stomach,
+    pub health: Health,
 }
 
 impl Snake {
     pub fn new(name: String, species: SnakeSpecies) -> Self {
+        Self::with_profile(name, species.profile())
+    }
+
+    /// Create a snake from a species profile, such as one loaded from a species file
+    pub fn with_profile(name: String, profile: SpeciesProfile) -> Self {
         Self {
             name,
-            species,
+            species: SnakeSpecies::from_name(&profile.name).unwrap_or(SnakeSpecies::Other),
+            profile,
             energy: EnergyLevel::Normal,
+            dive_state: DiveState::at_surface(),
+            stomach: Stomach::new(100),
+            health: Health::new(),
         }
     }
 }
@@ -60,6 +136,24 @@ impl Animal for Snake {
     }
 }
 
+impl Participant for Snake {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.profile.weight_kg
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        Some(self)
+    }
+}
+
 impl HasEnergy for Snake {
     fn energy(&self) -> EnergyLevel {
         self.energy
@@ -70,11 +164,76 @@ impl HasEnergy for Snake {
     }
 }
 
-impl Moving for Snake {}
+impl Feeding for Snake {
+    fn diet(&self) -> Diet {
+        Diet::Carnivore
+    }
+
+    fn stomach(&self) -> &Stomach {
+        &self.stomach
+    }
+
+    fn stomach_mut(&mut self) -> &mut Stomach {
+        &mut self.stomach
+    }
+}
+
+impl ThermalTolerance for Snake {
+    fn thermal_range(&self) -> ThermalRange {
+        self.profile.thermal_range
+    }
+
+    fn is_ectothermic(&self) -> bool {
+        true // Cold-blooded
+    }
+}
+
+impl HasHealth for Snake {
+    fn health(&self) -> &Health {
+        &self.health
+    }
+
+    fn health_mut(&mut self) -> &mut Health {
+        &mut self.health
+    }
+}
+
+impl Moving for Snake {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn check_movement(&self) -> Result<(), MovingError> {
+        Ok(self.check_health()?)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.injure(mishap);
+    }
+}
 
 impl Swimming for Snake {
     fn max_depth(&self) -> u32 {
-        self.species.swimming_depth()
+        self.health.limit(self.profile.max_depth)
+    }
+
+    fn swimming_speed(&self) -> u32 {
+        self.health
+            .limit(self.stomach.sated_speed(self.profile.swimming_speed))
+    }
+
+    fn dive_state(&self) -> DiveState {
+        self.dive_state
+    }
+
+    fn set_dive_state(&mut self, state: DiveState) {
+        self.dive_state = state;
+    }
+
+    fn air_supply(&self) -> AirSupply {
+        AirSupply::Breath {
+            max_seconds: self.profile.breath_hold_seconds,
+        }
     }
 }
 
@@ -0,0 +1,660 @@
+//! Data-Driven Species Definitions
+//!
+//! The numbers behind every dog breed, whale species and snake species live
+//! in a [`SpeciesProfile`]. The compiled-in tables (`DogBreed::profile`,
+//! `WhaleSpecies::profile`, `SnakeSpecies::profile`) are the defaults, and a
+//! [`SpeciesCatalog`] can override them or define new breeds and species
+//! from a species file, without recompiling.
+//!
+//! # File format
+//!
+//! Species files are TOML, read with the `toml` crate. Each table is named
+//! after the kind of animal (`dog`, `whale` or `snake`) and the breed or
+//! species, quoted if the name contains spaces:
+//!
+//! ```toml
+//! # A new breed, starting from the numbers of an existing one
+//! [dog.Dachshund]
+//! base = "Bulldog"
+//! weight_kg = 9
+//! walking_speed = 5
+//!
+//! # A new species defines every field
+//! [whale.Beluga]
+//! weight_kg = 1_400
+//! swimming_speed = 10
+//! max_depth = 700
+//! breath_hold_seconds = 1_500
+//! thermal_range = [-2, 18]
+//!
+//! # Overriding a single number of a compiled-in species
+//! [whale."Blue Whale"]
+//! max_depth = 300
+//! ```
+//!
+//! | Field                   | Value                                        |
+//! |-------------------------|----------------------------------------------|
+//! | `base`                  | name of an entry of the same kind to copy    |
+//! | `weight_kg`             | whole number                                 |
+//! | `walking_speed`         | whole number in km/h, ignored unless a dog   |
+//! | `swimming_speed`        | whole number in km/h                         |
+//! | `max_depth`             | whole number in meters                       |
+//! | `breath_hold_seconds`   | whole number                                 |
+//! | `thermal_range`         | `[min, max]` in °C                           |
+//! | `pulling_ratio_percent` | percent of its weight a dog pulls in harness |
+//! | `scent_range_m`         | meters a dog picks up a trail from           |
+//!
+//! Fields left out keep the value of the entry being overridden or of the
+//! `base`; a new entry without a `base` must give all of them, though only
+//! dogs need a `walking_speed`, a `pulling_ratio_percent` and a
+//! `scent_range_m`. Numbers must be whole; a float such as `9.5` is an
+//! invalid value.
+//!
+//! # Constructors
+//!
+//! `Dog::new`, `Whale::new` and `Snake::new` always take the numbers of the
+//! compiled-in breed or species and never read a species file. Animals
+//! with numbers from a file come from the catalog ([`SpeciesCatalog::dog`],
+//! [`SpeciesCatalog::whale`], [`SpeciesCatalog::snake`]) or from an
+//! `EntityRegistry::with_catalog`.
+
+use crate::animals::dog::DogBreed;
+use crate::animals::snake::SnakeSpecies;
+use crate::animals::whale::WhaleSpecies;
+use crate::animals::{Dog, Snake, Whale};
+use crate::core::ThermalRange;
+use crate::registry::same_name;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use thiserror::Error;
+use toml::de::{DeTable, DeValue};
+
+/// Fields of a species file table; new entries without a base must define
+/// all of them, except the [`DOG_FIELDS`] for other animals
+const FIELDS: [&str; 8] = [
+    "weight_kg",
+    "walking_speed",
+    "swimming_speed",
+    "max_depth",
+    "breath_hold_seconds",
+    "thermal_range",
+    "pulling_ratio_percent",
+    "scent_range_m",
+];
+
+/// Fields only dogs need
+const DOG_FIELDS: [&str; 3] = ["walking_speed", "pulling_ratio_percent", "scent_range_m"];
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CatalogError {
+    #[error("Line {line}: {message}")]
+    Syntax { line: usize, message: String },
+
+    #[error("Line {line}: unknown kind of animal '{kind}', expected dog, whale or snake")]
+    UnknownKind { line: usize, kind: String },
+
+    #[error("Line {line}: unknown field '{field}'")]
+    UnknownField { line: usize, field: String },
+
+    #[error("Line {line}: {field} must be {expected}")]
+    InvalidValue {
+        line: usize,
+        field: String,
+        expected: &'static str,
+    },
+
+    #[error("{kind} '{name}' is missing {field}")]
+    MissingField {
+        kind: SpeciesKind,
+        name: String,
+        field: &'static str,
+    },
+
+    #[error("{kind} '{name}' is based on unknown {kind} '{base}'")]
+    UnknownBase {
+        kind: SpeciesKind,
+        name: String,
+        base: String,
+    },
+
+    #[error("No {kind} called '{name}' in the catalog")]
+    UnknownSpecies { kind: SpeciesKind, name: String },
+
+    #[error("Cannot read {path}: {message}")]
+    Io { path: String, message: String },
+}
+
+/// Kinds of animals whose numbers come from a species catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SpeciesKind {
+    Dog,
+    Whale,
+    Snake,
+}
+
+impl SpeciesKind {
+    pub const ALL: [SpeciesKind; 3] = [SpeciesKind::Dog, SpeciesKind::Whale, SpeciesKind::Snake];
+
+    /// Table prefix used in species files
+    pub fn key(&self) -> &'static str {
+        match self {
+            SpeciesKind::Dog => "dog",
+            SpeciesKind::Whale => "whale",
+            SpeciesKind::Snake => "snake",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.key() == key)
+    }
+}
+
+impl fmt::Display for SpeciesKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+/// The numbers that make up a breed or species
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpeciesProfile {
+    pub name: String,
+    pub weight_kg: u32,
+    /// Walking pace in km/h, only used by animals that walk
+    pub walking_speed: u32,
+    pub swimming_speed: u32,
+    pub max_depth: u32,
+    pub breath_hold_seconds: u32,
+    pub thermal_range: ThermalRange,
+    /// Load pulled in harness, in percent of the animal's weight; 0 if it does not pull
+    pub pulling_ratio_percent: u32,
+    /// How far the animal picks up a person's trail in meters; 0 if it does not track
+    pub scent_range_m: u32,
+}
+
+/// Breed and species profiles by kind and name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpeciesCatalog {
+    profiles: BTreeMap<(SpeciesKind, String), SpeciesProfile>,
+}
+
+impl Default for SpeciesCatalog {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl SpeciesCatalog {
+    /// Catalog of the compiled-in breeds and species
+    pub fn builtin() -> Self {
+        let mut catalog = Self {
+            profiles: BTreeMap::new(),
+        };
+        for breed in DogBreed::ALL {
+            catalog.insert(SpeciesKind::Dog, breed.profile());
+        }
+        for species in WhaleSpecies::ALL {
+            catalog.insert(SpeciesKind::Whale, species.profile());
+        }
+        for species in SnakeSpecies::ALL {
+            catalog.insert(SpeciesKind::Snake, species.profile());
+        }
+        catalog
+    }
+
+    /// Compiled-in catalog with the definitions of a species file applied
+    pub fn parse(source: &str) -> Result<Self, CatalogError> {
+        let mut catalog = Self::builtin();
+        catalog.extend_from_str(source)?;
+        Ok(catalog)
+    }
+
+    /// Compiled-in catalog with the definitions of the species file at `path` applied
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CatalogError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|error| CatalogError::Io {
+            path: path.display().to_string(),
+            message: error.to_string(),
+        })?;
+        Self::parse(&source)
+    }
+
+    /// Apply the definitions of a species file; nothing changes if it has an error
+    pub fn extend_from_str(&mut self, source: &str) -> Result<(), CatalogError> {
+        let line = |offset: usize| source[..offset].matches('\n').count() + 1;
+        let document = DeTable::parse(source).map_err(|error| CatalogError::Syntax {
+            line: line(error.span().map_or(0, |span| span.start)),
+            message: error.message().trim_end().to_string(),
+        })?;
+
+        let mut drafts = Vec::new();
+        for (kind, entries) in document.get_ref() {
+            let DeValue::Table(entries) = entries.get_ref() else {
+                return Err(CatalogError::Syntax {
+                    line: line(kind.span().start),
+                    message: "field outside of a [kind.Name] table".to_string(),
+                });
+            };
+            let kind_line = line(kind.span().start);
+            let kind =
+                SpeciesKind::from_key(kind.get_ref()).ok_or_else(|| CatalogError::UnknownKind {
+                    line: kind_line,
+                    kind: kind.get_ref().to_string(),
+                })?;
+
+            for (name, fields) in entries {
+                let DeValue::Table(fields) = fields.get_ref() else {
+                    return Err(CatalogError::Syntax {
+                        line: line(name.span().start),
+                        message: "table header must be [kind.Name]".to_string(),
+                    });
+                };
+                let mut draft = Draft {
+                    kind,
+                    name: name.get_ref().to_string(),
+                    base: None,
+                    fields: BTreeMap::new(),
+                };
+                for (key, value) in fields {
+                    draft.set(key.get_ref(), value.get_ref(), line(key.span().start))?;
+                }
+                drafts.push((name.span().start, draft));
+            }
+        }
+
+        // Entries may be based on ones defined earlier in the same file
+        drafts.sort_by_key(|(offset, _)| *offset);
+        let mut catalog = self.clone();
+        for (_, draft) in drafts {
+            draft.apply(&mut catalog)?;
+        }
+        *self = catalog;
+        Ok(())
+    }
+
+    /// Add or replace a profile
+    pub fn insert(&mut self, kind: SpeciesKind, profile: SpeciesProfile) {
+        self.profiles.insert((kind, profile.name.clone()), profile);
+    }
+
+    pub fn get(&self, kind: SpeciesKind, name: &str) -> Option<&SpeciesProfile> {
+        self.profiles.get(&(kind, name.to_string()))
+    }
+
+    /// Look up a profile by name, ignoring case and spaces ("BlueWhale" finds "Blue Whale")
+    pub fn find(&self, kind: SpeciesKind, name: &str) -> Option<&SpeciesProfile> {
+        self.get(kind, name).or_else(|| {
+            self.profiles
+                .iter()
+                .find(|((k, n), _)| *k == kind && same_name(n, name))
+                .map(|(_, profile)| profile)
+        })
+    }
+
+    /// Names of all breeds or species of a kind, in alphabetical order
+    pub fn names(&self, kind: SpeciesKind) -> Vec<&str> {
+        self.profiles
+            .keys()
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, name)| name.as_str())
+            .collect()
+    }
+
+    fn profile(&self, kind: SpeciesKind, name: &str) -> Result<SpeciesProfile, CatalogError> {
+        self.get(kind, name)
+            .cloned()
+            .ok_or_else(|| CatalogError::UnknownSpecies {
+                kind,
+                name: name.to_string(),
+            })
+    }
+
+    pub fn dog(&self, name: String, breed: &str) -> Result<Dog, CatalogError> {
+        Ok(Dog::with_profile(
+            name,
+            self.profile(SpeciesKind::Dog, breed)?,
+        ))
+    }
+
+    pub fn whale(&self, name: String, species: &str) -> Result<Whale, CatalogError> {
+        Ok(Whale::with_profile(
+            name,
+            self.profile(SpeciesKind::Whale, species)?,
+        ))
+    }
+
+    pub fn snake(&self, name: String, species: &str) -> Result<Snake, CatalogError> {
+        Ok(Snake::with_profile(
+            name,
+            self.profile(SpeciesKind::Snake, species)?,
+        ))
+    }
+}
+
+/// Writes the catalog as a species file that parses back to the same catalog
+impl fmt::Display for SpeciesCatalog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut document = toml::Table::new();
+        for ((kind, name), profile) in &self.profiles {
+            let number = |value: u32| toml::Value::Integer(value.into());
+            let range = &profile.thermal_range;
+            let fields = toml::Table::from_iter([
+                ("weight_kg".to_string(), number(profile.weight_kg)),
+                ("walking_speed".to_string(), number(profile.walking_speed)),
+                ("swimming_speed".to_string(), number(profile.swimming_speed)),
+                ("max_depth".to_string(), number(profile.max_depth)),
+                (
+                    "breath_hold_seconds".to_string(),
+                    number(profile.breath_hold_seconds),
+                ),
+                (
+                    "thermal_range".to_string(),
+                    toml::Value::Array(vec![
+                        toml::Value::Integer(range.min_c.into()),
+                        toml::Value::Integer(range.max_c.into()),
+                    ]),
+                ),
+                (
+                    "pulling_ratio_percent".to_string(),
+                    number(profile.pulling_ratio_percent),
+                ),
+                ("scent_range_m".to_string(), number(profile.scent_range_m)),
+            ]);
+
+            let entries = document
+                .entry(kind.key())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if let toml::Value::Table(entries) = entries {
+                entries.insert(name.clone(), toml::Value::Table(fields));
+            }
+        }
+        let text = toml::to_string(&document).map_err(|_| fmt::Error)?;
+        f.write_str(&text)
+    }
+}
+
+/// Checked value of a species file field
+enum Field {
+    Number(u32),
+    Range(ThermalRange),
+}
+
+/// A table of a species file, applied once the whole file is read
+struct Draft {
+    kind: SpeciesKind,
+    name: String,
+    base: Option<String>,
+    fields: BTreeMap<&'static str, Field>,
+}
+
+impl Draft {
+    fn set(&mut self, key: &str, value: &DeValue<'_>, line: usize) -> Result<(), CatalogError> {
+        let invalid = |expected| CatalogError::InvalidValue {
+            line,
+            field: key.to_string(),
+            expected,
+        };
+
+        if key == "base" {
+            let DeValue::String(base) = value else {
+                return Err(invalid("a quoted name"));
+            };
+            self.base = Some(base.to_string());
+            return Ok(());
+        }
+
+        let field = FIELDS
+            .into_iter()
+            .find(|field| *field == key)
+            .ok_or_else(|| CatalogError::UnknownField {
+                line,
+                field: key.to_string(),
+            })?;
+        let value = if field == "thermal_range" {
+            let bounds = match value {
+                DeValue::Array(items) => items
+                    .iter()
+                    .map(|item| integer(item.get_ref()).and_then(|n| i32::try_from(n).ok()))
+                    .collect::<Option<Vec<_>>>(),
+                _ => None,
+            };
+            match bounds.as_deref() {
+                Some(&[min, max]) if min <= max => Field::Range(ThermalRange::new(min, max)),
+                _ => return Err(invalid("[min, max] with min not above max")),
+            }
+        } else {
+            integer(value)
+                .and_then(|n| u32::try_from(n).ok())
+                .map(Field::Number)
+                .ok_or_else(|| invalid("a non-negative whole number"))?
+        };
+        self.fields.insert(field, value);
+        Ok(())
+    }
+
+    fn apply(self, catalog: &mut SpeciesCatalog) -> Result<(), CatalogError> {
+        let start = match &self.base {
+            Some(base) => Some(catalog.get(self.kind, base).cloned().ok_or_else(|| {
+                CatalogError::UnknownBase {
+                    kind: self.kind,
+                    name: self.name.clone(),
+                    base: base.clone(),
+                }
+            })?),
+            None => catalog.get(self.kind, &self.name).cloned(),
+        };
+        let mut profile = match start {
+            Some(profile) => profile,
+            None => {
+                let required = FIELDS
+                    .into_iter()
+                    .filter(|field| self.kind == SpeciesKind::Dog || !DOG_FIELDS.contains(field));
+                if let Some(field) = required
+                    .into_iter()
+                    .find(|field| !self.fields.contains_key(field))
+                {
+                    return Err(CatalogError::MissingField {
+                        kind: self.kind,
+                        name: self.name,
+                        field,
+                    });
+                }
+                SpeciesProfile {
+                    name: String::new(),
+                    weight_kg: 0,
+                    walking_speed: 0,
+                    swimming_speed: 0,
+                    max_depth: 0,
+                    breath_hold_seconds: 0,
+                    thermal_range: ThermalRange::new(0, 0),
+                    pulling_ratio_percent: 0,
+                    scent_range_m: 0,
+                }
+            }
+        };
+        profile.name = self.name;
+
+        for (field, value) in self.fields {
+            match (field, value) {
+                (_, Field::Range(range)) => profile.thermal_range = range,
+                ("weight_kg", Field::Number(n)) => profile.weight_kg = n,
+                ("walking_speed", Field::Number(n)) => profile.walking_speed = n,
+                ("swimming_speed", Field::Number(n)) => profile.swimming_speed = n,
+                ("max_depth", Field::Number(n)) => profile.max_depth = n,
+                ("pulling_ratio_percent", Field::Number(n)) => profile.pulling_ratio_percent = n,
+                ("scent_range_m", Field::Number(n)) => profile.scent_range_m = n,
+                (_, Field::Number(n)) => profile.breath_hold_seconds = n,
+            }
+        }
+
+        catalog.insert(self.kind, profile);
+        Ok(())
+    }
+}
+
+/// Integer value of a field, in any of the bases TOML allows
+fn integer(value: &DeValue<'_>) -> Option<i64> {
+    match value {
+        DeValue::Integer(n) => i64::from_str_radix(n.as_str(), n.radix()).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behaviors::{Participant, Pulling, Sensing, Sensor, SensorKind, Swimming};
+    use crate::core::ThermalTolerance;
+
+    const SAMPLE: &str = include_str!("../../data/species.toml");
+
+    #[test]
+    fn test_builtin_catalog_matches_compiled_tables() {
+        let catalog = SpeciesCatalog::builtin();
+        let labrador = catalog.dog("Rex".to_string(), "Labrador").unwrap();
+        let compiled = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        assert_eq!(labrador.profile, compiled.profile);
+        assert_eq!(labrador.breed, DogBreed::Labrador);
+
+        let orca = catalog.whale("Tilly".to_string(), "Orca").unwrap();
+        assert_eq!(orca.max_depth(), WhaleSpecies::Orca.max_diving_depth());
+        assert!(catalog.names(SpeciesKind::Snake).contains(&"Anaconda"));
+
+        // The catalog writes itself out as a species file
+        assert_eq!(
+            SpeciesCatalog::parse(&catalog.to_string()).unwrap(),
+            catalog
+        );
+    }
+
+    #[test]
+    fn test_sample_file_defines_new_breeds() {
+        let catalog = SpeciesCatalog::parse(SAMPLE).unwrap();
+
+        let dachshund = catalog.dog("Waldi".to_string(), "Dachshund").unwrap();
+        assert_eq!(dachshund.breed, DogBreed::Other);
+        assert_eq!(dachshund.weight_kg(), 9);
+        assert_eq!(dachshund.max_depth(), DogBreed::Bulldog.max_diving_depth()); // From its base
+
+        let newfoundland = catalog.dog("Bear".to_string(), "Newfoundland").unwrap();
+        assert!(
+            newfoundland.max_depth() > Dog::new("Rex".to_string(), DogBreed::Labrador).max_depth()
+        );
+        assert_eq!(newfoundland.thermal_range(), ThermalRange::new(-30, 25));
+        assert_eq!(newfoundland.max_pull_kg(), 65 * 150 / 100); // Pulls carts
+        assert_eq!(dachshund.max_pull_kg(), 9 * 30 / 100);
+        assert!(
+            dachshund
+                .sensors()
+                .contains(&Sensor::new(SensorKind::Scent, 900)) // Badger hound
+        );
+
+        let beluga = catalog.whale("Juno".to_string(), "Beluga").unwrap();
+        assert_eq!(beluga.species, WhaleSpecies::Other);
+        assert_eq!(beluga.max_depth(), 700);
+
+        // Overrides only touch the fields they name
+        let blue = catalog.whale("Blue".to_string(), "Blue Whale").unwrap();
+        assert_eq!(blue.max_depth(), 300);
+        assert_eq!(
+            blue.weight_kg(),
+            WhaleSpecies::BlueWhale.typical_weight_kg()
+        );
+    }
+
+    #[test]
+    fn test_species_files_accept_any_toml() {
+        let catalog = SpeciesCatalog::parse(
+            r#"
+            whale."Killer \"Orca\"" = { base = "Orca", max_depth = 0x1F4 }
+
+            [snake.Boomslang]
+            weight_kg = 1
+            swimming_speed = 1
+            max_depth = 0
+            breath_hold_seconds = 300
+            thermal_range = [
+                20, # Tree dweller
+                35,
+            ]
+            "#,
+        )
+        .unwrap();
+
+        let orca = catalog
+            .whale("Tilly".to_string(), "Killer \"Orca\"")
+            .unwrap();
+        assert_eq!(orca.max_depth(), 500);
+        let boomslang = catalog.snake("Green".to_string(), "Boomslang").unwrap();
+        assert_eq!(boomslang.thermal_range(), ThermalRange::new(20, 35));
+
+        // Names that need escapes survive being written back out
+        assert_eq!(
+            SpeciesCatalog::parse(&catalog.to_string()).unwrap(),
+            catalog
+        );
+
+        assert!(matches!(
+            SpeciesCatalog::parse("[dog.Pug]\n\nweight_kg = 9.5"),
+            Err(CatalogError::InvalidValue { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_species_file_errors() {
+        let error = |source: &str| SpeciesCatalog::parse(source).unwrap_err();
+
+        assert_eq!(
+            error("[cat.Tabby]\nweight_kg = 4"),
+            CatalogError::UnknownKind {
+                line: 1,
+                kind: "cat".to_string()
+            }
+        );
+        assert!(matches!(
+            error("[whale.Narwhal]\nweight_kg = 1_000"),
+            CatalogError::MissingField {
+                field: "swimming_speed",
+                ..
+            }
+        ));
+        assert!(matches!(
+            error("[dog.Pug]\nbase = \"Puggle\""),
+            CatalogError::UnknownBase { .. }
+        ));
+        assert!(matches!(
+            error("[dog.Husky]\nmax_depth = -3"),
+            CatalogError::InvalidValue { line: 2, .. }
+        ));
+        assert!(matches!(
+            error("[dog.Husky]\nthermal_range = [25, -40]"),
+            CatalogError::InvalidValue { line: 2, .. }
+        ));
+        assert!(matches!(
+            error("[dog.Husky]\nwings = 2"),
+            CatalogError::UnknownField { line: 2, .. }
+        ));
+        assert!(matches!(
+            error("weight_kg = 4"),
+            CatalogError::Syntax { line: 1, .. }
+        ));
+        assert!(matches!(
+            error("[dog.Saint Bernard]"),
+            CatalogError::Syntax { line: 1, .. }
+        ));
+
+        // A failed file leaves the catalog untouched
+        let mut catalog = SpeciesCatalog::builtin();
+        assert!(
+            catalog
+                .extend_from_str("[dog.Husky]\nmax_depth = 20\n[dog.Husky]\nwings = 2")
+                .is_err()
+        );
+        assert_eq!(catalog, SpeciesCatalog::builtin());
+        assert!(matches!(
+            catalog.snake("Nagini".to_string(), "Basilisk"),
+            Err(CatalogError::UnknownSpecies { .. })
+        ));
+    }
+}
@@ -1,23 +1,125 @@
 use crate::animals::Animal;
-use crate::behaviors::moving::Moving;
+use crate::animals::diet::{Diet, Feeding, Stomach};
+use crate::animals::species::SpeciesProfile;
 use crate::behaviors::swimming::Swimming;
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::{
+    moving::{Moving, MovingError},
+    participant::Participant,
+    sensing::{Sensing, Sensor, SensorKind},
+};
+use crate::core::{
+    AirSupply, DiveState, EnergyLevel, HasEnergy, HasHealth, Health, Mishap, ThermalRange,
+    ThermalTolerance,
+};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WhaleSpecies {
     BlueWhale,
     Orca,
     Dolphin,
     Humpback,
+    Other,
+}
+
+impl std::fmt::Display for WhaleSpecies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            WhaleSpecies::BlueWhale => "Blue Whale",
+            WhaleSpecies::Orca => "Orca",
+            WhaleSpecies::Dolphin => "Dolphin",
+            WhaleSpecies::Humpback => "Humpback",
+            WhaleSpecies::Other => "Other",
+        };
+        write!(f, "{}", name)
+    }
 }
 
 impl WhaleSpecies {
+    pub const ALL: [WhaleSpecies; 5] = [
+        WhaleSpecies::BlueWhale,
+        WhaleSpecies::Orca,
+        WhaleSpecies::Dolphin,
+        WhaleSpecies::Humpback,
+        WhaleSpecies::Other,
+    ];
+
+    /// Look up a species by its display name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|species| species.to_string() == name)
+    }
+
+    pub fn typical_weight_kg(&self) -> u32 {
+        match self {
+            WhaleSpecies::BlueWhale => 150_000, // Largest animal ever
+            WhaleSpecies::Humpback => 30_000,
+            WhaleSpecies::Orca => 5_000,
+            WhaleSpecies::Dolphin => 200,
+            WhaleSpecies::Other => 1_000,
+        }
+    }
+
     pub fn max_diving_depth(&self) -> u32 {
         match self {
             WhaleSpecies::Humpback => 200,  // Moderate diver
             WhaleSpecies::BlueWhale => 500, // Surface feeder
             WhaleSpecies::Orca => 300,      // Medium depth
             WhaleSpecies::Dolphin => 150,   // Shallow water
+            WhaleSpecies::Other => 200,
+        }
+    }
+
+    /// Echolocation range in meters, for the toothed whales that have it
+    pub fn sonar_range_m(&self) -> Option<u32> {
+        match self {
+            WhaleSpecies::Orca => Some(2000),
+            WhaleSpecies::Dolphin => Some(800),
+            WhaleSpecies::BlueWhale | WhaleSpecies::Humpback | WhaleSpecies::Other => None,
+        }
+    }
+
+    /// How long this species can stay underwater, in seconds
+    pub fn breath_hold_seconds(&self) -> u32 {
+        match self {
+            WhaleSpecies::Humpback => 2700,  // Up to 45 minutes
+            WhaleSpecies::BlueWhale => 1800, // Up to 30 minutes
+            WhaleSpecies::Orca => 900,       // Up to 15 minutes
+            WhaleSpecies::Dolphin => 480,    // Up to 8 minutes
+            WhaleSpecies::Other => 600,
+        }
+    }
+
+    /// Water temperatures the species is comfortable in
+    pub fn thermal_range(&self) -> ThermalRange {
+        match self {
+            WhaleSpecies::Orca | WhaleSpecies::Humpback | WhaleSpecies::Other => {
+                ThermalRange::new(-2, 28) // Polar to tropical
+            }
+            WhaleSpecies::BlueWhale => ThermalRange::new(-2, 20),
+            WhaleSpecies::Dolphin => ThermalRange::new(10, 32), // Warm waters
+        }
+    }
+
+    pub fn cruising_speed(&self) -> u32 {
+        match self {
+            WhaleSpecies::Orca | WhaleSpecies::Dolphin => 30, // Fast hunters
+            _ => 15,                                          // Great whales cruise slowly
+        }
+    }
+
+    /// Compiled-in numbers for this species
+    pub fn profile(&self) -> SpeciesProfile {
+        SpeciesProfile {
+            name: self.to_string(),
+            weight_kg: self.typical_weight_kg(),
+            walking_speed: 0,
+            swimming_speed: self.cruising_speed(),
+            breath_hold_seconds: self.breath_hold_seconds(),
+            max_depth: self.max_diving_depth(),
+            thermal_range: self.thermal_range(),
+            pulling_ratio_percent: 0,
+            scent_range_m: 0,
         }
     }
 }
@@ -26,15 +128,28 @@ impl WhaleSpecies {
 pub struct Whale {
     pub name: String,
     pub species: WhaleSpecies,
+    pub profile: SpeciesProfile,
     pub energy: EnergyLevel,
+    pub dive_state: DiveState,
+    pub stomach: Stomach,
+    pub health: Health,
 }
 
 impl Whale {
     pub fn new(name: String, species: WhaleSpecies) -> Self {
+        Self::with_profile(name, species.profile())
+    }
+
+    /// Create a whale from a species profile, such as one loaded from a species file
+    pub fn with_profile(name: String, profile: SpeciesProfile) -> Self {
         Self {
             name,
-            species,
+            species: WhaleSpecies::from_name(&profile.name).unwrap_or(WhaleSpecies::Other),
+            profile,
             energy: EnergyLevel::Normal,
+            dive_state: DiveState::at_surface(),
+            stomach: Stomach::new(100),
+            health: Health::new(),
         }
     }
 }
@@ -49,6 +164,39 @@ impl Animal for Whale {
     }
 }
 
+impl Participant for Whale {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.profile.weight_kg
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        Some(self)
+    }
+
+    fn as_sensor(&self) -> Option<&dyn Sensing> {
+        Some(self)
+    }
+}
+
+impl Sensing for Whale {
+    fn sensors(&self) -> Vec<Sensor> {
+        // Eyes are of little use in murky water
+        let mut sensors = vec![Sensor::new(SensorKind::Vision, 50)];
+        if let Some(range_m) = self.species.sonar_range_m() {
+            sensors.push(Sensor::new(SensorKind::Sonar, range_m));
+        }
+        sensors
+    }
+}
+
 impl HasEnergy for Whale {
     fn energy(&self) -> EnergyLevel {
         self.energy
@@ -59,11 +207,72 @@ impl HasEnergy for Whale {
     }
 }
 
-impl Moving for Whale {}
+impl Feeding for Whale {
+    fn diet(&self) -> Diet {
+        Diet::Piscivore
+    }
+
+    fn stomach(&self) -> &Stomach {
+        &self.stomach
+    }
+
+    fn stomach_mut(&mut self) -> &mut Stomach {
+        &mut self.stomach
+    }
+}
+
+impl ThermalTolerance for Whale {
+    fn thermal_range(&self) -> ThermalRange {
+        self.profile.thermal_range
+    }
+}
+
+impl HasHealth for Whale {
+    fn health(&self) -> &Health {
+        &self.health
+    }
+
+    fn health_mut(&mut self) -> &mut Health {
+        &mut self.health
+    }
+}
+
+impl Moving for Whale {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn check_movement(&self) -> Result<(), MovingError> {
+        Ok(self.check_health()?)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.injure(mishap);
+    }
+}
 
 impl Swimming for Whale {
     fn max_depth(&self) -> u32 {
-        self.species.max_diving_depth()
+        self.health.limit(self.profile.max_depth)
+    }
+
+    fn swimming_speed(&self) -> u32 {
+        self.health
+            .limit(self.stomach.sated_speed(self.profile.swimming_speed))
+    }
+
+    fn dive_state(&self) -> DiveState {
+        self.dive_state
+    }
+
+    fn set_dive_state(&mut self, state: DiveState) {
+        self.dive_state = state;
+    }
+
+    fn air_supply(&self) -> AirSupply {
+        AirSupply::Breath {
+            max_seconds: self.profile.breath_hold_seconds,
+        }
     }
 }
 
@@ -80,4 +289,19 @@ mod tests {
         assert!(blue_whale.max_depth() > humpback.max_depth());
         assert!(humpback.max_depth() > dolphin.max_depth());
     }
+
+    #[test]
+    fn test_whale_dive_and_surface() {
+        let mut orca = Whale::new("Tilly".to_string(), WhaleSpecies::Orca);
+        orca.set_energy(EnergyLevel::Hyperactive);
+
+        assert!(orca.dive(300).is_ok());
+        assert_eq!(orca.current_depth(), 300);
+        assert!(orca.stay_submerged(600).is_ok());
+        assert!(orca.needs_to_surface());
+
+        assert!(orca.surface().is_ok());
+        assert!(!orca.is_submerged());
+        assert_eq!(orca.remaining_air(), 900);
+    }
 }
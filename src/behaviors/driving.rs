@@ -1,6 +1,6 @@
 use crate::behaviors::land_move::LandMove;
-use crate::behaviors::moving::MovingError;
-use crate::core::{EnergyLevel, Terrain};
+use crate::behaviors::moving::{Medium, MovingError};
+use crate::core::{EnergyLevel, Mishap, Terrain};
 use thiserror::Error;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -63,6 +63,22 @@ impl RoadType {
         matches!(self, RoadType::OffRoad | RoadType::ExtremeOff)
     }
 
+    /// Get the road type a vehicle faces when driving on a terrain, if any
+    pub fn for_terrain(terrain: Terrain) -> Option<RoadType> {
+        if !terrain.vehicle_accessible() {
+            return None;
+        }
+
+        match terrain {
+            Terrain::Road | Terrain::Pavement | Terrain::Sidewalk => Some(RoadType::Highway),
+            Terrain::Grass | Terrain::Dirt => Some(RoadType::Country),
+            Terrain::Gravel => Some(RoadType::OffRoad),
+            Terrain::Sand | Terrain::Rocky => Some(RoadType::OffRoad),
+            Terrain::Snow => Some(RoadType::Mountain),
+            _ => None,
+        }
+    }
+
     /// Get all road types
     pub fn all_road_types() -> Vec<RoadType> {
         vec![
@@ -143,10 +159,26 @@ pub enum DrivingError {
 
     #[error("Vehicle not capable of {terrain} terrain")]
     TerrainNotSupported { terrain: Terrain },
+
+    /// The vehicle drove onto hazardous terrain and was damaged before turning back
+    #[error("Vehicle damaged on {terrain} terrain")]
+    TerrainDamage { terrain: Terrain },
+
+    #[error("Cannot drive in {mode} mode")]
+    WrongMode { mode: &'static str },
+
+    #[error("Crew not fit to drive: {reason}")]
+    CrewNotFit { reason: String },
 }
 
 pub type DrivingResult = Result<String, DrivingError>;
 
+/// Distance assumed for a drive whose length is not given
+const STANDARD_LEG_KM: u32 = 10;
+
+/// Speed assumed for a drive whose speed is not given
+const CRUISING_SPEED: u32 = 80;
+
 /// Driving capability - uses LandMove as foundation and adds vehicle-specific features
 pub trait Driving: LandMove {
     /// Maximum driving speed in km/h - varies by implementation
@@ -167,8 +199,25 @@ pub trait Driving: LandMove {
         3 // Default: moderate driving skill (1-5 scale)
     }
 
+    /// Check vehicle-specific preconditions before any driving action
+    fn check_driving_conditions(&self) -> Result<(), DrivingError> {
+        Ok(()) // Default: always ready to drive
+    }
+
+    /// Record wear after a completed leg - vehicles may break down here
+    fn apply_wear(
+        &mut self,
+        _distance_km: u32,
+        _speed_kmh: u32,
+        _road_type: RoadType,
+    ) -> Result<(), DrivingError> {
+        Ok(()) // Default: no wear
+    }
+
     /// Basic driving - uses land_move
     fn drive(&mut self) -> DrivingResult {
+        self.check_driving_conditions()?;
+
         // Driving is basic land movement for vehicles
         match self.land_move() {
             Ok(_) => Ok("Entity drives".to_string()),
@@ -181,12 +230,16 @@ pub trait Driving: LandMove {
                     // Convert to generic movement error
                     return Err(DrivingError::InsufficientEnergyForDriving { required, current });
                 }
+                crate::behaviors::land_move::LandMoveError::TerrainNotWalkable { terrain } => {
+                    return Err(DrivingError::TerrainNotSupported { terrain });
+                }
             })),
         }
     }
 
     /// Drive at specific speed
     fn drive_at_speed(&mut self, target_speed: u32) -> DrivingResult {
+        self.check_driving_conditions()?;
         let current_energy = self.energy();
 
         // Check speed limits
@@ -223,14 +276,15 @@ pub trait Driving: LandMove {
         self.consume_energy_levels(speed_energy_cost);
 
         // Use basic movement for the driving motion
-        match self.do_move() {
-            Ok(_) => Ok(format!("Entity drives at {} km/h", target_speed)),
-            Err(movement_error) => Err(DrivingError::MovementError(movement_error)),
-        }
+        self.do_move_in(Medium::Land)?;
+        self.apply_wear(STANDARD_LEG_KM, target_speed, RoadType::Highway)?;
+
+        Ok(format!("Entity drives at {} km/h", target_speed))
     }
 
     /// Driving on different road types - now uses RoadType enum
     fn drive_on_road(&mut self, road_type: RoadType) -> DrivingResult {
+        self.check_driving_conditions()?;
         let current_energy = self.energy();
         let required_energy = road_type.required_energy_level();
 
@@ -277,40 +331,43 @@ pub trait Driving: LandMove {
         self.consume_energy_levels(road_energy_cost);
 
         // Use basic driving as foundation
-        match self.drive() {
-            Ok(_) => Ok(format!(
-                "Entity drives on {} roads ({})",
-                road_type,
-                road_type.description()
-            )),
-            Err(driving_error) => Err(driving_error),
-        }
+        self.drive()?;
+        self.apply_wear(STANDARD_LEG_KM, CRUISING_SPEED, road_type)?;
+
+        Ok(format!(
+            "Entity drives on {} roads ({})",
+            road_type,
+            road_type.description()
+        ))
     }
 
     /// Drive on terrain (converts Terrain to appropriate driving context)
+    ///
+    /// Terrain no vehicle can cross is refused with `TerrainNotSupported`,
+    /// without harm. Hazardous terrain such as a volcano is entered before
+    /// the vehicle has to turn back: it suffers the mishap and the drive
+    /// fails with `TerrainDamage`.
     fn drive_on_terrain(&mut self, terrain: Terrain) -> DrivingResult {
-        // Check if terrain is suitable for vehicles
-        if !terrain.vehicle_accessible() {
-            return Err(DrivingError::TerrainNotSupported { terrain });
-        }
-
         // Convert terrain to road type for driving context
-        let road_type = match terrain {
-            Terrain::Road | Terrain::Pavement | Terrain::Sidewalk => RoadType::Highway,
-            Terrain::Grass | Terrain::Dirt => RoadType::Country,
-            Terrain::Gravel => RoadType::OffRoad,
-            Terrain::Sand | Terrain::Rocky => RoadType::OffRoad,
-            Terrain::Snow => RoadType::Mountain,
-            _ => {
-                return Err(DrivingError::TerrainNotSupported { terrain });
+        match RoadType::for_terrain(terrain) {
+            Some(road_type) => {
+                let temperature_c = terrain.temperature_c();
+                self.check_ambient(temperature_c)?;
+                let result = self.drive_on_road(road_type)?;
+                self.endure_ambient(temperature_c)?;
+                Ok(result)
             }
-        };
-
-        self.drive_on_road(road_type)
+            None if !terrain.walkable() => {
+                self.suffer(Mishap::Terrain(terrain));
+                Err(DrivingError::TerrainDamage { terrain })
+            }
+            None => Err(DrivingError::TerrainNotSupported { terrain }),
+        }
     }
 
     /// Long distance driving with fuel efficiency considerations
     fn drive_distance(&mut self, distance_km: u32) -> DrivingResult {
+        self.check_driving_conditions()?;
         let current_energy = self.energy();
         let efficiency = self.fuel_efficiency();
 
@@ -327,13 +384,13 @@ pub trait Driving: LandMove {
         // Consume calculated energy
         self.consume_energy_levels(energy_needed as u8);
 
-        match self.do_move() {
-            Ok(_) => Ok(format!(
-                "Entity drives {} km (efficiency: {} km per energy level)",
-                distance_km, efficiency
-            )),
-            Err(movement_error) => Err(DrivingError::MovementError(movement_error)),
-        }
+        self.do_move_in(Medium::Land)?;
+        self.apply_wear(distance_km, CRUISING_SPEED, RoadType::Highway)?;
+
+        Ok(format!(
+            "Entity drives {} km (efficiency: {} km per energy level)",
+            distance_km, efficiency
+        ))
     }
 
     /// Get available road types for current energy level and capabilities
@@ -547,6 +604,56 @@ mod tests {
         assert!(!available.contains(&RoadType::OffRoad)); // No off-road capability
     }
 
+    #[derive(Debug)]
+    struct WornVehicle {
+        energy: EnergyLevel,
+    }
+
+    impl HasEnergy for WornVehicle {
+        fn energy(&self) -> EnergyLevel {
+            self.energy
+        }
+        fn set_energy(&mut self, level: EnergyLevel) {
+            self.energy = level;
+        }
+    }
+
+    impl Moving for WornVehicle {}
+    impl LandMove for WornVehicle {}
+
+    impl Driving for WornVehicle {
+        fn max_speed(&self) -> u32 {
+            120
+        }
+
+        fn apply_wear(
+            &mut self,
+            distance_km: u32,
+            _speed_kmh: u32,
+            _road_type: RoadType,
+        ) -> Result<(), DrivingError> {
+            Err(DrivingError::MechanicalFailure {
+                reason: format!("gearbox failed after {} km", distance_km),
+            })
+        }
+    }
+
+    #[test]
+    fn test_wear_can_cause_breakdown() {
+        let mut vehicle = WornVehicle {
+            energy: EnergyLevel::Hyperactive,
+        };
+
+        assert!(vehicle.drive().is_ok()); // Basic driving records no leg
+        vehicle.set_energy(EnergyLevel::Hyperactive);
+
+        let result = vehicle.drive_distance(100);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Mechanical failure: gearbox failed after 100 km"
+        );
+    }
+
     #[test]
     fn test_max_challenge_driving() {
         let mut vehicle = TestVehicle {
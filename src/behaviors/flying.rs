@@ -1,5 +1,7 @@
-use crate::behaviors::moving::{Moving, MovingError};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::moving::{Medium, Moving, MovingError};
+use crate::core::temperature::MILD_TEMPERATURE_C;
+use crate::core::{EnergyLevel, HasEnergy, Mishap, Weather};
+use std::fmt;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,17 +20,72 @@ pub enum FlyingError {
         requested_altitude: u32,
         max_altitude: u32,
     },
+
+    #[error("Crew not fit to fly: {reason}")]
+    CrewNotFit { reason: String },
+
+    #[error("Flying is not safe in {weather} weather")]
+    UnsafeWeather { weather: Weather },
+
+    #[error("Mechanical failure: {reason}")]
+    MechanicalFailure { reason: String },
 }
 
 pub type FlyingResult = Result<String, FlyingError>;
 
+/// What kind of traffic a flyer is, as air traffic control sees it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlightCategory {
+    Bird,
+    Rotorcraft,
+    /// Small fixed-wing aircraft flying below the airways
+    LightAircraft,
+    /// Jets cruising in the airways - airliners, freighters and fighters
+    Airliner,
+}
+
+impl fmt::Display for FlightCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FlightCategory::Bird => "Bird",
+            FlightCategory::Rotorcraft => "Rotorcraft",
+            FlightCategory::LightAircraft => "Light Aircraft",
+            FlightCategory::Airliner => "Airliner",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// Flying capability - depends on Moving trait and energy
 pub trait Flying: Moving + HasEnergy {
     /// Maximum flying altitude in meters - varies by implementation
     fn max_altitude(&self) -> u32;
 
+    /// Cruising flying speed in km/h
+    fn flying_speed(&self) -> u32 {
+        40 // Default: small bird
+    }
+
+    /// Kind of traffic this flyer counts as
+    fn flight_category(&self) -> FlightCategory {
+        FlightCategory::Bird // Default: small bird
+    }
+
+    /// Check entity-specific preconditions before any flight
+    fn check_flying_conditions(&self) -> Result<(), FlyingError> {
+        Ok(()) // Default: always ready to fly
+    }
+
+    /// Record a completed flight, e.g. to tire the crew or wear the airframe
+    fn log_flight(&mut self) -> Result<(), FlyingError> {
+        Ok(())
+    }
+
     /// Basic flying
     fn fly(&mut self) -> FlyingResult {
+        self.check_flying_conditions()?;
+        self.check_movement()?;
+        self.check_medium(Medium::Air)?;
         let current_energy = self.energy();
 
         // Flying requires at least Normal energy level
@@ -44,14 +101,34 @@ pub trait Flying: Moving + HasEnergy {
         self.consume_energy_levels(2);
 
         // Use basic movement as foundation
-        match self.do_move() {
-            Ok(_) => Ok("Entity flies".to_string()),
-            Err(movement_error) => Err(FlyingError::MovementError(movement_error)),
+        self.do_move_in(Medium::Air)?;
+        self.log_flight()?;
+
+        Ok("Entity flies".to_string())
+    }
+
+    /// Flying through weather - rough weather costs extra, unsafe weather hurts
+    fn fly_in_weather(&mut self, weather: Weather) -> FlyingResult {
+        if !weather.is_safe_for_flying() {
+            self.suffer(Mishap::Weather(weather));
+            return Err(FlyingError::UnsafeWeather { weather });
         }
+
+        // Aloft the ground does not matter, only what the weather does to mild air
+        let temperature_c = MILD_TEMPERATURE_C + weather.temperature_shift_c();
+        self.check_ambient(temperature_c)?;
+
+        let result = self.fly()?;
+        self.consume_energy_levels(weather.energy_cost());
+        self.endure_ambient(temperature_c)?;
+        Ok(format!("{} through {} weather", result, weather))
     }
 
     /// Flying to specific altitude
     fn fly_to_altitude(&mut self, target_altitude: u32) -> FlyingResult {
+        self.check_flying_conditions()?;
+        self.check_movement()?;
+        self.check_medium(Medium::Air)?;
         let current_energy = self.energy();
 
         // Check altitude limits
@@ -75,10 +152,10 @@ pub trait Flying: Moving + HasEnergy {
         self.consume_energy_levels(3);
 
         // Use basic movement for the flying motion
-        match self.do_move() {
-            Ok(_) => Ok(format!("Entity flies to {}m altitude", target_altitude)),
-            Err(movement_error) => Err(FlyingError::MovementError(movement_error)),
-        }
+        self.do_move_in(Medium::Air)?;
+        self.log_flight()?;
+
+        Ok(format!("Entity flies to {}m altitude", target_altitude))
     }
 }
 
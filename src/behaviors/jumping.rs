@@ -0,0 +1,18 @@
+use crate::behaviors::land_move::{LandMove, LandMoveResult};
+
+/// Jumping capability - a burst of fast land movement
+pub trait Jumping: LandMove {
+    /// Longest jump in centimeters
+    fn jump_distance_cm(&self) -> u32;
+
+    /// Jump as far as possible - uses land_move_fast
+    fn jump(&mut self) -> LandMoveResult {
+        self.land_move_fast()?;
+        Ok(format!("Entity jumps {} cm", self.jump_distance_cm()))
+    }
+
+    /// Check if a gap can be cleared in a single jump
+    fn can_clear(&self, gap_cm: u32) -> bool {
+        gap_cm <= self.jump_distance_cm()
+    }
+}
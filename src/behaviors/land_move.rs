@@ -3,8 +3,8 @@
 //! This module defines the LandMove trait which serves as the base
 //! for both walking and driving capabilities.
 
-use crate::behaviors::moving::{Moving, MovingError};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::moving::{Medium, Moving, MovingError};
+use crate::core::{EnergyLevel, HasEnergy, Terrain};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -17,6 +17,9 @@ pub enum LandMoveError {
         required: EnergyLevel,
         current: EnergyLevel,
     },
+
+    #[error("{terrain} terrain cannot be crossed on foot")]
+    TerrainNotWalkable { terrain: Terrain },
 }
 
 pub type LandMoveResult = Result<String, LandMoveError>;
@@ -26,6 +29,8 @@ pub type LandMoveResult = Result<String, LandMoveError>;
 pub trait LandMove: Moving + HasEnergy {
     /// Basic land movement
     fn land_move(&mut self) -> LandMoveResult {
+        self.check_movement()?;
+        self.check_medium(Medium::Land)?;
         let current_energy = self.energy();
 
         // Land movement requires at least Exhausted energy level
@@ -38,7 +43,7 @@ pub trait LandMove: Moving + HasEnergy {
         }
 
         // Use basic movement as foundation
-        match self.do_move() {
+        match self.do_move_in(Medium::Land) {
             Ok(_) => Ok("Entity moves on land".to_string()),
             Err(movement_error) => Err(LandMoveError::MovementError(movement_error)),
         }
@@ -46,6 +51,8 @@ pub trait LandMove: Moving + HasEnergy {
 
     /// Fast land movement
     fn land_move_fast(&mut self) -> LandMoveResult {
+        self.check_movement()?;
+        self.check_medium(Medium::Land)?;
         let current_energy = self.energy();
 
         // Fast movement requires at least Normal energy level
@@ -58,7 +65,7 @@ pub trait LandMove: Moving + HasEnergy {
         }
 
         // Use basic movement as foundation first
-        match self.do_move() {
+        match self.do_move_in(Medium::Land) {
             Ok(_) => {
                 // Fast movement consumes extra energy after movement
                 self.consume_energy();
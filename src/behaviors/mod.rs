@@ -1,12 +1,20 @@
 pub mod driving;
 pub mod flying;
+pub mod jumping;
 pub mod land_move;
 pub mod moving;
+pub mod participant;
+pub mod pulling;
+pub mod sensing;
 pub mod swimming;
 pub mod walking;
 
 pub use driving::Driving;
-pub use flying::Flying;
+pub use flying::{FlightCategory, Flying};
+pub use jumping::Jumping;
 pub use land_move::LandMove;
+pub use participant::{Participant, TravelMode};
+pub use pulling::Pulling;
+pub use sensing::{Sensing, Sensor, SensorKind, UNAIDED_VISION_M};
 pub use swimming::Swimming;
 pub use walking::Walking;
@@ -1,6 +1,27 @@
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::core::{EnergyLevel, HasEnergy, Mishap, ThermalTolerance};
+use crate::errors::activity_error::ActivityError;
+use std::fmt;
 use thiserror::Error;
 
+/// What an entity is moving through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Medium {
+    Land,
+    Water,
+    Air,
+}
+
+impl fmt::Display for Medium {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Medium::Land => "land",
+            Medium::Water => "water",
+            Medium::Air => "air",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum MovingError {
     #[error("Cannot move: entity is collapsed (energy: {current})")]
@@ -14,14 +35,58 @@ pub enum MovingError {
 
     #[error("Movement blocked: {reason}")]
     MovementBlocked { reason: String },
+
+    #[error("Cannot move on {medium} in {mode} mode")]
+    WrongMode { mode: &'static str, medium: Medium },
+
+    #[error("{0}")]
+    Unfit(#[from] ActivityError),
 }
 
 pub type MovingResult = Result<String, MovingError>;
 
 /// Basic movement capability that depends on energy
 pub trait Moving: HasEnergy {
+    /// Check entity-specific fitness before any movement, e.g. injuries
+    fn check_movement(&self) -> Result<(), MovingError> {
+        Ok(()) // Default: always fit to move
+    }
+
+    /// Check the entity can move through `medium` as it is, e.g. an amphibious
+    /// vehicle in the matching mode
+    fn check_medium(&self, _medium: Medium) -> Result<(), MovingError> {
+        Ok(()) // Default: moves through anything it has a behavior for
+    }
+
+    /// React to a failed risky activity - injury for animals, damage for vehicles
+    fn suffer(&mut self, _mishap: Mishap) {}
+
+    /// Comfort range, for entities that feel the heat and the cold
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        None // Default: indifferent to temperature
+    }
+
+    /// Refuse an ambient temperature far outside the comfort range, without spending energy
+    fn check_ambient(&self, temperature_c: i32) -> Result<(), MovingError> {
+        if let Some(thermal) = self.thermal_tolerance() {
+            thermal.check_temperature(temperature_c)?;
+        }
+        Ok(())
+    }
+
+    /// Spend the extra energy an ambient temperature costs, like [`ThermalTolerance::endure`]
+    fn endure_ambient(&mut self, temperature_c: i32) -> Result<u8, MovingError> {
+        self.check_ambient(temperature_c)?;
+        let cost = self
+            .thermal_tolerance()
+            .map_or(0, |thermal| thermal.exposure(temperature_c).energy_cost());
+        self.consume_energy_levels(cost);
+        Ok(cost)
+    }
+
     /// Perform basic movement - requires at least Exhausted energy level
     fn do_move(&mut self) -> MovingResult {
+        self.check_movement()?;
         let current_energy = self.energy();
 
         // Check if collapsed - cannot move at all
@@ -56,6 +121,12 @@ pub trait Moving: HasEnergy {
         Ok(format!("Entity {}", movement_description))
     }
 
+    /// Perform basic movement through `medium`, see [`Moving::check_medium`]
+    fn do_move_in(&mut self, medium: Medium) -> MovingResult {
+        self.check_medium(medium)?;
+        self.do_move()
+    }
+
     /// Check if movement is currently possible
     fn can_move(&self) -> bool {
         self.energy() > EnergyLevel::Collapsed
@@ -0,0 +1,127 @@
+use crate::behaviors::{Driving, Flying, Jumping, Sensing, Swimming, Walking};
+use crate::core::{HasEnergy, ThermalTolerance};
+use crate::vehicles::Carrier;
+use crate::world::infrastructure::UsesInfrastructure;
+use std::fmt;
+
+/// Ways an entity can get from one place to another
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TravelMode {
+    Walk,
+    Drive,
+    Swim,
+    Fly,
+}
+
+impl TravelMode {
+    /// Get all travel modes
+    pub fn all_modes() -> Vec<TravelMode> {
+        vec![
+            TravelMode::Walk,
+            TravelMode::Drive,
+            TravelMode::Swim,
+            TravelMode::Fly,
+        ]
+    }
+}
+
+impl fmt::Display for TravelMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TravelMode::Walk => "Walk",
+            TravelMode::Drive => "Drive",
+            TravelMode::Swim => "Swim",
+            TravelMode::Fly => "Fly",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Any animal or vehicle that can take part in routes, races and missions
+///
+/// Behaviors are compile-time traits; this trait exposes them at runtime so
+/// code holding a `&dyn Participant` can ask what the entity is able to do.
+/// Each accessor returns `None` unless the entity implements that behavior.
+pub trait Participant: HasEnergy + fmt::Debug {
+    /// Display name of the participant
+    fn participant_name(&self) -> String;
+
+    /// Weight in kg, used when the participant is carried by a vehicle
+    fn weight_kg(&self) -> u32;
+
+    fn as_walker(&self) -> Option<&dyn Walking> {
+        None
+    }
+
+    fn as_driver(&self) -> Option<&dyn Driving> {
+        None
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        None
+    }
+
+    fn as_flyer(&self) -> Option<&dyn Flying> {
+        None
+    }
+
+    /// Jumping is not a travel mode, but lets a participant clear gaps
+    fn as_jumper(&self) -> Option<&dyn Jumping> {
+        None
+    }
+
+    /// Operating temperatures, if the participant is sensitive to them
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        None
+    }
+
+    /// Sonar, vision or radar, if the participant can detect others at a distance
+    fn as_sensor(&self) -> Option<&dyn Sensing> {
+        None
+    }
+
+    /// Room on board for others, if the participant is a carrier
+    fn as_carrier(&self) -> Option<&dyn Carrier> {
+        None
+    }
+
+    /// Facilities the participant needs, if it depends on infrastructure
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        None
+    }
+
+    /// Energy levels spent changing into travel mode `to`
+    ///
+    /// `from` is the mode of the previous leg, or `None` when setting out.
+    fn mode_switch_energy(&self, _from: Option<TravelMode>, _to: TravelMode) -> u32 {
+        0 // Default: modes are always ready
+    }
+
+    /// Check if the participant supports a travel mode
+    fn can_travel(&self, mode: TravelMode) -> bool {
+        match mode {
+            TravelMode::Walk => self.as_walker().is_some(),
+            TravelMode::Drive => self.as_driver().is_some(),
+            TravelMode::Swim => self.as_swimmer().is_some(),
+            TravelMode::Fly => self.as_flyer().is_some(),
+        }
+    }
+
+    /// Get all travel modes the participant supports
+    fn travel_modes(&self) -> Vec<TravelMode> {
+        TravelMode::all_modes()
+            .into_iter()
+            .filter(|mode| self.can_travel(*mode))
+            .collect()
+    }
+
+    /// Cruising speed in km/h for a travel mode, if supported
+    fn cruising_speed(&self, mode: TravelMode) -> Option<u32> {
+        match mode {
+            TravelMode::Walk => self.as_walker().map(|walker| walker.walking_speed()),
+            TravelMode::Drive => self.as_driver().map(|driver| driver.max_speed()),
+            TravelMode::Swim => self.as_swimmer().map(|swimmer| swimmer.swimming_speed()),
+            TravelMode::Fly => self.as_flyer().map(|flyer| flyer.flying_speed()),
+        }
+    }
+}
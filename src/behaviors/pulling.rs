@@ -0,0 +1,95 @@
+//! Pulling Trait
+//!
+//! Draft animals pull loads behind them. What an animal can pull is its own
+//! business; how much a stage of pulling costs depends on the load compared
+//! to that capacity and on the ground underfoot.
+
+use crate::behaviors::moving::{Medium, MovingError};
+use crate::behaviors::walking::Walking;
+use crate::core::{EnergyLevel, Terrain};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PullingError {
+    #[error("Cannot pull: {0}")]
+    MovementError(#[from] MovingError),
+
+    #[error("Load of {load_kg} kg is more than the {capacity_kg} kg that can be pulled")]
+    Overloaded { load_kg: u32, capacity_kg: u32 },
+
+    #[error("Insufficient energy for pulling: need at least {required}, have {current}")]
+    InsufficientEnergyForPulling {
+        required: EnergyLevel,
+        current: EnergyLevel,
+    },
+
+    #[error("{terrain} terrain is too treacherous to pull a load across")]
+    TerrainNotPullable { terrain: Terrain },
+}
+
+pub type PullingResult = Result<String, PullingError>;
+
+/// Pulling capability - draft animals that walk in harness
+pub trait Pulling: Walking {
+    /// Heaviest load in kg that can be pulled over a long distance
+    fn max_pull_kg(&self) -> u32;
+
+    /// Pace in km/h while pulling - walking speed unloaded, half of it at full load
+    fn pulling_speed(&self, load_kg: u32) -> u32 {
+        let capacity = self.max_pull_kg().max(1) as u64;
+        let load = (load_kg as u64).min(capacity);
+        (self.walking_speed() as u64 * (2 * capacity - load) / (2 * capacity)) as u32
+    }
+
+    /// Energy levels one stage of pulling costs
+    fn pulling_effort(&self, load_kg: u32, terrain: Terrain) -> u8 {
+        let terrain_cost = match terrain {
+            Terrain::Snow => 0,    // Runners glide over packed snow
+            Terrain::Glacier => 1, // Rough ice and crevasses
+            _ => terrain.energy_cost(),
+        };
+        // Anything beyond half the capacity is hard work
+        let load_cost = if load_kg.saturating_mul(2) > self.max_pull_kg() {
+            1
+        } else {
+            0
+        };
+        1 + load_cost + terrain_cost
+    }
+
+    /// Check if a stage of pulling is possible, without spending energy
+    fn check_pulling(&self, load_kg: u32, terrain: Terrain) -> Result<(), PullingError> {
+        self.check_movement()?;
+        self.check_medium(Medium::Land)?;
+
+        // A team on a glacier is roped together, which a lone walker is not
+        if !terrain.walkable() && terrain != Terrain::Glacier {
+            return Err(PullingError::TerrainNotPullable { terrain });
+        }
+
+        self.check_ambient(terrain.temperature_c())?;
+
+        let capacity_kg = self.max_pull_kg();
+        if load_kg > capacity_kg {
+            return Err(PullingError::Overloaded {
+                load_kg,
+                capacity_kg,
+            });
+        }
+
+        let required = EnergyLevel::Tired;
+        let current = self.energy();
+        if current < required {
+            return Err(PullingError::InsufficientEnergyForPulling { required, current });
+        }
+        Ok(())
+    }
+
+    /// Pull a load across one stage of terrain
+    fn pull(&mut self, load_kg: u32, terrain: Terrain) -> PullingResult {
+        self.check_pulling(load_kg, terrain)?;
+        self.consume_energy_levels(self.pulling_effort(load_kg, terrain));
+        self.endure_ambient(terrain.temperature_c())?;
+        Ok(format!("Pulls {} kg across {} terrain", load_kg, terrain))
+    }
+}
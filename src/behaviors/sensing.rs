@@ -0,0 +1,207 @@
+//! Sensing Trait
+//!
+//! This module defines the Sensing trait for entities that can detect
+//! others at a distance. Like `LandMove`, it spans domains: a whale's sonar
+//! and a warship's sonar answer the same question, as do an eagle's eyes and
+//! an airplane's radar. Weather shortens every range, but not equally.
+//! Dogs follow their nose, which rain washes out but fog does not.
+
+use crate::behaviors::TravelMode;
+use crate::core::Weather;
+use std::fmt;
+
+/// How far plain eyesight reaches in clear weather, in meters
+pub const UNAIDED_VISION_M: u32 = 300;
+
+/// How a sensor detects its targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SensorKind {
+    Vision,
+    Sonar,
+    Radar,
+    Scent,
+}
+
+impl SensorKind {
+    /// Share of the clear-weather range left in this weather, in percent
+    pub fn weather_factor_percent(&self, weather: Weather) -> u32 {
+        let visibility = weather.visibility_level() as u32;
+        match self {
+            SensorKind::Vision => visibility * 20,
+            // Fog is water droplets too small to scatter radar or sound, and damp air holds a scent
+            SensorKind::Radar | SensorKind::Sonar | SensorKind::Scent
+                if weather == Weather::Fog =>
+            {
+                100
+            }
+            // Rain and storms clutter the radar picture
+            SensorKind::Radar => 50 + visibility * 10,
+            // Underwater only the surface noise of rough weather gets through
+            SensorKind::Sonar => 85 + visibility * 3,
+            // Rain and snow wash the trail away
+            SensorKind::Scent => 40 + visibility * 12,
+        }
+    }
+
+    /// Check if this kind of sensor can pick up a target moving this way
+    pub fn detects(&self, target: TravelMode) -> bool {
+        match self {
+            SensorKind::Sonar => target == TravelMode::Swim,
+            // A trail is left on the ground
+            SensorKind::Scent => target == TravelMode::Walk,
+            SensorKind::Vision | SensorKind::Radar => true,
+        }
+    }
+}
+
+impl fmt::Display for SensorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SensorKind::Vision => "Vision",
+            SensorKind::Sonar => "Sonar",
+            SensorKind::Radar => "Radar",
+            SensorKind::Scent => "Scent",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A single sensor and its range in clear weather
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sensor {
+    pub kind: SensorKind,
+    pub range_m: u32,
+}
+
+impl Sensor {
+    pub fn new(kind: SensorKind, range_m: u32) -> Self {
+        Self { kind, range_m }
+    }
+
+    /// Plain eyesight, for those without sensors of their own
+    pub fn unaided() -> Self {
+        Self::new(SensorKind::Vision, UNAIDED_VISION_M)
+    }
+
+    /// Range left after the weather has taken its share
+    pub fn effective_range_m(&self, weather: Weather) -> u32 {
+        self.range_m * self.kind.weather_factor_percent(weather) / 100
+    }
+}
+
+/// Trait for anything that can detect other entities at a distance
+pub trait Sensing {
+    /// Sensors the entity carries, with their clear-weather ranges
+    fn sensors(&self) -> Vec<Sensor>;
+
+    /// Sensor reaching furthest toward a target moving this way, if any can detect it
+    fn best_sensor(&self, weather: Weather, target: TravelMode) -> Option<Sensor> {
+        self.sensors()
+            .into_iter()
+            .filter(|sensor| sensor.kind.detects(target))
+            .max_by_key(|sensor| sensor.effective_range_m(weather))
+    }
+
+    /// How far away a target moving this way can be detected, in meters
+    fn detection_range_m(&self, weather: Weather, target: TravelMode) -> u32 {
+        self.best_sensor(weather, target)
+            .map(|sensor| sensor.effective_range_m(weather))
+            .unwrap_or(0)
+    }
+
+    /// Check if a target at this distance can be detected
+    fn can_detect(&self, distance_m: u32, weather: Weather, target: TravelMode) -> bool {
+        distance_m <= self.detection_range_m(weather, target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::whale::WhaleSpecies;
+    use crate::animals::{Eagle, Whale};
+    use crate::vehicles::airplane::{AirplaneEngine, AirplaneType};
+    use crate::vehicles::ship::{PropulsionType, ShipType};
+    use crate::vehicles::{Airplane, Ship};
+
+    #[test]
+    fn test_eagle_vision_fades_in_fog() {
+        let eagle = Eagle::new("Aquila".to_string());
+        assert_eq!(
+            eagle.detection_range_m(Weather::Clear, TravelMode::Walk),
+            3000
+        );
+        assert_eq!(eagle.detection_range_m(Weather::Fog, TravelMode::Walk), 600);
+        assert!(!eagle.can_detect(1000, Weather::Fog, TravelMode::Walk));
+        assert_eq!(
+            eagle.detection_range_m(Weather::Blizzard, TravelMode::Fly),
+            0
+        );
+    }
+
+    #[test]
+    fn test_sonar_hears_swimmers_through_fog() {
+        let orca = Whale::new("Shamu".to_string(), WhaleSpecies::Orca);
+        let sonar = orca.best_sensor(Weather::Fog, TravelMode::Swim).unwrap();
+        assert_eq!(sonar.kind, SensorKind::Sonar);
+        assert_eq!(orca.detection_range_m(Weather::Fog, TravelMode::Swim), 2000);
+        assert!(orca.detection_range_m(Weather::Storm, TravelMode::Swim) < 2000);
+
+        // Sonar cannot hear a walker on the shore, so only the whale's eyes are left
+        let best = orca.best_sensor(Weather::Clear, TravelMode::Walk).unwrap();
+        assert_eq!(best.kind, SensorKind::Vision);
+
+        let humpback = Whale::new("Moby".to_string(), WhaleSpecies::Humpback);
+        assert!(
+            humpback
+                .sensors()
+                .iter()
+                .all(|sensor| sensor.kind != SensorKind::Sonar)
+        );
+    }
+
+    #[test]
+    fn test_vehicle_sensors() {
+        let engine = AirplaneEngine::Jet {
+            engines: 2,
+            thrust_each: 200,
+        };
+        let fighter = Airplane::new(
+            "Viper".to_string(),
+            "General Dynamics".to_string(),
+            2020,
+            AirplaneType::Military,
+            10,
+            engine,
+        );
+        assert_eq!(
+            fighter.detection_range_m(Weather::Fog, TravelMode::Fly),
+            300_000
+        );
+        assert!(fighter.detection_range_m(Weather::Thunderstorm, TravelMode::Fly) < 300_000);
+
+        let submarine = Ship::new(
+            "Nautilus".to_string(),
+            "General Dynamics".to_string(),
+            2015,
+            ShipType::Submarine,
+            7000,
+            PropulsionType::Nuclear { reactors: 1 },
+        );
+        assert!(submarine.can_detect(9000, Weather::Fog, TravelMode::Swim));
+        assert!(!submarine.can_detect(100, Weather::Clear, TravelMode::Fly));
+
+        let ferry = Ship::new(
+            "Island Hopper".to_string(),
+            "Damen".to_string(),
+            2018,
+            ShipType::Ferry,
+            3000,
+            PropulsionType::Diesel {
+                engines: 2,
+                power_each: 2000,
+            },
+        );
+        assert!(ferry.sensors().is_empty());
+    }
+}
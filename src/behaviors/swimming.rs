@@ -1,5 +1,6 @@
-use crate::behaviors::moving::{Moving, MovingError};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::moving::{Medium, Moving, MovingError};
+use crate::core::{AirSupply, DiveState, EnergyLevel, HasEnergy, Mishap};
+use crate::routes::SegmentKind;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,6 +19,35 @@ pub enum SwimmingError {
         requested_depth: u32,
         max_depth: u32,
     },
+
+    #[error(
+        "Not enough {supply} to dive to {requested_depth}m and return: need {required}s, have {remaining}s"
+    )]
+    InsufficientAir {
+        supply: &'static str,
+        requested_depth: u32,
+        required: u32,
+        remaining: u32,
+    },
+
+    #[error("Out of {supply}: {requested}s requested but only {remaining}s remain - must surface")]
+    OutOfAir {
+        supply: &'static str,
+        requested: u32,
+        remaining: u32,
+    },
+
+    #[error("Ascent too fast: {requested_rate} m/s exceeds the safe limit of {max_rate} m/s")]
+    AscentTooFast { requested_rate: u32, max_rate: u32 },
+
+    #[error("Cannot swim in {mode} mode")]
+    WrongMode { mode: &'static str },
+
+    #[error("Crew not fit to sail: {reason}")]
+    CrewNotFit { reason: String },
+
+    #[error("Mechanical failure: {reason}")]
+    MechanicalFailure { reason: String },
 }
 
 pub type SwimmingResult = Result<String, SwimmingError>;
@@ -27,8 +57,75 @@ pub trait Swimming: Moving + HasEnergy {
     /// Maximum diving depth in meters - varies by implementation
     fn max_depth(&self) -> u32;
 
+    /// Cruising swimming speed in km/h
+    fn swimming_speed(&self) -> u32 {
+        3 // Default: steady paddle
+    }
+
+    /// Get current depth and time underwater
+    ///
+    /// Swimmers that dive should store the state and override this together
+    /// with [`Swimming::set_dive_state`]; by default a swimmer does not track
+    /// its dives and always reports being at the surface.
+    fn dive_state(&self) -> DiveState {
+        DiveState::at_surface()
+    }
+
+    /// Set current depth and time underwater
+    fn set_dive_state(&mut self, _state: DiveState) {}
+
+    /// What limits time underwater - breath for animals, battery for submarines
+    fn air_supply(&self) -> AirSupply {
+        AirSupply::Surface // Default: breathes at the surface, no time limit
+    }
+
+    /// Safe ascent speed in meters per second
+    fn max_ascent_rate(&self) -> u32 {
+        self.air_supply().max_ascent_rate()
+    }
+
+    /// Current depth in meters (0 = at the surface)
+    fn current_depth(&self) -> u32 {
+        self.dive_state().depth
+    }
+
+    /// Check if the swimmer is below the surface
+    fn is_submerged(&self) -> bool {
+        self.dive_state().is_submerged()
+    }
+
+    /// Seconds of air left before the swimmer must be back at the surface
+    fn remaining_air(&self) -> u32 {
+        self.air_supply()
+            .capacity_seconds()
+            .saturating_sub(self.dive_state().time_underwater)
+    }
+
+    /// Seconds needed to get back to the surface at the safe ascent rate
+    fn time_to_surface(&self) -> u32 {
+        self.current_depth().div_ceil(self.max_ascent_rate().max(1))
+    }
+
+    /// Check if the remaining air only just covers the way back up
+    fn needs_to_surface(&self) -> bool {
+        self.is_submerged() && self.remaining_air() <= self.time_to_surface()
+    }
+
+    /// Check entity-specific preconditions before swimming or diving
+    fn check_swimming_conditions(&self) -> Result<(), SwimmingError> {
+        Ok(()) // Default: always ready to swim
+    }
+
+    /// Record a completed swim, e.g. to tire the crew or wear the hull
+    fn log_swim(&mut self) -> Result<(), SwimmingError> {
+        Ok(())
+    }
+
     /// Basic swimming
     fn swim(&mut self) -> SwimmingResult {
+        self.check_swimming_conditions()?;
+        self.check_movement()?;
+        self.check_medium(Medium::Water)?;
         let current_energy = self.energy();
 
         // Swimming requires at least Tired energy level
@@ -44,24 +141,48 @@ pub trait Swimming: Moving + HasEnergy {
         self.consume_energy();
 
         // Use basic movement as foundation
-        match self.do_move() {
-            Ok(_) => Ok("Entity swims".to_string()),
+        match self.do_move_in(Medium::Water) {
+            Ok(_) => {
+                self.log_swim()?;
+                Ok("Entity swims".to_string())
+            }
             Err(movement_error) => Err(SwimmingError::MovementError(movement_error)),
         }
     }
 
     /// Diving to specific depth
     fn dive(&mut self, target_depth: u32) -> SwimmingResult {
+        self.check_swimming_conditions()?;
+        self.check_movement()?;
+        self.check_medium(Medium::Water)?;
         let current_energy = self.energy();
 
-        // Check depth limits
+        // Check depth limits - trying anyway hurts
         if target_depth > self.max_depth() {
+            self.suffer(Mishap::DiveTooDeep {
+                requested: target_depth,
+                max_depth: self.max_depth(),
+            });
             return Err(SwimmingError::DepthLimitExceeded {
                 requested_depth: target_depth,
                 max_depth: self.max_depth(),
             });
         }
 
+        // Going up is a controlled ascent, not a dive
+        let state = self.dive_state();
+        if target_depth < state.depth {
+            let meters = state.depth - target_depth;
+            return self.ascend(meters, meters.div_ceil(self.max_ascent_rate().max(1)));
+        }
+
+        // Deep water is cold
+        let temperature_c = SegmentKind::Water {
+            depth: target_depth,
+        }
+        .temperature_c();
+        self.check_ambient(temperature_c)?;
+
         // Diving requires Normal energy level
         let required_energy = EnergyLevel::Normal;
         if current_energy < required_energy {
@@ -71,15 +192,120 @@ pub trait Swimming: Moving + HasEnergy {
             });
         }
 
+        // The air budget must cover the descent and the way back up
+        let rate = self.max_ascent_rate().max(1);
+        let descent_time = (target_depth - state.depth).div_ceil(rate);
+        let required = descent_time + target_depth.div_ceil(rate);
+        let remaining = self.remaining_air();
+        if required > remaining {
+            return Err(SwimmingError::InsufficientAir {
+                supply: self.air_supply().resource_name(),
+                requested_depth: target_depth,
+                required,
+                remaining,
+            });
+        }
+
         // Diving consumes more energy
         self.consume_energy_levels(2);
 
         // Use basic movement for diving
-        match self.do_move() {
-            Ok(_) => Ok(format!("Entity dives to {}m depth", target_depth)),
+        match self.do_move_in(Medium::Water) {
+            Ok(_) => {
+                self.set_dive_state(DiveState {
+                    depth: target_depth,
+                    time_underwater: state.time_underwater + descent_time,
+                });
+                self.endure_ambient(temperature_c)?;
+                Ok(format!("Entity dives to {}m depth", target_depth))
+            }
             Err(movement_error) => Err(SwimmingError::MovementError(movement_error)),
         }
     }
+
+    /// Stay at the current depth for a number of seconds
+    fn stay_submerged(&mut self, seconds: u32) -> SwimmingResult {
+        self.check_medium(Medium::Water)?;
+        let state = self.dive_state();
+        if !state.is_submerged() {
+            return Ok(format!("Entity floats at the surface for {}s", seconds));
+        }
+
+        let remaining = self.remaining_air();
+        if seconds > remaining {
+            return Err(SwimmingError::OutOfAir {
+                supply: self.air_supply().resource_name(),
+                requested: seconds,
+                remaining,
+            });
+        }
+
+        self.set_dive_state(DiveState {
+            depth: state.depth,
+            time_underwater: state.time_underwater + seconds,
+        });
+
+        Ok(format!(
+            "Entity stays at {}m for {}s ({}s of {} left)",
+            state.depth,
+            seconds,
+            self.remaining_air(),
+            self.air_supply().resource_name()
+        ))
+    }
+
+    /// Ascend a number of meters over a number of seconds
+    fn ascend(&mut self, meters: u32, seconds: u32) -> SwimmingResult {
+        self.check_medium(Medium::Water)?;
+        let state = self.dive_state();
+        let meters = meters.min(state.depth);
+        if meters == 0 {
+            return Ok(format!("Entity holds at {}m", state.depth));
+        }
+
+        // Ascending faster than the safe rate is refused
+        let max_rate = self.max_ascent_rate();
+        if seconds == 0 || meters > max_rate.saturating_mul(seconds) {
+            return Err(SwimmingError::AscentTooFast {
+                requested_rate: meters.div_ceil(seconds.max(1)),
+                max_rate,
+            });
+        }
+
+        let remaining = self.remaining_air();
+        if seconds > remaining {
+            return Err(SwimmingError::OutOfAir {
+                supply: self.air_supply().resource_name(),
+                requested: seconds,
+                remaining,
+            });
+        }
+
+        let depth = state.depth - meters;
+        if depth == 0 {
+            // Back at the surface: breath or battery is replenished
+            self.set_dive_state(DiveState::at_surface());
+            return Ok(format!(
+                "Entity surfaces after {}s underwater",
+                state.time_underwater + seconds
+            ));
+        }
+
+        self.set_dive_state(DiveState {
+            depth,
+            time_underwater: state.time_underwater + seconds,
+        });
+        Ok(format!("Entity ascends to {}m depth", depth))
+    }
+
+    /// Return to the surface at the safe ascent rate
+    fn surface(&mut self) -> SwimmingResult {
+        if !self.is_submerged() {
+            return Ok("Entity is already at the surface".to_string());
+        }
+
+        self.ascend(self.current_depth(), self.time_to_surface())
+    }
 }
 
 #[cfg(test)]
@@ -90,11 +316,22 @@ mod tests {
     struct TestSwimmer {
         energy: EnergyLevel,
         max_depth: u32,
+        dive_state: DiveState,
+        air_supply: AirSupply,
     }
 
     impl TestSwimmer {
         fn new(energy: EnergyLevel, max_depth: u32) -> Self {
-            Self { energy, max_depth }
+            Self::with_air(energy, max_depth, AirSupply::Breath { max_seconds: 600 })
+        }
+
+        fn with_air(energy: EnergyLevel, max_depth: u32, air_supply: AirSupply) -> Self {
+            Self {
+                energy,
+                max_depth,
+                dive_state: DiveState::at_surface(),
+                air_supply,
+            }
         }
     }
 
@@ -114,6 +351,55 @@ mod tests {
         fn max_depth(&self) -> u32 {
             self.max_depth
         }
+
+        fn dive_state(&self) -> DiveState {
+            self.dive_state
+        }
+
+        fn set_dive_state(&mut self, state: DiveState) {
+            self.dive_state = state;
+        }
+
+        fn air_supply(&self) -> AirSupply {
+            self.air_supply
+        }
+    }
+
+    /// A swimmer written before dives were tracked
+    #[derive(Debug)]
+    struct PaddlingSwimmer {
+        energy: EnergyLevel,
+    }
+
+    impl HasEnergy for PaddlingSwimmer {
+        fn energy(&self) -> EnergyLevel {
+            self.energy
+        }
+
+        fn set_energy(&mut self, level: EnergyLevel) {
+            self.energy = level;
+        }
+    }
+
+    impl Moving for PaddlingSwimmer {}
+
+    impl Swimming for PaddlingSwimmer {
+        fn max_depth(&self) -> u32 {
+            5
+        }
+    }
+
+    #[test]
+    fn test_untracked_swimmer_stays_at_surface() {
+        let mut swimmer = PaddlingSwimmer {
+            energy: EnergyLevel::Hyperactive,
+        };
+        assert_eq!(swimmer.air_supply(), AirSupply::Surface);
+
+        assert!(swimmer.dive(5).is_ok());
+        assert!(!swimmer.is_submerged());
+        assert_eq!(swimmer.remaining_air(), u32::MAX);
+        assert!(swimmer.stay_submerged(600).is_ok());
     }
 
     #[test]
@@ -227,4 +513,137 @@ mod tests {
         // swimmer1 should have more energy left than swimmer2
         assert!(swimmer1.energy() > swimmer2.energy());
     }
+
+    #[test]
+    fn test_dive_tracks_depth_and_time() {
+        let mut swimmer = TestSwimmer::new(EnergyLevel::Hyperactive, 100);
+        assert!(!swimmer.is_submerged());
+
+        swimmer.dive(40).unwrap();
+        assert_eq!(swimmer.current_depth(), 40);
+        assert_eq!(swimmer.dive_state().time_underwater, 20); // 40m at 2 m/s
+        assert_eq!(swimmer.remaining_air(), 580);
+        assert!(swimmer.is_submerged());
+    }
+
+    #[test]
+    fn test_dive_needs_air_for_return_trip() {
+        let mut swimmer = TestSwimmer::with_air(
+            EnergyLevel::Hyperactive,
+            100,
+            AirSupply::Breath { max_seconds: 30 },
+        );
+
+        // 40m down and 40m up at 2 m/s needs 40s of breath
+        let result = swimmer.dive(40);
+        if let Err(SwimmingError::InsufficientAir {
+            supply,
+            required,
+            remaining,
+            ..
+        }) = result
+        {
+            assert_eq!(supply, "breath");
+            assert_eq!(required, 40);
+            assert_eq!(remaining, 30);
+        } else {
+            panic!("Expected InsufficientAir error");
+        }
+        assert_eq!(swimmer.current_depth(), 0);
+        assert_eq!(swimmer.energy(), EnergyLevel::Hyperactive);
+    }
+
+    #[test]
+    fn test_stay_submerged_until_out_of_air() {
+        let mut swimmer = TestSwimmer::with_air(
+            EnergyLevel::Hyperactive,
+            100,
+            AirSupply::Breath { max_seconds: 60 },
+        );
+        swimmer.dive(20).unwrap(); // 10s down
+
+        assert!(swimmer.stay_submerged(30).is_ok());
+        assert_eq!(swimmer.remaining_air(), 20);
+        assert!(!swimmer.needs_to_surface());
+
+        assert!(swimmer.stay_submerged(10).is_ok());
+        assert!(swimmer.needs_to_surface()); // 10s left, 10s to surface
+
+        let result = swimmer.stay_submerged(15);
+        assert!(matches!(result, Err(SwimmingError::OutOfAir { .. })));
+    }
+
+    #[test]
+    fn test_surface_restores_air() {
+        let mut swimmer = TestSwimmer::new(EnergyLevel::Hyperactive, 100);
+        swimmer.dive(50).unwrap();
+        swimmer.stay_submerged(100).unwrap();
+
+        let result = swimmer.surface();
+        assert!(result.is_ok());
+        assert_eq!(swimmer.dive_state(), DiveState::at_surface());
+        assert_eq!(swimmer.remaining_air(), 600);
+
+        assert_eq!(
+            swimmer.surface().unwrap(),
+            "Entity is already at the surface"
+        );
+    }
+
+    #[test]
+    fn test_ascent_rate_limit() {
+        let mut swimmer = TestSwimmer::new(EnergyLevel::Hyperactive, 100);
+        swimmer.dive(60).unwrap();
+
+        let result = swimmer.ascend(60, 10); // 6 m/s
+        if let Err(SwimmingError::AscentTooFast {
+            requested_rate,
+            max_rate,
+        }) = result
+        {
+            assert_eq!(requested_rate, 6);
+            assert_eq!(max_rate, 2);
+        } else {
+            panic!("Expected AscentTooFast error");
+        }
+        assert_eq!(swimmer.current_depth(), 60);
+
+        assert_eq!(
+            swimmer.ascend(20, 10).unwrap(),
+            "Entity ascends to 40m depth"
+        );
+        assert_eq!(swimmer.current_depth(), 40);
+    }
+
+    #[test]
+    fn test_dive_to_shallower_depth_ascends() {
+        let mut swimmer = TestSwimmer::new(EnergyLevel::Hyperactive, 100);
+        swimmer.dive(50).unwrap();
+        let energy_after_dive = swimmer.energy();
+
+        swimmer.dive(10).unwrap();
+        assert_eq!(swimmer.current_depth(), 10);
+        assert_eq!(swimmer.energy(), energy_after_dive); // Ascending is free
+    }
+
+    #[test]
+    fn test_ballast_uses_battery() {
+        let mut submarine = TestSwimmer::with_air(
+            EnergyLevel::Hyperactive,
+            300,
+            AirSupply::Ballast {
+                battery_seconds: 100,
+                ballast_tanks: 5,
+            },
+        );
+        assert_eq!(submarine.max_ascent_rate(), 5);
+
+        submarine.dive(200).unwrap(); // 40s down
+        let result = submarine.stay_submerged(80);
+        if let Err(SwimmingError::OutOfAir { supply, .. }) = result {
+            assert_eq!(supply, "battery");
+        } else {
+            panic!("Expected OutOfAir error");
+        }
+    }
 }
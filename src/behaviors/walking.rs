@@ -1,13 +1,35 @@
-use crate::behaviors::land_move::{LandMove, LandMoveResult};
+use crate::behaviors::land_move::{LandMove, LandMoveError, LandMoveResult};
+use crate::core::{Mishap, Terrain};
 
 /// Walking capability - uses LandMove as foundation
 pub trait Walking: LandMove {
+    /// Cruising walking speed in km/h
+    fn walking_speed(&self) -> u32 {
+        5 // Default: human walking pace
+    }
+
     /// Basic walking - uses land_move
     fn walk(&mut self) -> LandMoveResult {
         // Walking is just basic land movement for biological entities
         self.land_move()
     }
 
+    /// Walking across terrain - costs extra energy, and hazardous terrain hurts
+    fn walk_on_terrain(&mut self, terrain: Terrain) -> LandMoveResult {
+        if !terrain.walkable() {
+            self.suffer(Mishap::Terrain(terrain));
+            return Err(LandMoveError::TerrainNotWalkable { terrain });
+        }
+
+        let temperature_c = terrain.temperature_c();
+        self.check_ambient(temperature_c)?;
+
+        let result = self.walk()?;
+        self.consume_energy_levels(terrain.energy_cost());
+        self.endure_ambient(temperature_c)?;
+        Ok(format!("{} across {} terrain", result, terrain))
+    }
+
     /// Running - faster but more energy-intensive - uses land_move_fast
     fn run(&mut self) -> LandMoveResult {
         // Running is fast land movement for biological entities
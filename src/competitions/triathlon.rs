@@ -1,6 +1,7 @@
 //! Triathlon competition for animals that can walk, swim, and fly
 
 use crate::animals::Animal;
+use crate::animals::diet::{Feeding, FeedingStation};
 use crate::behaviors::{Flying, Swimming, Walking};
 use crate::core::{EnergyLevel, HasEnergy};
 use std::fmt;
@@ -100,6 +101,26 @@ impl Triathlon {
     /// Add a participant to the triathlon
     /// T must implement all three movement traits plus Animal and HasEnergy
     pub fn add_participant<T>(&mut self, participant: &mut T)
+    where
+        T: Walking + Swimming + Flying + Animal + HasEnergy,
+    {
+        self.run(participant, |_| {});
+    }
+
+    /// Add a participant that stops at a feeding station between stages
+    pub fn add_participant_with_feeding<T>(&mut self, participant: &mut T, station: &FeedingStation)
+    where
+        T: Walking + Swimming + Flying + Feeding,
+    {
+        self.run(participant, |participant| {
+            match station.serve(participant) {
+                Ok(meal) => println!("  🍽️  {}", meal),
+                Err(e) => println!("  🍽️  No food: {}", e),
+            }
+        });
+    }
+
+    fn run<T>(&mut self, participant: &mut T, mut between_stages: impl FnMut(&mut T))
     where
         T: Walking + Swimming + Flying + Animal + HasEnergy,
     {
@@ -133,6 +154,8 @@ impl Triathlon {
             }
         };
 
+        between_stages(participant);
+
         // Stage 2: Swimming (only if still has energy)
         println!("  🏊 Stage 2: Swimming...");
         let swim_result = if participant.energy() > EnergyLevel::Collapsed {
@@ -156,6 +179,8 @@ impl Triathlon {
             Err("Too exhausted to continue".to_string())
         };
 
+        between_stages(participant);
+
         // Stage 3: Flying (only if still has energy)
         println!("  🛩️  Stage 3: Flying...");
         let fly_result = if participant.energy() > EnergyLevel::Collapsed {
@@ -268,6 +293,7 @@ impl Default for Triathlon {
 mod tests {
     use super::*;
     use crate::animals::Duck;
+    use crate::animals::diet::{Food, FoodKind};
 
     #[test]
     fn test_triathlon() {
@@ -280,6 +306,20 @@ mod tests {
         assert_eq!(triathlon.results[0].participant_name, "Test Duck");
     }
 
+    #[test]
+    fn test_feeding_stations_between_stages() {
+        let station = FeedingStation::new(vec![Food::new(FoodKind::Seeds, 80)], 45);
+
+        let mut hungry = Duck::new("Hungry".to_string());
+        let mut fed = Duck::new("Fed".to_string());
+        let mut triathlon = Triathlon::new();
+        triathlon.add_participant(&mut hungry);
+        triathlon.add_participant_with_feeding(&mut fed, &station);
+
+        assert_eq!(triathlon.results[0].completed_stages, 2);
+        assert_eq!(triathlon.results[1].completed_stages, 3);
+    }
+
     #[test]
     fn test_scoring() {
         let result = TriathlonResult {
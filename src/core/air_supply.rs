@@ -0,0 +1,132 @@
+use std::fmt;
+
+/// What keeps a swimmer alive while it is submerged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AirSupply {
+    /// Biological breath-hold, limited to a number of seconds
+    Breath { max_seconds: u32 },
+
+    /// Mechanical submersion: battery endurance limits time underwater,
+    /// ballast tanks limit how fast depth can be changed
+    Ballast {
+        battery_seconds: u32,
+        ballast_tanks: u8,
+    },
+
+    /// Air is drawn from the surface (snorkel, open deck, air intake)
+    Surface,
+
+    /// Oxygen is drawn from the water itself (fish, tadpoles)
+    Gills,
+}
+
+impl AirSupply {
+    /// Total number of seconds that can be spent underwater
+    pub fn capacity_seconds(&self) -> u32 {
+        match self {
+            AirSupply::Breath { max_seconds } => *max_seconds,
+            AirSupply::Ballast {
+                battery_seconds, ..
+            } => *battery_seconds,
+            AirSupply::Surface | AirSupply::Gills => u32::MAX,
+        }
+    }
+
+    /// Safe vertical speed in meters per second
+    pub fn max_ascent_rate(&self) -> u32 {
+        match self {
+            AirSupply::Breath { .. } | AirSupply::Gills => 2,
+            AirSupply::Ballast { ballast_tanks, .. } => (*ballast_tanks as u32).max(1),
+            AirSupply::Surface => 1,
+        }
+    }
+
+    /// Name of the limited resource, used in error messages
+    pub fn resource_name(&self) -> &'static str {
+        match self {
+            AirSupply::Breath { .. } => "breath",
+            AirSupply::Ballast { .. } => "battery",
+            AirSupply::Surface => "surface air",
+            AirSupply::Gills => "dissolved oxygen",
+        }
+    }
+
+    /// Check if this supply comes from machinery rather than lungs
+    pub fn is_mechanical(&self) -> bool {
+        matches!(self, AirSupply::Ballast { .. })
+    }
+}
+
+impl fmt::Display for AirSupply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AirSupply::Breath { max_seconds } => write!(f, "Breath-hold ({}s)", max_seconds),
+            AirSupply::Ballast {
+                battery_seconds,
+                ballast_tanks,
+            } => write!(
+                f,
+                "Ballast ({} tanks, {}s battery)",
+                ballast_tanks, battery_seconds
+            ),
+            AirSupply::Surface => write!(f, "Surface air"),
+            AirSupply::Gills => write!(f, "Gills"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity() {
+        assert_eq!(AirSupply::Breath { max_seconds: 30 }.capacity_seconds(), 30);
+        assert_eq!(
+            AirSupply::Ballast {
+                battery_seconds: 3600,
+                ballast_tanks: 4
+            }
+            .capacity_seconds(),
+            3600
+        );
+        assert_eq!(AirSupply::Surface.capacity_seconds(), u32::MAX);
+    }
+
+    #[test]
+    fn test_ascent_rate() {
+        assert_eq!(AirSupply::Breath { max_seconds: 30 }.max_ascent_rate(), 2);
+        assert_eq!(
+            AirSupply::Ballast {
+                battery_seconds: 3600,
+                ballast_tanks: 4
+            }
+            .max_ascent_rate(),
+            4
+        );
+        assert_eq!(
+            AirSupply::Ballast {
+                battery_seconds: 3600,
+                ballast_tanks: 0
+            }
+            .max_ascent_rate(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_mechanical() {
+        assert!(!AirSupply::Breath { max_seconds: 30 }.is_mechanical());
+        assert!(
+            AirSupply::Ballast {
+                battery_seconds: 3600,
+                ballast_tanks: 4
+            }
+            .is_mechanical()
+        );
+        assert_eq!(
+            AirSupply::Breath { max_seconds: 30 }.resource_name(),
+            "breath"
+        );
+    }
+}
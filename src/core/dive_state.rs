@@ -0,0 +1,20 @@
+/// Where a swimmer currently is in the water column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DiveState {
+    /// Current depth in meters (0 = at the surface)
+    pub depth: u32,
+    /// Seconds spent underwater since the last time at the surface
+    pub time_underwater: u32,
+}
+
+impl DiveState {
+    /// State of a swimmer resting at the surface
+    pub fn at_surface() -> Self {
+        Self::default()
+    }
+
+    /// Check if the swimmer is below the surface
+    pub fn is_submerged(&self) -> bool {
+        self.depth > 0
+    }
+}
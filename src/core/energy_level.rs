@@ -9,6 +9,15 @@ pub enum EnergyLevel {
 }
 
 impl EnergyLevel {
+    pub const ALL: [EnergyLevel; 6] = [
+        EnergyLevel::Collapsed,
+        EnergyLevel::Exhausted,
+        EnergyLevel::Tired,
+        EnergyLevel::Normal,
+        EnergyLevel::Energetic,
+        EnergyLevel::Hyperactive,
+    ];
+
     /// Create energy level from numeric value (0-100)
     pub fn from_points(points: u8) -> Self {
         match points {
@@ -0,0 +1,359 @@
+//! Health and Injuries
+//!
+//! Energy says how tired an entity is; health says whether it is hurt.
+//! Failed risky activities - diving past the maximum depth, setting foot on
+//! a volcano, flying into a hurricane - are [`Mishap`]s. Animals turn them
+//! into [`Injury`]s that lower their capability limits until healed;
+//! vehicles turn them into damage to their condition.
+//!
+//! | Severity | Capability | Rests to heal | Can move |
+//! |----------|------------|---------------|----------|
+//! | Minor    | 90%        | 1             | yes      |
+//! | Moderate | 60%        | 3             | yes      |
+//! | Severe   | 30%        | 6             | no       |
+
+use crate::core::{HasEnergy, Terrain, Weather};
+use crate::errors::activity_error::ActivityError;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Minor,
+    Moderate,
+    Severe,
+}
+
+impl Severity {
+    /// Share of the normal capability left while injured, in percent
+    pub fn capability_percent(&self) -> u32 {
+        match self {
+            Severity::Minor => 90,
+            Severity::Moderate => 60,
+            Severity::Severe => 30,
+        }
+    }
+
+    /// Rests needed before the injury has healed
+    pub fn rests_to_heal(&self) -> u8 {
+        match self {
+            Severity::Minor => 1,
+            Severity::Moderate => 3,
+            Severity::Severe => 6,
+        }
+    }
+
+    /// Condition points a vehicle loses from a mishap of this severity
+    pub fn damage_percent(&self) -> u8 {
+        match self {
+            Severity::Minor => 10,
+            Severity::Moderate => 30,
+            Severity::Severe => 60,
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Severity::Minor => "Minor",
+            Severity::Moderate => "Moderate",
+            Severity::Severe => "Severe",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A failed risky activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mishap {
+    /// Tried to dive deeper than possible
+    DiveTooDeep { requested: u32, max_depth: u32 },
+    /// Tried to cross terrain nobody should set foot on
+    Terrain(Terrain),
+    /// Tried to fly in weather that is not safe for flying
+    Weather(Weather),
+}
+
+impl Mishap {
+    /// How badly the mishap hurts
+    pub fn severity(&self) -> Severity {
+        match self {
+            Mishap::DiveTooDeep {
+                requested,
+                max_depth,
+            } => {
+                // The further past the limit, the worse the barotrauma
+                let overshoot_percent =
+                    requested.saturating_sub(*max_depth) as u64 * 100 / (*max_depth).max(1) as u64;
+                match overshoot_percent {
+                    0..=20 => Severity::Minor,
+                    21..=50 => Severity::Moderate,
+                    _ => Severity::Severe,
+                }
+            }
+            Mishap::Terrain(Terrain::Volcano | Terrain::Cliff) => Severity::Severe,
+            Mishap::Terrain(Terrain::Glacier | Terrain::Extreme) => Severity::Moderate,
+            Mishap::Terrain(_) => Severity::Minor,
+            Mishap::Weather(Weather::Hurricane | Weather::Tornado) => Severity::Severe,
+            Mishap::Weather(Weather::Thunderstorm) => Severity::Moderate,
+            Mishap::Weather(_) => Severity::Minor,
+        }
+    }
+}
+
+impl fmt::Display for Mishap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mishap::DiveTooDeep {
+                requested,
+                max_depth,
+            } => write!(f, "dive to {}m past the {}m limit", requested, max_depth),
+            Mishap::Terrain(terrain) => write!(f, "accident on {} terrain", terrain),
+            Mishap::Weather(weather) => write!(f, "flight into {} weather", weather),
+        }
+    }
+}
+
+/// An injury that heals with rest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Injury {
+    pub cause: String,
+    pub severity: Severity,
+    /// Rests still needed before the injury has healed
+    pub rests_left: u8,
+}
+
+impl Injury {
+    pub fn new(cause: String, severity: Severity) -> Self {
+        Self {
+            cause,
+            severity,
+            rests_left: severity.rests_to_heal(),
+        }
+    }
+}
+
+impl From<Mishap> for Injury {
+    fn from(mishap: Mishap) -> Self {
+        Injury::new(mishap.to_string(), mishap.severity())
+    }
+}
+
+impl fmt::Display for Injury {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} injury from {} ({} rests to heal)",
+            self.severity, self.cause, self.rests_left
+        )
+    }
+}
+
+/// Injuries an entity is currently recovering from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Health {
+    injuries: Vec<Injury>,
+}
+
+impl Health {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn injuries(&self) -> &[Injury] {
+        &self.injuries
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.injuries.is_empty()
+    }
+
+    /// Get the most severe injury
+    pub fn worst_injury(&self) -> Option<&Injury> {
+        self.injuries.iter().max_by_key(|injury| injury.severity)
+    }
+
+    pub fn injure(&mut self, injury: Injury) {
+        self.injuries.push(injury);
+    }
+
+    /// Share of the normal capability left, in percent
+    pub fn capability_percent(&self) -> u32 {
+        self.worst_injury()
+            .map_or(100, |injury| injury.severity.capability_percent())
+    }
+
+    /// Scale a capability limit such as speed or depth by the current health;
+    /// a capability that is left at all never rounds down to nothing
+    pub fn limit(&self, value: u32) -> u32 {
+        let percent = self.capability_percent();
+        let limited = (value as u64 * percent as u64 / 100) as u32;
+        if value > 0 && percent > 0 {
+            limited.max(1)
+        } else {
+            limited
+        }
+    }
+
+    /// Heal every injury by one rest, returning those that healed completely
+    pub fn heal(&mut self) -> Vec<Injury> {
+        for injury in &mut self.injuries {
+            injury.rests_left = injury.rests_left.saturating_sub(1);
+        }
+        let (healed, open) = std::mem::take(&mut self.injuries)
+            .into_iter()
+            .partition(|injury| injury.rests_left == 0);
+        self.injuries = open;
+        healed
+    }
+}
+
+/// Entities that can get hurt
+pub trait HasHealth: HasEnergy {
+    /// Get the health record
+    fn health(&self) -> &Health;
+
+    /// Get mutable access to the health record
+    fn health_mut(&mut self) -> &mut Health;
+
+    fn is_injured(&self) -> bool {
+        !self.health().is_healthy()
+    }
+
+    /// Get hurt by a failed risky activity
+    fn injure(&mut self, mishap: Mishap) -> Injury {
+        let injury = Injury::from(mishap);
+        self.health_mut().injure(injury.clone());
+        injury
+    }
+
+    /// Refuse any activity while severely injured
+    fn check_health(&self) -> Result<(), ActivityError> {
+        match self.health().worst_injury() {
+            Some(injury) if injury.severity == Severity::Severe => {
+                Err(ActivityError::RecoveryNeeded(injury.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Rest, recovering energy and healing injuries
+    fn recover(&mut self) -> Vec<Injury> {
+        self.rest();
+        self.health_mut().heal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::{Dog, Eagle, Frog, dog::DogBreed};
+    use crate::behaviors::flying::FlyingError;
+    use crate::behaviors::{
+        Driving, Flying, Swimming, Walking, driving::DrivingError, moving::MovingError,
+    };
+    use crate::vehicles::maintenance::Maintainable;
+    use crate::vehicles::{Car, car::EngineType};
+
+    #[test]
+    fn test_mishap_severity() {
+        let shallow = Mishap::DiveTooDeep {
+            requested: 11,
+            max_depth: 10,
+        };
+        let deep = Mishap::DiveTooDeep {
+            requested: 30,
+            max_depth: 10,
+        };
+        assert_eq!(shallow.severity(), Severity::Minor);
+        assert_eq!(deep.severity(), Severity::Severe);
+        assert_eq!(
+            Mishap::Weather(Weather::Thunderstorm).severity(),
+            Severity::Moderate
+        );
+    }
+
+    #[test]
+    fn test_injuries_limit_and_heal() {
+        let mut health = Health::new();
+        health.injure(Injury::new("sprain".to_string(), Severity::Minor));
+        health.injure(Injury::new("fracture".to_string(), Severity::Moderate));
+        assert_eq!(health.limit(10), 6);
+
+        assert_eq!(health.heal().len(), 1); // The sprain
+        assert_eq!(health.capability_percent(), 60);
+        health.heal();
+        health.heal();
+        assert!(health.is_healthy());
+        assert_eq!(health.limit(10), 10);
+    }
+
+    #[test]
+    fn test_small_capabilities_do_not_round_to_zero() {
+        let mut health = Health::new();
+        health.injure(Injury::new("sprain".to_string(), Severity::Minor));
+        assert_eq!(health.limit(1), 1);
+        assert_eq!(health.limit(0), 0);
+
+        let mut frog = Frog::new("Kermit".to_string());
+        assert_eq!(frog.walking_speed(), 1);
+        frog.injure(Mishap::Terrain(Terrain::Grass));
+        assert_eq!(frog.walking_speed(), 1); // Hops on, slowly
+    }
+
+    #[test]
+    fn test_failed_dive_injures_and_limits() {
+        let mut dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        let speed = dog.walking_speed();
+
+        assert!(dog.dive(12).is_err()); // 20% past the limit
+        assert_eq!(dog.health.worst_injury().unwrap().severity, Severity::Minor);
+        assert_eq!(dog.max_depth(), 9);
+        assert!(dog.walking_speed() < speed);
+
+        dog.recover();
+        assert!(!dog.is_injured());
+        assert_eq!(dog.walking_speed(), speed);
+    }
+
+    #[test]
+    fn test_severe_injury_needs_recovery() {
+        let mut eagle = Eagle::new("Baldy".to_string());
+        assert!(eagle.fly_in_weather(Weather::Hurricane).is_err());
+
+        match eagle.fly() {
+            Err(FlyingError::MovementError(MovingError::Unfit(ActivityError::RecoveryNeeded(
+                injury,
+            )))) => assert_eq!(injury.severity, Severity::Severe),
+            other => panic!("expected RecoveryNeeded, got {:?}", other),
+        }
+
+        for _ in 0..Severity::Severe.rests_to_heal() {
+            eagle.recover();
+        }
+        assert!(eagle.fly().is_ok());
+    }
+
+    #[test]
+    fn test_vehicles_take_damage() {
+        let mut car = Car::new(
+            "Test".to_string(),
+            "Test Motors".to_string(),
+            2022,
+            EngineType::Electric {
+                battery_capacity: 100,
+            },
+        );
+        assert!(matches!(
+            car.drive_on_terrain(Terrain::Forest),
+            Err(DrivingError::TerrainNotSupported { .. })
+        ));
+        assert_eq!(car.condition().percent(), 100); // Refused at the tree line
+
+        assert!(matches!(
+            car.drive_on_terrain(Terrain::Volcano),
+            Err(DrivingError::TerrainDamage { .. })
+        ));
+        assert_eq!(car.condition().percent(), 40);
+    }
+}
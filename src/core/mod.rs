@@ -1,11 +1,21 @@
+pub mod air_supply;
+pub mod dive_state;
 pub mod energy_level;
 pub mod has_energy;
+pub mod health;
 pub mod intensity;
+pub mod rng;
+pub mod temperature;
 pub mod terrain;
 pub mod weather;
 
+pub use air_supply::AirSupply;
+pub use dive_state::DiveState;
 pub use energy_level::EnergyLevel;
 pub use has_energy::HasEnergy;
+pub use health::{HasHealth, Health, Injury, Mishap, Severity};
 pub use intensity::Intensity;
+pub use rng::SeededRng;
+pub use temperature::{Exposure, ThermalRange, ThermalTolerance};
 pub use terrain::Terrain;
 pub use weather::Weather;
@@ -0,0 +1,83 @@
+/// Small deterministic random number generator (SplitMix64)
+///
+/// Simulations use this instead of an external crate so that the same seed
+/// always produces the same sequence, on every platform and version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Derive a seed from a label (e.g. an entity name) using FNV-1a
+    pub fn from_label(label: &str) -> Self {
+        let hash = label.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+        Self::new(hash)
+    }
+
+    /// Get the next raw 64-bit value
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Get a value in `0..upper` (returns 0 when `upper` is 0)
+    pub fn below(&mut self, upper: u32) -> u32 {
+        if upper == 0 {
+            return 0;
+        }
+        (self.next_u64() % upper as u64) as u32
+    }
+
+    /// Roll against a chance given in per mille (0 = never, 1000 = always)
+    pub fn chance_per_mille(&mut self, per_mille: u32) -> bool {
+        self.below(1000) < per_mille
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SeededRng::new(42);
+        let mut b = SeededRng::new(42);
+
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(SeededRng::new(1).next_u64(), SeededRng::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_from_label_is_stable() {
+        assert_eq!(
+            SeededRng::from_label("Tesla"),
+            SeededRng::from_label("Tesla")
+        );
+        assert_ne!(
+            SeededRng::from_label("Tesla"),
+            SeededRng::from_label("Beetle")
+        );
+    }
+
+    #[test]
+    fn test_chance_bounds() {
+        let mut rng = SeededRng::new(7);
+
+        for _ in 0..100 {
+            assert!(!rng.chance_per_mille(0));
+            assert!(rng.chance_per_mille(1000));
+            assert!(rng.below(6) < 6);
+        }
+    }
+}
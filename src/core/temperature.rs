@@ -0,0 +1,320 @@
+//! Temperature Tolerance
+//!
+//! Terrain and weather set the ambient temperature: glaciers and blizzards
+//! are bitterly cold, deserts and volcanoes scorching. Every animal and
+//! vehicle has a [`ThermalRange`] it is comfortable in. Outside that range
+//! each started 10 degrees costs an extra energy level, and more than 20
+//! degrees outside it the activity is refused. Walking and driving across
+//! terrain, flying through weather, diving and pulling all pay this through
+//! `Moving::endure_ambient`, and the route planner prices it in.
+//!
+//! Ectothermic animals such as snakes also slow down in the cold, and the
+//! air gets colder with altitude, which is what limits piston engines.
+
+use crate::core::HasEnergy;
+use crate::errors::activity_error::ActivityError;
+use std::fmt;
+
+/// Temperature of temperate lowlands in degrees Celsius
+pub const MILD_TEMPERATURE_C: i32 = 15;
+
+/// Degrees outside the comfort range that cost one extra energy level
+const DEGREES_PER_LEVEL: u32 = 10;
+
+/// Degrees outside the comfort range beyond which activities are refused
+const REFUSAL_MARGIN_C: u32 = 20;
+
+/// Speed an ectothermic animal loses per degree below its range, in percent
+const ECTOTHERM_SLOWDOWN_PERCENT: u32 = 4;
+
+/// Air temperature at an altitude, using the standard lapse rate of 6.5°C/km
+pub fn temperature_at_altitude(ground_c: i32, altitude_m: u32) -> i32 {
+    ground_c - (altitude_m as i64 * 65 / 10_000) as i32
+}
+
+/// How an entity experiences a temperature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exposure {
+    Comfortable,
+    /// Colder than the range by this many degrees
+    Cold {
+        degrees: u32,
+    },
+    /// Hotter than the range by this many degrees
+    Hot {
+        degrees: u32,
+    },
+}
+
+impl Exposure {
+    /// Degrees outside the comfort range
+    pub fn degrees(&self) -> u32 {
+        match self {
+            Exposure::Comfortable => 0,
+            Exposure::Cold { degrees } | Exposure::Hot { degrees } => *degrees,
+        }
+    }
+
+    /// Extra energy levels an activity costs
+    pub fn energy_cost(&self) -> u8 {
+        self.degrees().div_ceil(DEGREES_PER_LEVEL) as u8
+    }
+
+    /// Check if the temperature is too extreme to do anything
+    pub fn is_unbearable(&self) -> bool {
+        self.degrees() > REFUSAL_MARGIN_C
+    }
+}
+
+impl fmt::Display for Exposure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exposure::Comfortable => write!(f, "comfortable"),
+            Exposure::Cold { degrees } => write!(f, "{}°C too cold", degrees),
+            Exposure::Hot { degrees } => write!(f, "{}°C too hot", degrees),
+        }
+    }
+}
+
+/// Temperatures an entity can operate in without extra effort
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThermalRange {
+    pub min_c: i32,
+    pub max_c: i32,
+}
+
+impl ThermalRange {
+    pub const fn new(min_c: i32, max_c: i32) -> Self {
+        Self { min_c, max_c }
+    }
+
+    pub fn contains(&self, temperature_c: i32) -> bool {
+        (self.min_c..=self.max_c).contains(&temperature_c)
+    }
+
+    /// How a temperature feels for this range
+    pub fn exposure(&self, temperature_c: i32) -> Exposure {
+        if temperature_c < self.min_c {
+            Exposure::Cold {
+                degrees: self.min_c.abs_diff(temperature_c),
+            }
+        } else if temperature_c > self.max_c {
+            Exposure::Hot {
+                degrees: self.max_c.abs_diff(temperature_c),
+            }
+        } else {
+            Exposure::Comfortable
+        }
+    }
+}
+
+impl fmt::Display for ThermalRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}°C to {}°C", self.min_c, self.max_c)
+    }
+}
+
+/// Entities with a comfortable operating temperature
+pub trait ThermalTolerance: HasEnergy {
+    /// Temperatures this entity operates in without extra effort
+    fn thermal_range(&self) -> ThermalRange;
+
+    /// Cold-blooded animals take on the ambient temperature and slow down
+    fn is_ectothermic(&self) -> bool {
+        false
+    }
+
+    /// How a temperature feels to this entity
+    fn exposure(&self, temperature_c: i32) -> Exposure {
+        self.thermal_range().exposure(temperature_c)
+    }
+
+    /// Refuse activities in temperatures far outside the range
+    fn check_temperature(&self, temperature_c: i32) -> Result<(), ActivityError> {
+        let exposure = self.exposure(temperature_c);
+        if exposure.is_unbearable() {
+            return Err(ActivityError::EnvironmentalConstraint(format!(
+                "{}°C is {} for a range of {}",
+                temperature_c,
+                exposure,
+                self.thermal_range()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Speed at a temperature - ectotherms lose speed below their range
+    fn thermal_speed(&self, base_speed: u32, temperature_c: i32) -> u32 {
+        match self.exposure(temperature_c) {
+            Exposure::Cold { degrees } if self.is_ectothermic() => {
+                let slowdown = (degrees * ECTOTHERM_SLOWDOWN_PERCENT).min(100);
+                (base_speed * (100 - slowdown) / 100).max(1)
+            }
+            _ => base_speed,
+        }
+    }
+
+    /// Spend the extra energy a temperature costs, returning the levels spent
+    fn endure(&mut self, temperature_c: i32) -> Result<u8, ActivityError> {
+        self.check_temperature(temperature_c)?;
+        let cost = self.exposure(temperature_c).energy_cost();
+        self.consume_energy_levels(cost);
+        Ok(cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::{Dog, Penguin, Snake, dog::DogBreed, snake::SnakeSpecies};
+    use crate::behaviors::TravelMode;
+    use crate::core::{EnergyLevel, Terrain, Weather};
+    use crate::routes::{RoutePlanner, SegmentKind};
+    use crate::vehicles::airplane::{AirplaneEngine, AirplaneType};
+    use crate::vehicles::{Airplane, Car, car::EngineType};
+    use crate::world::{EnergySupply, Facility, Position, Site, UsesInfrastructure};
+
+    #[test]
+    fn test_exposure_costs_and_refusal() {
+        let range = ThermalRange::new(-10, 30);
+        assert_eq!(range.exposure(20), Exposure::Comfortable);
+        assert_eq!(range.exposure(35), Exposure::Hot { degrees: 5 });
+        assert_eq!(range.exposure(-25).energy_cost(), 2);
+        assert!(!range.exposure(-30).is_unbearable());
+        assert!(range.exposure(-31).is_unbearable());
+    }
+
+    #[test]
+    fn test_air_cools_with_altitude() {
+        assert_eq!(temperature_at_altitude(15, 0), 15);
+        assert_eq!(temperature_at_altitude(15, 2000), 2);
+        assert_eq!(temperature_at_altitude(15, 10_000), -50);
+    }
+
+    #[test]
+    fn test_species_tolerance() {
+        let mut penguin = Penguin::new("Pingu".to_string());
+        penguin.set_energy(EnergyLevel::Energetic);
+        assert_eq!(penguin.endure(Terrain::Glacier.temperature_c()).unwrap(), 0);
+        assert!(penguin.endure(Terrain::Desert.temperature_c()).is_err());
+        assert_eq!(penguin.endure(Terrain::Sand.temperature_c()).unwrap(), 1);
+        assert_eq!(penguin.energy(), EnergyLevel::Normal);
+
+        // A blizzard on snow is fine for a husky but too much for a greyhound
+        let blizzard = Terrain::Snow.temperature_c() + Weather::Blizzard.temperature_shift_c();
+        let husky = Dog::new("Balto".to_string(), DogBreed::Husky);
+        let greyhound = Dog::new("Flash".to_string(), DogBreed::Greyhound);
+        assert_eq!(husky.exposure(blizzard), Exposure::Comfortable);
+        assert!(greyhound.check_temperature(blizzard).is_err());
+    }
+
+    #[test]
+    fn test_moving_through_cold_and_heat() {
+        use crate::behaviors::{Driving, Pulling, Walking};
+
+        // A thin coat costs the greyhound an extra level on snow
+        let mut husky = Dog::new("Balto".to_string(), DogBreed::Husky);
+        let mut greyhound = Dog::new("Flash".to_string(), DogBreed::Greyhound);
+        husky.walk_on_terrain(Terrain::Snow).unwrap();
+        greyhound.walk_on_terrain(Terrain::Snow).unwrap();
+        assert_eq!(husky.energy() as u8, greyhound.energy() as u8 + 1);
+
+        let mut husky = Dog::new("Balto".to_string(), DogBreed::Husky);
+        let mut greyhound = Dog::new("Flash".to_string(), DogBreed::Greyhound);
+        husky.pull(5, Terrain::Snow).unwrap();
+        greyhound.pull(5, Terrain::Snow).unwrap();
+        assert_eq!(husky.energy() as u8, greyhound.energy() as u8 + 1);
+
+        // The desert is refused outright, before any energy is spent
+        let mut penguin = Penguin::new("Pingu".to_string());
+        let before = penguin.energy();
+        assert!(penguin.walk_on_terrain(Terrain::Desert).is_err());
+        assert_eq!(penguin.energy(), before);
+
+        let mut electric = Car::new(
+            "Volt".to_string(),
+            "Test Motors".to_string(),
+            2022,
+            EngineType::Electric {
+                battery_capacity: 80,
+            },
+        );
+        let mut diesel = Car::new(
+            "Hauler".to_string(),
+            "Test Motors".to_string(),
+            2022,
+            EngineType::Diesel {
+                cylinders: 4,
+                displacement: 2.0,
+            },
+        );
+        let services = Site::new("Services", Position::new(0, 0))
+            .with(Facility::Station {
+                supply: EnergySupply::Fuel,
+            })
+            .with(Facility::Station {
+                supply: EnergySupply::Charging,
+            });
+        electric.refuel(&services).unwrap();
+        diesel.refuel(&services).unwrap();
+        electric.drive_on_terrain(Terrain::Snow).unwrap();
+        diesel.drive_on_terrain(Terrain::Snow).unwrap();
+        assert_eq!(diesel.energy() as u8, electric.energy() as u8 + 1);
+    }
+
+    #[test]
+    fn test_snakes_slow_down_in_the_cold() {
+        let snake = Snake::new("Kaa".to_string(), SnakeSpecies::Python);
+        assert_eq!(snake.thermal_speed(10, 25), 10);
+        assert_eq!(snake.thermal_speed(10, 10), 6);
+        assert_eq!(snake.thermal_speed(10, -20), 1);
+    }
+
+    #[test]
+    fn test_vehicle_operating_ranges() {
+        let car = |engine_type| {
+            Car::new(
+                "Test".to_string(),
+                "Test Motors".to_string(),
+                2022,
+                engine_type,
+            )
+        };
+        let electric = car(EngineType::Electric {
+            battery_capacity: 80,
+        });
+        let diesel = car(EngineType::Diesel {
+            cylinders: 4,
+            displacement: 2.0,
+        });
+        let snow = SegmentKind::Land(Terrain::Snow);
+        let effort = |car: &Car| {
+            RoutePlanner::profile(car, TravelMode::Drive, snow)
+                .unwrap()
+                .effort
+        };
+        assert_eq!(effort(&electric), effort(&diesel) + 1); // Batteries lose range in the cold
+
+        let airplane = |engine_type| {
+            Airplane::new(
+                "Test".to_string(),
+                "Test Aero".to_string(),
+                2020,
+                AirplaneType::Military,
+                12,
+                engine_type,
+            )
+        };
+        let piston = airplane(AirplaneEngine::Piston {
+            engines: 1,
+            power_each: 300,
+        });
+        let jet = airplane(AirplaneEngine::Jet {
+            engines: 1,
+            thrust_each: 80,
+        });
+        let high = temperature_at_altitude(MILD_TEMPERATURE_C, 6000);
+        assert_eq!(jet.exposure(high), Exposure::Comfortable);
+        assert_eq!(piston.exposure(high).energy_cost(), 2);
+    }
+}
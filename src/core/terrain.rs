@@ -1,3 +1,4 @@
+use crate::core::temperature::MILD_TEMPERATURE_C;
 use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -160,6 +161,21 @@ impl Terrain {
         }
     }
 
+    /// Typical ground temperature in degrees Celsius
+    pub fn temperature_c(&self) -> i32 {
+        match self {
+            Terrain::Glacier => -25,
+            Terrain::Snow => -10,
+            Terrain::Mountain => -5,
+            Terrain::Extreme => 0,
+            Terrain::Steep | Terrain::Cliff => 10,
+            Terrain::Sand | Terrain::Swamp => 25,
+            Terrain::Desert => 45,
+            Terrain::Volcano => 70,
+            _ => MILD_TEMPERATURE_C,
+        }
+    }
+
     /// Get all terrain variants
     pub fn all_terrains() -> Vec<Terrain> {
         vec![
@@ -119,18 +119,18 @@ impl Weather {
 
     /// Check if flying is safe in this weather
     pub fn is_safe_for_flying(&self) -> bool {
-        match self {
-            Weather::Hurricane | Weather::Tornado | Weather::Thunderstorm => false,
-            _ => true,
-        }
+        !matches!(
+            self,
+            Weather::Hurricane | Weather::Tornado | Weather::Thunderstorm
+        )
     }
 
     /// Check if this weather condition affects visibility
     pub fn affects_visibility(&self) -> bool {
-        match self {
-            Weather::Clear | Weather::Sunny | Weather::PartlyCloudy | Weather::LightWind => false,
-            _ => true,
-        }
+        !matches!(
+            self,
+            Weather::Clear | Weather::Sunny | Weather::PartlyCloudy | Weather::LightWind
+        )
     }
 
     /// Get the visibility level (1-5, where 5 is perfect visibility)
@@ -149,6 +149,17 @@ impl Weather {
         }
     }
 
+    /// How much this weather shifts the temperature, in degrees Celsius
+    pub fn temperature_shift_c(&self) -> i32 {
+        match self {
+            Weather::Sunny => 5,
+            Weather::Hail => -5,
+            Weather::Snow => -10,
+            Weather::Blizzard => -20,
+            _ => 0,
+        }
+    }
+
     /// Get weather conditions suitable for a given skill level
     pub fn suitable_for_skill_level(skill_level: u8) -> Vec<Weather> {
         Self::all_weather_conditions()
@@ -0,0 +1,128 @@
+use crate::core::EnergyLevel;
+use crate::crew::operator::{License, Operator, VehicleClass};
+use crate::vehicles::Vehicle;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CrewError {
+    #[error("{operator} does not hold a {required}")]
+    NotLicensed { operator: String, required: License },
+
+    #[error("{operator} is not trained on {class} vehicles")]
+    NotTrained {
+        operator: String,
+        class: VehicleClass,
+    },
+
+    #[error("The controls are already taken by {current}")]
+    SeatTaken { current: String },
+
+    #[error("{operator} is too tired to operate: need {required}, have {current}")]
+    Unfit {
+        operator: String,
+        required: EnergyLevel,
+        current: EnergyLevel,
+    },
+}
+
+/// Returned when an operator cannot be assigned; gives the operator back
+#[derive(Error, Debug)]
+#[error("{reason}")]
+pub struct AssignError {
+    pub reason: CrewError,
+    pub operator: Box<dyn Operator>,
+}
+
+/// Vehicles operated by a driver or pilot
+///
+/// Without an operator a vehicle keeps its built-in behavior. Once someone
+/// is at the controls, their skill limits the vehicle's performance and
+/// their fatigue can ground it.
+pub trait Crewed: Vehicle {
+    /// Class of this vehicle, used to look up the operator's skill
+    fn vehicle_class(&self) -> VehicleClass;
+
+    /// License needed to operate this vehicle
+    fn required_license(&self) -> License;
+
+    /// Get the operator seat
+    fn crew_seat(&self) -> &Option<Box<dyn Operator>>;
+
+    /// Get mutable access to the operator seat
+    fn crew_seat_mut(&mut self) -> &mut Option<Box<dyn Operator>>;
+
+    /// Get the current operator, if any
+    fn operator(&self) -> Option<&dyn Operator> {
+        self.crew_seat().as_deref()
+    }
+
+    /// Put an operator at the controls
+    fn assign_operator(&mut self, operator: Box<dyn Operator>) -> Result<String, AssignError> {
+        let reason = if let Some(current) = self.operator() {
+            Some(CrewError::SeatTaken {
+                current: current.name(),
+            })
+        } else if !operator.holds_license(self.required_license()) {
+            Some(CrewError::NotLicensed {
+                operator: operator.name(),
+                required: self.required_license(),
+            })
+        } else if operator.base_skill(self.vehicle_class()) == 0 {
+            Some(CrewError::NotTrained {
+                operator: operator.name(),
+                class: self.vehicle_class(),
+            })
+        } else {
+            None
+        };
+
+        if let Some(reason) = reason {
+            return Err(AssignError { reason, operator });
+        }
+
+        let message = format!("{} takes the controls of {}", operator.name(), self.name());
+        *self.crew_seat_mut() = Some(operator);
+        Ok(message)
+    }
+
+    /// Take the operator out of the vehicle
+    fn unassign_operator(&mut self) -> Option<Box<dyn Operator>> {
+        self.crew_seat_mut().take()
+    }
+
+    /// Current operator skill for this vehicle, including fatigue
+    fn crew_skill(&self) -> Option<u8> {
+        self.operator()
+            .map(|operator| operator.skill(self.vehicle_class()))
+    }
+
+    /// Refuse to operate when the operator is too tired
+    fn check_crew(&self) -> Result<(), CrewError> {
+        match self.operator() {
+            Some(operator) if !operator.is_fit_to_operate(self.vehicle_class()) => {
+                Err(CrewError::Unfit {
+                    operator: operator.name(),
+                    required: operator.min_operating_energy(self.vehicle_class()),
+                    current: operator.energy(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Share of a vehicle limit the operator can use (skill 5 = 100%, 1 = 60%)
+    fn crew_limited(&self, vehicle_limit: u32) -> u32 {
+        match self.crew_skill() {
+            Some(skill) => vehicle_limit * (50 + 10 * skill.min(5) as u32) / 100,
+            None => vehicle_limit,
+        }
+    }
+
+    /// Add distance to the operator's duty log
+    fn log_crew_duty(&mut self, km: u32) {
+        let class = self.vehicle_class();
+        if let Some(operator) = self.crew_seat_mut() {
+            operator.log_duty(class, km);
+        }
+    }
+}
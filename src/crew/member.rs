@@ -0,0 +1,146 @@
+use crate::core::{EnergyLevel, HasEnergy};
+use crate::crew::operator::{License, Operator, VehicleClass};
+
+/// Someone who operates vehicles - a driver, skipper or pilot
+///
+/// What a crew member may operate comes from their licenses and skills.
+/// Rest rules follow the vehicle at hand: at the controls of an aircraft
+/// they must be at least `Normal`, but cover more distance per energy level.
+#[derive(Debug, Clone)]
+pub struct CrewMember {
+    pub name: String,
+    pub energy: EnergyLevel,
+    pub licenses: Vec<License>,
+    pub skills: Vec<(VehicleClass, u8)>,
+    pub duty_km: u32,
+}
+
+impl CrewMember {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            energy: EnergyLevel::Energetic,
+            licenses: Vec::new(),
+            skills: Vec::new(),
+            duty_km: 0,
+        }
+    }
+
+    /// Grant a license
+    pub fn with_license(mut self, license: License) -> Self {
+        if !self.licenses.contains(&license) {
+            self.licenses.push(license);
+        }
+        self
+    }
+
+    /// Set the trained skill (1-5) for a vehicle class
+    pub fn with_skill(mut self, class: VehicleClass, skill: u8) -> Self {
+        self.skills.retain(|(trained, _)| *trained != class);
+        self.skills.push((class, skill.min(5)));
+        self
+    }
+}
+
+impl HasEnergy for CrewMember {
+    fn energy(&self) -> EnergyLevel {
+        self.energy
+    }
+
+    fn set_energy(&mut self, level: EnergyLevel) {
+        self.energy = level;
+    }
+}
+
+impl Operator for CrewMember {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn licenses(&self) -> &[License] {
+        &self.licenses
+    }
+
+    fn base_skill(&self, class: VehicleClass) -> u8 {
+        self.skills
+            .iter()
+            .find(|(trained, _)| *trained == class)
+            .map(|(_, skill)| *skill)
+            .unwrap_or(0)
+    }
+
+    fn duty_km(&self) -> u32 {
+        self.duty_km
+    }
+
+    fn set_duty_km(&mut self, km: u32) {
+        self.duty_km = km;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fatigue_reduces_skill() {
+        let mut driver = CrewMember::new("Alex".to_string()).with_skill(VehicleClass::Car, 4);
+        assert_eq!(driver.skill(VehicleClass::Car), 4);
+        assert_eq!(driver.skill(VehicleClass::Ship), 0);
+
+        driver.log_duty(VehicleClass::Car, 450); // Two levels at 200 km each
+        assert_eq!(driver.energy(), EnergyLevel::Tired);
+        assert_eq!(driver.skill(VehicleClass::Car), 3);
+        assert!(driver.is_fit_to_operate(VehicleClass::Car));
+
+        driver.log_duty(VehicleClass::Car, 150);
+        assert_eq!(driver.energy(), EnergyLevel::Exhausted);
+        assert!(!driver.is_fit_to_operate(VehicleClass::Car));
+
+        driver.take_break();
+        assert_eq!(driver.energy(), EnergyLevel::Tired);
+        assert_eq!(driver.duty_km(), 0);
+    }
+
+    #[test]
+    fn test_licenses() {
+        let driver = CrewMember::new("Alex".to_string())
+            .with_license(License::Car)
+            .with_license(License::Car);
+
+        assert_eq!(driver.licenses().len(), 1);
+        assert!(driver.holds_license(License::Car));
+        assert!(!driver.holds_license(License::Maritime));
+    }
+
+    #[test]
+    fn test_tired_pilot_is_unfit() {
+        let mut pilot = CrewMember::new("Sam".to_string())
+            .with_license(License::CommercialPilot)
+            .with_license(License::Car);
+        assert!(pilot.is_fit_to_operate(VehicleClass::Airplane));
+
+        pilot.set_energy(EnergyLevel::Tired);
+        assert!(!pilot.is_fit_to_operate(VehicleClass::Airplane));
+        assert!(pilot.is_fit_to_operate(VehicleClass::Car)); // Can still drive home
+    }
+
+    #[test]
+    fn test_commercial_license_covers_private() {
+        let pilot = CrewMember::new("Sam".to_string()).with_license(License::CommercialPilot);
+
+        assert!(pilot.holds_license(License::PrivatePilot));
+        assert!(!pilot.holds_license(License::Helicopter));
+    }
+
+    #[test]
+    fn test_fatigue_follows_the_vehicle() {
+        let mut flying = CrewMember::new("Sam".to_string()).with_license(License::Helicopter);
+        let mut driving = flying.clone();
+
+        flying.log_duty(VehicleClass::Helicopter, 400);
+        driving.log_duty(VehicleClass::Car, 400);
+        assert_eq!(flying.energy(), EnergyLevel::Energetic);
+        assert_eq!(driving.energy(), EnergyLevel::Tired);
+    }
+}
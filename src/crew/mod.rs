@@ -0,0 +1,7 @@
+pub mod crewed;
+pub mod member;
+pub mod operator;
+
+pub use crewed::{AssignError, CrewError, Crewed};
+pub use member::CrewMember;
+pub use operator::{License, Operator, VehicleClass};
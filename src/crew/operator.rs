@@ -0,0 +1,148 @@
+use crate::core::{EnergyLevel, HasEnergy};
+use std::fmt;
+
+/// Broad vehicle categories an operator can be trained on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VehicleClass {
+    Car,
+    Motorcycle,
+    Airplane,
+    Helicopter,
+    Ship,
+}
+
+impl fmt::Display for VehicleClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            VehicleClass::Car => "Car",
+            VehicleClass::Motorcycle => "Motorcycle",
+            VehicleClass::Airplane => "Airplane",
+            VehicleClass::Helicopter => "Helicopter",
+            VehicleClass::Ship => "Ship",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl VehicleClass {
+    /// Check if this class flies, which brings stricter rest rules
+    pub fn is_aircraft(&self) -> bool {
+        matches!(self, VehicleClass::Airplane | VehicleClass::Helicopter)
+    }
+
+    /// Kilometers on duty that cost one energy level at the controls
+    pub fn km_per_fatigue_level(&self) -> u32 {
+        if self.is_aircraft() { 800 } else { 200 }
+    }
+
+    /// Minimum energy level needed to take the controls
+    ///
+    /// Pilots must be at least `Normal`, but cover more distance per level.
+    pub fn min_operating_energy(&self) -> EnergyLevel {
+        if self.is_aircraft() {
+            EnergyLevel::Normal
+        } else {
+            EnergyLevel::Tired
+        }
+    }
+}
+
+/// Licenses an operator can hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum License {
+    Car,
+    Motorcycle,
+    PrivatePilot,
+    CommercialPilot,
+    Helicopter,
+    Maritime,
+}
+
+impl License {
+    /// Check if holding this license satisfies the `required` one
+    pub fn covers(&self, required: License) -> bool {
+        *self == required
+            || (*self == License::CommercialPilot && required == License::PrivatePilot)
+    }
+}
+
+impl fmt::Display for License {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            License::Car => "Car",
+            License::Motorcycle => "Motorcycle",
+            License::PrivatePilot => "Private Pilot",
+            License::CommercialPilot => "Commercial Pilot",
+            License::Helicopter => "Helicopter",
+            License::Maritime => "Maritime",
+        };
+        write!(f, "{} license", name)
+    }
+}
+
+/// A person who can operate vehicles - has their own energy (fatigue)
+pub trait Operator: HasEnergy + fmt::Debug {
+    fn name(&self) -> String;
+
+    /// Licenses held by this operator
+    fn licenses(&self) -> &[License];
+
+    /// Trained skill for a vehicle class (0 = untrained, 1-5 scale)
+    fn base_skill(&self, class: VehicleClass) -> u8;
+
+    /// Kilometers on duty since the operator was last fresh
+    fn duty_km(&self) -> u32;
+
+    fn set_duty_km(&mut self, km: u32);
+
+    /// Kilometers on duty that cost one energy level when operating `class`
+    fn km_per_fatigue_level(&self, class: VehicleClass) -> u32 {
+        class.km_per_fatigue_level()
+    }
+
+    /// Minimum energy level needed to operate a vehicle of `class`
+    fn min_operating_energy(&self, class: VehicleClass) -> EnergyLevel {
+        class.min_operating_energy()
+    }
+
+    /// Check if the operator holds a license that covers `required`
+    fn holds_license(&self, required: License) -> bool {
+        self.licenses()
+            .iter()
+            .any(|license| license.covers(required))
+    }
+
+    /// Check if the operator is rested enough to take the controls of `class`
+    fn is_fit_to_operate(&self, class: VehicleClass) -> bool {
+        self.energy() >= self.min_operating_energy(class)
+    }
+
+    /// Skill for a vehicle class, reduced by fatigue
+    fn skill(&self, class: VehicleClass) -> u8 {
+        let base = self.base_skill(class);
+        let fatigue_penalty = match self.energy() {
+            EnergyLevel::Collapsed => return 0,
+            EnergyLevel::Exhausted => 2,
+            EnergyLevel::Tired => 1,
+            _ => 0,
+        };
+        base.saturating_sub(fatigue_penalty).max(base.min(1))
+    }
+
+    /// Record time at the controls of `class`; every `km_per_fatigue_level` costs energy
+    fn log_duty(&mut self, class: VehicleClass, km: u32) {
+        let per_level = self.km_per_fatigue_level(class).max(1);
+        let before = self.duty_km() / per_level;
+        let total = self.duty_km().saturating_add(km);
+        self.set_duty_km(total);
+
+        let levels = (total / per_level - before).min(u8::MAX as u32) as u8;
+        self.consume_energy_levels(levels);
+    }
+
+    /// Take a break: recovers one energy level and starts a new duty period
+    fn take_break(&mut self) {
+        self.rest();
+        self.set_duty_km(0);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::core::energy_level::EnergyLevel;
+use crate::core::health::Injury;
 
 use thiserror::Error;
 
@@ -26,5 +27,5 @@ pub enum ActivityError {
     },
 
     #[error("Recovery needed: {0}")]
-    RecoveryNeeded(String),
+    RecoveryNeeded(Injury),
 }
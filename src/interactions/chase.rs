@@ -0,0 +1,566 @@
+//! Predator-Prey Chases
+//!
+//! A [`Chase`] pits a predator against its prey, turn by turn. Each turn
+//! lasts ten seconds in which both sides sprint at twice their cruising
+//! speed in their current mode. Sprinting is exhausting: every three turns
+//! cost an energy level, and below Normal energy an animal only manages
+//! half its sprint.
+//!
+//! Modes decide who can reach whom: a flyer can swoop down on prey on land
+//! or at the water's surface, but nothing on land or in the water can reach
+//! prey in the air, and a swimmer cannot follow prey onto land. Each turn
+//! the prey picks the mode that gains it the most ground - escaping into
+//! water or air when the [`Arena`] allows it - and the predator answers
+//! with its fastest mode that can still reach the prey. Switching mode
+//! takes the whole turn.
+//!
+//! The predator keeps the prey in sight as far as its best [`Sensing`]
+//! sensor reaches in the chase's weather; predators without sensors rely
+//! on plain eyesight. Sonar keeps tracking swimmers through fog, while an
+//! eagle loses a snake in it.
+
+use crate::behaviors::{Participant, Sensor, TravelMode};
+use crate::core::{EnergyLevel, Weather};
+use std::fmt;
+use thiserror::Error;
+
+/// Sprinting turns that cost one energy level
+const SPRINT_TURNS_PER_LEVEL: u32 = 3;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ChaseError {
+    #[error("{participant} cannot move anywhere in the {arena}")]
+    CannotMove { participant: String, arena: Arena },
+}
+
+/// Where a chase takes place; the open sky is always available
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arena {
+    pub land: bool,
+    pub water: bool,
+}
+
+impl Arena {
+    /// Fields, plains and roads
+    pub fn open_land() -> Self {
+        Self {
+            land: true,
+            water: false,
+        }
+    }
+
+    /// Open sea, far from any shore
+    pub fn open_water() -> Self {
+        Self {
+            land: false,
+            water: true,
+        }
+    }
+
+    /// Where land meets water
+    pub fn shoreline() -> Self {
+        Self {
+            land: true,
+            water: true,
+        }
+    }
+
+    /// Check if a mode can be used here
+    pub fn allows(&self, mode: TravelMode) -> bool {
+        match mode {
+            TravelMode::Walk | TravelMode::Drive => self.land,
+            TravelMode::Swim => self.water,
+            TravelMode::Fly => true,
+        }
+    }
+}
+
+impl fmt::Display for Arena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match (self.land, self.water) {
+            (true, true) => "shoreline",
+            (true, false) => "open land",
+            (false, true) => "open water",
+            (false, false) => "open sky",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Check if a pursuer moving in one mode can strike a target in another
+pub fn can_reach(pursuer: TravelMode, target: TravelMode) -> bool {
+    match (pursuer, target) {
+        _ if pursuer == target => true,
+        // Both on land
+        (TravelMode::Walk, TravelMode::Drive) | (TravelMode::Drive, TravelMode::Walk) => true,
+        // Swooping down from the air
+        (TravelMode::Fly, TravelMode::Walk | TravelMode::Drive | TravelMode::Swim) => true,
+        _ => false,
+    }
+}
+
+/// Why the prey got away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Escape {
+    /// The prey moved where the predator cannot follow
+    OutOfReach(TravelMode),
+    /// The prey got too far ahead to be seen
+    OutOfSight,
+    /// The chase went on too long
+    Outlasted,
+}
+
+impl fmt::Display for Escape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Escape::OutOfReach(mode) => write!(f, "out of reach ({})", mode),
+            Escape::OutOfSight => write!(f, "out of sight"),
+            Escape::Outlasted => write!(f, "outlasted the predator"),
+        }
+    }
+}
+
+/// How a chase ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChaseOutcome {
+    Caught { turn: u32 },
+    Escaped { turn: u32, escape: Escape },
+    PredatorGaveUp { turn: u32 },
+}
+
+impl fmt::Display for ChaseOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChaseOutcome::Caught { turn } => write!(f, "prey caught on turn {}", turn),
+            ChaseOutcome::Escaped { turn, escape } => {
+                write!(f, "prey escaped on turn {}: {}", turn, escape)
+            }
+            ChaseOutcome::PredatorGaveUp { turn } => {
+                write!(f, "predator gave up on turn {}", turn)
+            }
+        }
+    }
+}
+
+/// What happened in one turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChaseTurn {
+    pub turn: u32,
+    pub predator_mode: TravelMode,
+    pub prey_mode: TravelMode,
+    /// Meters between predator and prey at the end of the turn
+    pub gap_m: i64,
+    pub predator_energy: EnergyLevel,
+    pub prey_energy: EnergyLevel,
+    /// Notable events, such as a switch of mode
+    pub events: Vec<String>,
+}
+
+impl fmt::Display for ChaseTurn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Turn {}: predator {} ({}), prey {} ({}), gap {} m",
+            self.turn,
+            self.predator_mode,
+            self.predator_energy,
+            self.prey_mode,
+            self.prey_energy,
+            self.gap_m.max(0)
+        )?;
+        if !self.events.is_empty() {
+            write!(f, " - {}", self.events.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Turn-by-turn record of a chase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChaseLog {
+    pub predator: String,
+    pub prey: String,
+    pub turns: Vec<ChaseTurn>,
+    pub outcome: ChaseOutcome,
+}
+
+impl ChaseLog {
+    pub fn prey_caught(&self) -> bool {
+        matches!(self.outcome, ChaseOutcome::Caught { .. })
+    }
+}
+
+impl fmt::Display for ChaseLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} chases {}", self.predator, self.prey)?;
+        for turn in &self.turns {
+            writeln!(f, "  {}", turn)?;
+        }
+        write!(f, "{}", self.outcome)
+    }
+}
+
+/// One side of a chase
+struct Runner<'a> {
+    participant: &'a mut dyn Participant,
+    mode: TravelMode,
+    sprint_turns: u32,
+}
+
+impl Runner<'_> {
+    /// Meters covered sprinting for one turn in a mode
+    fn sprint_m(&self, mode: TravelMode) -> i64 {
+        let speed_kmh = self.participant.cruising_speed(mode).unwrap_or(0) * 2;
+        let speed_kmh = match self.participant.energy() {
+            EnergyLevel::Collapsed => 0,
+            energy if energy < EnergyLevel::Normal => speed_kmh / 2,
+            _ => speed_kmh,
+        };
+        speed_kmh as i64 * 25 / 9 // Ten seconds at this speed
+    }
+
+    /// Spend a turn either sprinting or switching mode, returning the meters covered
+    fn take_turn(&mut self, mode: TravelMode) -> i64 {
+        let meters = if mode == self.mode {
+            self.sprint_m(mode)
+        } else {
+            self.mode = mode;
+            0
+        };
+
+        self.sprint_turns += 1;
+        if self.sprint_turns.is_multiple_of(SPRINT_TURNS_PER_LEVEL) {
+            self.participant.consume_energy();
+        }
+        meters
+    }
+}
+
+/// A pursuit between a predator and its prey
+#[derive(Debug, Clone, Copy)]
+pub struct Chase {
+    pub arena: Arena,
+    /// Meters between predator and prey when the chase starts
+    pub head_start_m: u32,
+    /// Mode the prey is in when spotted - its fastest one by default
+    pub prey_start: Option<TravelMode>,
+    pub weather: Weather,
+    pub max_turns: u32,
+}
+
+impl Chase {
+    pub fn new(arena: Arena, head_start_m: u32) -> Self {
+        Self {
+            arena,
+            head_start_m,
+            prey_start: None,
+            weather: Weather::Clear,
+            max_turns: 60,
+        }
+    }
+
+    /// Catch the prey unaware in a particular mode
+    pub fn prey_starts_in(mut self, mode: TravelMode) -> Self {
+        self.prey_start = Some(mode);
+        self
+    }
+
+    /// Chase in weather that limits how far the predator can see
+    pub fn in_weather(mut self, weather: Weather) -> Self {
+        self.weather = weather;
+        self
+    }
+
+    /// Run the chase, spending both sides' energy
+    pub fn run(
+        &self,
+        predator: &mut dyn Participant,
+        prey: &mut dyn Participant,
+    ) -> Result<ChaseLog, ChaseError> {
+        let predator_modes = self.modes(predator)?;
+        let prey_modes = self.modes(prey)?;
+
+        let prey_mode = self
+            .prey_start
+            .filter(|mode| prey_modes.contains(mode))
+            .unwrap_or_else(|| Self::fastest(prey, &prey_modes));
+        let predator_mode =
+            Self::pursuit(predator, &predator_modes, prey_mode).unwrap_or(predator_modes[0]);
+
+        let mut log = ChaseLog {
+            predator: predator.participant_name(),
+            prey: prey.participant_name(),
+            turns: Vec::new(),
+            outcome: ChaseOutcome::Escaped {
+                turn: 0,
+                escape: Escape::OutOfReach(prey_mode),
+            },
+        };
+        if Self::pursuit(predator, &predator_modes, prey_mode).is_none() {
+            return Ok(log); // Never in danger
+        }
+        if self.head_start_m as i64 > self.sight_range_m(predator, prey_mode) {
+            log.outcome = ChaseOutcome::Escaped {
+                turn: 0,
+                escape: Escape::OutOfSight,
+            };
+            return Ok(log); // Never spotted
+        }
+
+        let mut predator = Runner {
+            participant: predator,
+            mode: predator_mode,
+            sprint_turns: 0,
+        };
+        let mut prey = Runner {
+            participant: prey,
+            mode: prey_mode,
+            sprint_turns: 0,
+        };
+        let mut gap = self.head_start_m as i64;
+
+        for turn in 1..=self.max_turns {
+            let mut events = Vec::new();
+
+            // The prey goes where it gains the most ground
+            let escape_mode = Self::escape(&prey, &predator, &predator_modes, &prey_modes);
+            if escape_mode != prey.mode {
+                events.push(format!("prey switches to {}", escape_mode));
+            }
+            gap += prey.take_turn(escape_mode);
+
+            // The predator follows as best it can
+            let pursuit = Self::pursuit(predator.participant, &predator_modes, prey.mode);
+            if let Some(mode) = pursuit {
+                if mode != predator.mode {
+                    events.push(format!("predator switches to {}", mode));
+                }
+                gap -= predator.take_turn(mode);
+            }
+
+            log.turns.push(ChaseTurn {
+                turn,
+                predator_mode: predator.mode,
+                prey_mode: prey.mode,
+                gap_m: gap,
+                predator_energy: predator.participant.energy(),
+                prey_energy: prey.participant.energy(),
+                events,
+            });
+
+            let outcome = if pursuit.is_none() {
+                Some(ChaseOutcome::Escaped {
+                    turn,
+                    escape: Escape::OutOfReach(prey.mode),
+                })
+            } else if gap <= 0 {
+                Some(ChaseOutcome::Caught { turn })
+            } else if gap > self.sight_range_m(predator.participant, prey.mode) {
+                Some(ChaseOutcome::Escaped {
+                    turn,
+                    escape: Escape::OutOfSight,
+                })
+            } else if predator.participant.energy() < EnergyLevel::Tired {
+                Some(ChaseOutcome::PredatorGaveUp { turn })
+            } else {
+                None
+            };
+            if let Some(outcome) = outcome {
+                log.outcome = outcome;
+                return Ok(log);
+            }
+        }
+
+        log.outcome = ChaseOutcome::Escaped {
+            turn: self.max_turns,
+            escape: Escape::Outlasted,
+        };
+        Ok(log)
+    }
+
+    /// How far away the predator can still make out prey moving this way
+    fn sight_range_m(&self, predator: &dyn Participant, prey_mode: TravelMode) -> i64 {
+        let range_m = match predator.as_sensor() {
+            Some(sensing) => sensing.detection_range_m(self.weather, prey_mode),
+            None => Sensor::unaided().effective_range_m(self.weather),
+        };
+        range_m as i64
+    }
+
+    /// Modes a participant can use in the arena
+    fn modes(&self, participant: &dyn Participant) -> Result<Vec<TravelMode>, ChaseError> {
+        let modes: Vec<TravelMode> = participant
+            .travel_modes()
+            .into_iter()
+            .filter(|mode| self.arena.allows(*mode))
+            .collect();
+        if modes.is_empty() {
+            return Err(ChaseError::CannotMove {
+                participant: participant.participant_name(),
+                arena: self.arena,
+            });
+        }
+        Ok(modes)
+    }
+
+    fn fastest(participant: &dyn Participant, modes: &[TravelMode]) -> TravelMode {
+        *modes
+            .iter()
+            .max_by_key(|mode| participant.cruising_speed(**mode))
+            .unwrap_or(&modes[0])
+    }
+
+    /// Fastest predator mode that can reach the prey's mode
+    fn pursuit(
+        predator: &dyn Participant,
+        modes: &[TravelMode],
+        prey_mode: TravelMode,
+    ) -> Option<TravelMode> {
+        modes
+            .iter()
+            .copied()
+            .filter(|mode| can_reach(*mode, prey_mode))
+            .max_by_key(|mode| predator.cruising_speed(*mode))
+    }
+
+    /// Mode in which the prey gains the most ground; unreachable modes win outright
+    fn escape(
+        prey: &Runner,
+        predator: &Runner,
+        predator_modes: &[TravelMode],
+        prey_modes: &[TravelMode],
+    ) -> TravelMode {
+        let advantage =
+            |mode: TravelMode| match Self::pursuit(predator.participant, predator_modes, mode) {
+                None => i64::MAX,
+                Some(pursuit) => prey.sprint_m(mode) - predator.sprint_m(pursuit),
+            };
+
+        // Stay put on ties - switching costs a turn
+        prey_modes
+            .iter()
+            .copied()
+            .filter(|mode| advantage(*mode) > advantage(prey.mode))
+            .max_by_key(|mode| advantage(*mode))
+            .unwrap_or(prey.mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::dog::DogBreed;
+    use crate::animals::snake::SnakeSpecies;
+    use crate::animals::whale::WhaleSpecies;
+    use crate::animals::{Dog, Duck, Eagle, Penguin, Snake, Whale};
+    use crate::core::HasEnergy;
+
+    #[test]
+    fn test_eagle_catches_snake() {
+        let mut eagle = Eagle::new("Baldy".to_string());
+        let mut snake = Snake::new("Kaa".to_string(), SnakeSpecies::Viper);
+
+        let log = Chase::new(Arena::shoreline(), 100)
+            .run(&mut eagle, &mut snake)
+            .unwrap();
+        assert!(log.prey_caught(), "{}", log);
+        assert!(
+            log.turns
+                .iter()
+                .all(|turn| turn.predator_mode == TravelMode::Fly)
+        );
+    }
+
+    #[test]
+    fn test_prey_hides_in_fog() {
+        let mut eagle = Eagle::new("Baldy".to_string());
+        let mut snake = Snake::new("Kaa".to_string(), SnakeSpecies::Viper);
+
+        let log = Chase::new(Arena::shoreline(), 1000)
+            .in_weather(Weather::Fog)
+            .run(&mut eagle, &mut snake)
+            .unwrap();
+        assert_eq!(
+            log.outcome,
+            ChaseOutcome::Escaped {
+                turn: 0,
+                escape: Escape::OutOfSight
+            }
+        );
+
+        let log = Chase::new(Arena::shoreline(), 1000)
+            .run(&mut eagle, &mut snake)
+            .unwrap();
+        assert!(log.prey_caught(), "{}", log);
+
+        // Sonar tracks a swimmer through fog just as well
+        let mut orca = Whale::new("Shamu".to_string(), WhaleSpecies::Orca);
+        let mut penguin = Penguin::new("Pingu".to_string());
+        let log = Chase::new(Arena::open_water(), 400)
+            .in_weather(Weather::Fog)
+            .run(&mut orca, &mut penguin)
+            .unwrap();
+        assert!(log.prey_caught(), "{}", log);
+    }
+
+    #[test]
+    fn test_penguin_escapes_orca_onto_land() {
+        let orca = || Whale::new("Shamu".to_string(), WhaleSpecies::Orca);
+        let penguin = || Penguin::new("Pingu".to_string());
+
+        let log = Chase::new(Arena::shoreline(), 200)
+            .run(&mut orca(), &mut penguin())
+            .unwrap();
+        assert_eq!(
+            log.outcome,
+            ChaseOutcome::Escaped {
+                turn: 1,
+                escape: Escape::OutOfReach(TravelMode::Walk)
+            }
+        );
+
+        // No ice floe to climb onto in open water
+        let log = Chase::new(Arena::open_water(), 200)
+            .run(&mut orca(), &mut penguin())
+            .unwrap();
+        assert!(log.prey_caught(), "{}", log);
+    }
+
+    #[test]
+    fn test_duck_takes_off() {
+        let mut dog = Dog::new("Flash".to_string(), DogBreed::Greyhound);
+        let mut duck = Duck::new("Donald".to_string());
+
+        let log = Chase::new(Arena::open_land(), 20)
+            .prey_starts_in(TravelMode::Walk)
+            .run(&mut dog, &mut duck)
+            .unwrap();
+        assert!(log.turns[0].events[0].contains("prey switches to Fly"));
+        assert!(matches!(
+            log.outcome,
+            ChaseOutcome::Escaped {
+                escape: Escape::OutOfReach(TravelMode::Fly),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_tired_predator_gives_up() {
+        let mut hunter = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        hunter.set_energy(EnergyLevel::Normal);
+        let mut prey = Dog::new("Max".to_string(), DogBreed::Labrador);
+
+        let log = Chase::new(Arena::open_land(), 50)
+            .run(&mut hunter, &mut prey)
+            .unwrap();
+        assert_eq!(log.outcome, ChaseOutcome::PredatorGaveUp { turn: 6 });
+        assert_eq!(hunter.energy(), EnergyLevel::Exhausted);
+
+        let mut whale = Whale::new("Shamu".to_string(), WhaleSpecies::Orca);
+        assert!(matches!(
+            Chase::new(Arena::open_land(), 50).run(&mut whale, &mut prey),
+            Err(ChaseError::CannotMove { .. })
+        ));
+    }
+}
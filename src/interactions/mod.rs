@@ -0,0 +1,3 @@
+pub mod chase;
+
+pub use chase::{Arena, Chase, ChaseError, ChaseLog, ChaseOutcome, ChaseTurn, Escape, can_reach};
@@ -1,6 +1,14 @@
+pub mod airspace;
 pub mod animals;
 pub mod behaviors;
 pub mod competitions;
 pub mod core;
+pub mod crew;
 pub mod errors;
+pub mod interactions;
+pub mod logistics;
+pub mod missions;
+pub mod registry;
+pub mod routes;
 pub mod vehicles;
+pub mod world;
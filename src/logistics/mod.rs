@@ -0,0 +1,5 @@
+pub mod planner;
+
+pub use planner::{
+    Delivery, DeliveryLeg, FleetVehicle, Hub, LogisticsError, LogisticsPlanner, Schedule, Shipment,
+};
@@ -0,0 +1,712 @@
+//! Delivery Planning
+//!
+//! The [`LogisticsPlanner`] schedules [`Shipment`]s on a fleet of carriers
+//! across a [`GridMap`]. A shipment can change vehicles at a [`Hub`] - a
+//! port, an airfield or a depot - so a truck brings it to the quay, a
+//! ship takes it across the sea and another truck delivers it. Cargo is
+//! handed over anywhere within one tile of a hub, its origin or its
+//! destination, so a ship at the quay and a truck on the pier can swap it.
+//!
+//! Shipments are planned one after another, in the order given. For each,
+//! the planner searches the cheapest chain of legs between origin, hubs and
+//! destination, pricing every leg with the [`Pathfinder`] under the chosen
+//! [`PlanPreference`]: fastest delivery, or least energy (fuel). A leg
+//! includes the empty run that brings the vehicle to the pickup point.
+//! Vehicles only take cargo that fits in their free capacity, never spend
+//! their last energy level, and carry one shipment at a time.
+
+use crate::behaviors::{Participant, TravelMode};
+use crate::routes::PlanPreference;
+use crate::world::{GridMap, GridPath, Pathfinder, Position, pathfinding::is_passable};
+use std::fmt;
+use thiserror::Error;
+
+/// Minutes to load and unload the cargo on each leg
+const HANDLING_MINUTES: u32 = 30;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LogisticsError {
+    #[error("{place} at {position} is outside the map")]
+    OffMap { place: String, position: Position },
+
+    #[error("No vehicle in the fleet can carry {shipment} ({weight_kg} kg)")]
+    TooHeavy { shipment: String, weight_kg: u32 },
+
+    #[error("No chain of vehicles can deliver {shipment} from {from} to {to}")]
+    NoRoute {
+        shipment: String,
+        from: Position,
+        to: Position,
+    },
+}
+
+/// Cargo to move from one place to another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shipment {
+    pub name: String,
+    pub weight_kg: u32,
+    pub origin: Position,
+    pub destination: Position,
+}
+
+impl Shipment {
+    pub fn new(name: String, weight_kg: u32, origin: Position, destination: Position) -> Self {
+        Self {
+            name,
+            weight_kg,
+            origin,
+            destination,
+        }
+    }
+}
+
+/// A place where cargo can change vehicles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hub {
+    pub name: String,
+    pub position: Position,
+}
+
+/// A vehicle of the fleet, waiting at its depot
+#[derive(Debug)]
+pub struct FleetVehicle {
+    pub participant: Box<dyn Participant>,
+    pub depot: Position,
+}
+
+impl FleetVehicle {
+    pub fn new(participant: Box<dyn Participant>, depot: Position) -> Self {
+        Self { participant, depot }
+    }
+
+    pub fn name(&self) -> String {
+        self.participant.participant_name()
+    }
+}
+
+/// One vehicle carrying one shipment between two stops
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryLeg {
+    pub vehicle: String,
+    pub from: Position,
+    pub to: Position,
+    /// Modes used along the way, in order, without repeats
+    pub modes: Vec<TravelMode>,
+    /// Minute the vehicle starts loading
+    pub departs_at: u32,
+    /// Minute the cargo is unloaded at the next stop
+    pub arrives_at: u32,
+    /// Minutes of driving empty to the pickup point
+    pub empty_run_minutes: u32,
+    /// Energy for the empty run and the leg itself
+    pub energy_levels: u32,
+}
+
+/// How one shipment gets delivered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery {
+    pub shipment: String,
+    pub legs: Vec<DeliveryLeg>,
+}
+
+impl Delivery {
+    /// Minute the shipment reaches its destination
+    pub fn delivered_at(&self) -> u32 {
+        self.legs.last().map_or(0, |leg| leg.arrives_at)
+    }
+
+    /// Times the cargo changes vehicles
+    pub fn handovers(&self) -> usize {
+        self.legs.len().saturating_sub(1)
+    }
+
+    pub fn energy_levels(&self) -> u32 {
+        self.legs.iter().map(|leg| leg.energy_levels).sum()
+    }
+
+    /// Vehicles used, in order
+    pub fn vehicles(&self) -> Vec<&str> {
+        self.legs.iter().map(|leg| leg.vehicle.as_str()).collect()
+    }
+}
+
+/// Deliveries for every shipment, in the order they were planned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub deliveries: Vec<Delivery>,
+}
+
+impl Schedule {
+    /// Minute the last shipment arrives
+    pub fn completed_at(&self) -> u32 {
+        self.deliveries
+            .iter()
+            .map(|delivery| delivery.delivered_at())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Energy spent by the whole fleet
+    pub fn total_energy_levels(&self) -> u32 {
+        self.deliveries
+            .iter()
+            .map(|delivery| delivery.energy_levels())
+            .sum()
+    }
+
+    pub fn delivery(&self, shipment: &str) -> Option<&Delivery> {
+        self.deliveries
+            .iter()
+            .find(|delivery| delivery.shipment == shipment)
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for delivery in &self.deliveries {
+            writeln!(
+                f,
+                "{}: delivered at minute {}, {} energy levels",
+                delivery.shipment,
+                delivery.delivered_at(),
+                delivery.energy_levels()
+            )?;
+            for leg in &delivery.legs {
+                writeln!(
+                    f,
+                    "  {} -> {} by {} ({}), minute {} to {}",
+                    leg.from,
+                    leg.to,
+                    leg.vehicle,
+                    join_modes(&leg.modes),
+                    leg.departs_at,
+                    leg.arrives_at
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn join_modes(modes: &[TravelMode]) -> String {
+    modes
+        .iter()
+        .map(|mode| mode.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Where a vehicle is and what it has left while the schedule is built
+#[derive(Debug, Clone, Copy)]
+struct VehicleState {
+    position: Position,
+    free_at: u32,
+    /// Energy it may still spend, in thousandths of a level
+    energy_milli: u32,
+}
+
+/// A leg under consideration, with what it costs the vehicle
+#[derive(Debug, Clone)]
+struct Candidate {
+    vehicle: usize,
+    leg: DeliveryLeg,
+    end: Position,
+    energy_milli: u32,
+}
+
+/// Cheapest known way to get the cargo to a stop
+#[derive(Debug, Clone)]
+struct Label {
+    ready_at: u32,
+    energy_milli: u32,
+    legs: Vec<Candidate>,
+}
+
+/// Plans deliveries for a fleet
+#[derive(Debug, Clone)]
+pub struct LogisticsPlanner {
+    pub map: GridMap,
+    pub hubs: Vec<Hub>,
+    pub preference: PlanPreference,
+}
+
+impl LogisticsPlanner {
+    pub fn new(map: GridMap, preference: PlanPreference) -> Self {
+        Self {
+            map,
+            hubs: Vec::new(),
+            preference,
+        }
+    }
+
+    /// Add a place where cargo can change vehicles
+    pub fn with_hub(mut self, name: &str, position: Position) -> Self {
+        self.hubs.push(Hub {
+            name: name.to_string(),
+            position,
+        });
+        self
+    }
+
+    /// Schedule every shipment on the fleet
+    pub fn plan(
+        &self,
+        shipments: &[Shipment],
+        fleet: &[FleetVehicle],
+    ) -> Result<Schedule, LogisticsError> {
+        self.check_places(shipments, fleet)?;
+
+        // Keep the last energy level, like a fuel reserve
+        let mut states: Vec<VehicleState> = fleet
+            .iter()
+            .map(|vehicle| VehicleState {
+                position: vehicle.depot,
+                free_at: 0,
+                energy_milli: (vehicle.participant.energy() as u32).saturating_sub(1) * 1000,
+            })
+            .collect();
+
+        let mut deliveries = Vec::with_capacity(shipments.len());
+        for shipment in shipments {
+            let legs = self.route(shipment, fleet, &states)?;
+            for candidate in &legs {
+                let state = &mut states[candidate.vehicle];
+                state.position = candidate.end;
+                state.free_at = candidate.leg.arrives_at;
+                state.energy_milli -= candidate.energy_milli;
+            }
+            deliveries.push(Delivery {
+                shipment: shipment.name.clone(),
+                legs: legs.into_iter().map(|candidate| candidate.leg).collect(),
+            });
+        }
+
+        Ok(Schedule { deliveries })
+    }
+
+    fn check_places(
+        &self,
+        shipments: &[Shipment],
+        fleet: &[FleetVehicle],
+    ) -> Result<(), LogisticsError> {
+        let places = shipments
+            .iter()
+            .flat_map(|shipment| {
+                [
+                    (format!("Origin of {}", shipment.name), shipment.origin),
+                    (
+                        format!("Destination of {}", shipment.name),
+                        shipment.destination,
+                    ),
+                ]
+            })
+            .chain(
+                self.hubs
+                    .iter()
+                    .map(|hub| (format!("Hub {}", hub.name), hub.position)),
+            )
+            .chain(
+                fleet
+                    .iter()
+                    .map(|vehicle| (format!("Depot of {}", vehicle.name()), vehicle.depot)),
+            );
+        for (place, position) in places {
+            if !self.map.contains(position) {
+                return Err(LogisticsError::OffMap { place, position });
+            }
+        }
+        Ok(())
+    }
+
+    /// Cheapest chain of legs from origin to destination (Dijkstra over the stops)
+    fn route(
+        &self,
+        shipment: &Shipment,
+        fleet: &[FleetVehicle],
+        states: &[VehicleState],
+    ) -> Result<Vec<Candidate>, LogisticsError> {
+        if !fleet.iter().any(|vehicle| Self::fits(vehicle, shipment)) {
+            return Err(LogisticsError::TooHeavy {
+                shipment: shipment.name.clone(),
+                weight_kg: shipment.weight_kg,
+            });
+        }
+
+        // Already there - nothing to move
+        if shipment.origin == shipment.destination {
+            return Ok(Vec::new());
+        }
+
+        // Origin first, destination second, then every hub not already a stop
+        const DESTINATION: usize = 1;
+        let mut stops = vec![shipment.origin, shipment.destination];
+        for hub in &self.hubs {
+            if !stops.contains(&hub.position) {
+                stops.push(hub.position);
+            }
+        }
+
+        let mut labels: Vec<Option<Label>> = vec![None; stops.len()];
+        labels[0] = Some(Label {
+            ready_at: 0,
+            energy_milli: 0,
+            legs: Vec::new(),
+        });
+        let mut settled = vec![false; stops.len()];
+
+        while let Some(current) = (0..stops.len())
+            .filter(|index| !settled[*index])
+            .filter_map(|index| labels[index].as_ref().map(|label| (index, label)))
+            .min_by_key(|(_, label)| self.key(label.ready_at, label.energy_milli))
+            .map(|(index, _)| index)
+        {
+            settled[current] = true;
+            if current == DESTINATION {
+                break;
+            }
+            let label = labels[current].clone().expect("settled stops have a label");
+
+            for (next, &to) in stops.iter().enumerate() {
+                if settled[next] {
+                    continue;
+                }
+                let Some(candidate) =
+                    self.best_leg(fleet, states, shipment, &label, stops[current], to)
+                else {
+                    continue;
+                };
+                let ready_at = candidate.leg.arrives_at;
+                let energy_milli = label.energy_milli + candidate.energy_milli;
+                let better = labels[next].as_ref().is_none_or(|known| {
+                    self.key(ready_at, energy_milli) < self.key(known.ready_at, known.energy_milli)
+                });
+                if better {
+                    let mut legs = label.legs.clone();
+                    legs.push(candidate);
+                    labels[next] = Some(Label {
+                        ready_at,
+                        energy_milli,
+                        legs,
+                    });
+                }
+            }
+        }
+
+        labels[DESTINATION]
+            .take()
+            .map(|label| label.legs)
+            .ok_or_else(|| LogisticsError::NoRoute {
+                shipment: shipment.name.clone(),
+                from: shipment.origin,
+                to: shipment.destination,
+            })
+    }
+
+    /// Best vehicle for one leg between two stops, if any can make it
+    fn best_leg(
+        &self,
+        fleet: &[FleetVehicle],
+        states: &[VehicleState],
+        shipment: &Shipment,
+        label: &Label,
+        from: Position,
+        to: Position,
+    ) -> Option<Candidate> {
+        fleet
+            .iter()
+            .enumerate()
+            .filter(|(index, vehicle)| {
+                Self::fits(vehicle, shipment) && !label.legs.iter().any(|leg| leg.vehicle == *index) // One trip per shipment
+            })
+            .filter_map(|(index, vehicle)| {
+                // Earlier legs of this shipment do not move other vehicles
+                let state = states[index];
+                let participant = vehicle.participant.as_ref();
+
+                let (empty_run, leg) = self
+                    .area(participant, from)
+                    .into_iter()
+                    .filter_map(|pickup| {
+                        let empty_run = self.path(participant, state.position, &[pickup])?;
+                        let leg = self.path(participant, pickup, &self.area(participant, to))?;
+                        Some((empty_run, leg))
+                    })
+                    .min_by_key(|(empty_run, leg)| {
+                        self.key(
+                            empty_run.total_minutes + leg.total_minutes,
+                            energy_milli(empty_run) + energy_milli(leg),
+                        )
+                    })?;
+
+                let spent_milli = energy_milli(&empty_run) + energy_milli(&leg);
+                if spent_milli > state.energy_milli {
+                    return None;
+                }
+
+                let departs_at = (state.free_at + empty_run.total_minutes).max(label.ready_at);
+                let mut modes = leg.modes();
+                modes.dedup();
+                Some(Candidate {
+                    vehicle: index,
+                    end: leg.positions().last().copied().unwrap_or(leg.start),
+                    energy_milli: spent_milli,
+                    leg: DeliveryLeg {
+                        vehicle: vehicle.name(),
+                        from,
+                        to,
+                        modes,
+                        departs_at,
+                        arrives_at: departs_at + HANDLING_MINUTES + leg.total_minutes,
+                        empty_run_minutes: empty_run.total_minutes,
+                        energy_levels: spent_milli.div_ceil(1000),
+                    },
+                })
+            })
+            .min_by_key(|candidate| {
+                self.key(
+                    candidate.leg.arrives_at,
+                    label.energy_milli + candidate.energy_milli,
+                )
+            })
+    }
+
+    /// Check if the shipment fits in a vehicle's free capacity
+    fn fits(vehicle: &FleetVehicle, shipment: &Shipment) -> bool {
+        vehicle
+            .participant
+            .as_carrier()
+            .is_some_and(|carrier| carrier.can_carry(shipment.weight_kg))
+    }
+
+    /// Tiles within reach of a stop that the vehicle can stand on
+    fn area(&self, participant: &dyn Participant, stop: Position) -> Vec<Position> {
+        std::iter::once(stop)
+            .chain(self.map.neighbours(stop))
+            .filter(|position| is_passable(&self.map, participant, *position))
+            .collect()
+    }
+
+    /// Cheapest path to any of the targets - an empty path if already there
+    fn path(
+        &self,
+        participant: &dyn Participant,
+        from: Position,
+        targets: &[Position],
+    ) -> Option<GridPath> {
+        if targets.contains(&from) {
+            return Some(GridPath {
+                participant: participant.participant_name(),
+                start: from,
+                steps: Vec::new(),
+                total_minutes: 0,
+                total_energy_levels: 0,
+            });
+        }
+        let pathfinder = Pathfinder::new(self.preference);
+        targets
+            .iter()
+            .filter_map(|to| pathfinder.find_path(&self.map, participant, from, *to).ok())
+            .min_by_key(|path| self.key(path.total_minutes, energy_milli(path)))
+    }
+
+    /// Ordering key under the current preference
+    fn key(&self, minutes: u32, energy_milli: u32) -> (u32, u32) {
+        match self.preference {
+            PlanPreference::Fastest => (minutes, energy_milli),
+            PlanPreference::LeastEnergy => (energy_milli, minutes),
+        }
+    }
+}
+
+/// Energy spent along a path, in thousandths of a level
+fn energy_milli(path: &GridPath) -> u32 {
+    path.steps.iter().map(|step| step.energy_milli).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vehicles::car::EngineType;
+    use crate::vehicles::helicopter::{HelicopterEngine, HelicopterType};
+    use crate::vehicles::ship::{PropulsionType, ShipType};
+    use crate::vehicles::{Car, Helicopter, Ship};
+
+    // Two towns on either side of a strait, each with a port on the shore
+    const ROWS: [&str; 3] = ["===~~~~~~===", "===~~~~~~===", "===~~~~~~==="];
+
+    const WEST_TOWN: Position = Position { x: 0, y: 1 };
+    const EAST_TOWN: Position = Position { x: 11, y: 1 };
+
+    fn truck(name: &str) -> Box<dyn Participant> {
+        Box::new(Car::new(
+            name.to_string(),
+            "Volvo".to_string(),
+            2021,
+            EngineType::Diesel {
+                cylinders: 6,
+                displacement: 3.0,
+            },
+        ))
+    }
+
+    fn fleet(with_helicopter: bool) -> Vec<FleetVehicle> {
+        let freighter = Ship::new(
+            "Strait Runner".to_string(),
+            "Damen".to_string(),
+            2017,
+            ShipType::CargoShip,
+            5000,
+            PropulsionType::Diesel {
+                engines: 2,
+                power_each: 4000,
+            },
+        );
+        let mut fleet = vec![
+            FleetVehicle::new(truck("West Truck"), Position::new(0, 0)),
+            FleetVehicle::new(Box::new(freighter), Position::new(5, 1)),
+            FleetVehicle::new(truck("East Truck"), Position::new(11, 2)),
+        ];
+        if with_helicopter {
+            let helicopter = Helicopter::new(
+                "Skycrane".to_string(),
+                "Sikorsky".to_string(),
+                2012,
+                HelicopterType::Cargo,
+                22,
+                HelicopterEngine::Turboshaft {
+                    engines: 2,
+                    power_each: 3000,
+                },
+            );
+            fleet.push(FleetVehicle::new(Box::new(helicopter), Position::new(0, 2)));
+        }
+        fleet
+    }
+
+    fn planner(preference: PlanPreference) -> LogisticsPlanner {
+        LogisticsPlanner::new(GridMap::from_rows(&ROWS).unwrap(), preference)
+            .with_hub("West Port", Position::new(2, 1))
+            .with_hub("East Port", Position::new(9, 1))
+    }
+
+    #[test]
+    fn test_fastest_plan_flies_direct() {
+        let shipments = [Shipment::new(
+            "Parts".to_string(),
+            400,
+            WEST_TOWN,
+            EAST_TOWN,
+        )];
+        let schedule = planner(PlanPreference::Fastest)
+            .plan(&shipments, &fleet(true))
+            .unwrap();
+
+        let delivery = schedule.delivery("Parts").unwrap();
+        assert_eq!(delivery.vehicles(), ["Skycrane"]);
+        assert_eq!(delivery.handovers(), 0);
+        assert_eq!(delivery.legs[0].modes, [TravelMode::Fly]);
+    }
+
+    #[test]
+    fn test_cargo_changes_hands_at_the_ports() {
+        let shipments = [
+            Shipment::new("Parts".to_string(), 400, WEST_TOWN, EAST_TOWN),
+            Shipment::new("Tools".to_string(), 200, WEST_TOWN, EAST_TOWN),
+        ];
+        let schedule = planner(PlanPreference::LeastEnergy)
+            .plan(&shipments, &fleet(false))
+            .unwrap();
+
+        let parts = schedule.delivery("Parts").unwrap();
+        assert_eq!(
+            parts.vehicles(),
+            ["West Truck", "Strait Runner", "East Truck"]
+        );
+        assert_eq!(parts.handovers(), 2);
+        assert_eq!(parts.legs[1].modes, [TravelMode::Swim]);
+        for pair in parts.legs.windows(2) {
+            assert!(pair[1].departs_at >= pair[0].arrives_at);
+        }
+
+        // The second shipment waits for the fleet to come back
+        let tools = schedule.delivery("Tools").unwrap();
+        assert!(tools.delivered_at() > parts.delivered_at());
+        assert_eq!(schedule.completed_at(), tools.delivered_at());
+    }
+
+    #[test]
+    fn test_shipment_already_at_its_destination() {
+        let shipments = [Shipment::new(
+            "Spares".to_string(),
+            100,
+            WEST_TOWN,
+            WEST_TOWN,
+        )];
+        for planner in [
+            planner(PlanPreference::Fastest),
+            LogisticsPlanner::new(GridMap::from_rows(&ROWS).unwrap(), PlanPreference::Fastest),
+        ] {
+            let schedule = planner.plan(&shipments, &fleet(false)).unwrap();
+            let spares = schedule.delivery("Spares").unwrap();
+            assert!(spares.legs.is_empty());
+            assert_eq!(spares.delivered_at(), 0);
+        }
+
+        // A hub on the origin does not stand in for the destination
+        let schedule = planner(PlanPreference::Fastest)
+            .with_hub("Depot", WEST_TOWN)
+            .plan(
+                &[Shipment::new(
+                    "Parts".to_string(),
+                    400,
+                    WEST_TOWN,
+                    EAST_TOWN,
+                )],
+                &fleet(true),
+            )
+            .unwrap();
+        let parts = schedule.delivery("Parts").unwrap();
+        assert_eq!(parts.legs.last().unwrap().to, EAST_TOWN);
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let planner = planner(PlanPreference::Fastest);
+
+        let heavy = [Shipment::new(
+            "Turbine".to_string(),
+            50_000_000,
+            WEST_TOWN,
+            EAST_TOWN,
+        )];
+        assert!(matches!(
+            planner.plan(&heavy, &fleet(true)),
+            Err(LogisticsError::TooHeavy { .. })
+        ));
+
+        let off_map = [Shipment::new(
+            "Lost".to_string(),
+            10,
+            WEST_TOWN,
+            Position::new(20, 1),
+        )];
+        assert!(matches!(
+            planner.plan(&off_map, &fleet(true)),
+            Err(LogisticsError::OffMap { .. })
+        ));
+
+        // Without the east truck nothing leaves the quay
+        let mut fleet = fleet(false);
+        fleet.pop();
+        let parts = [Shipment::new(
+            "Parts".to_string(),
+            400,
+            WEST_TOWN,
+            EAST_TOWN,
+        )];
+        assert!(matches!(
+            planner.plan(&parts, &fleet),
+            Err(LogisticsError::NoRoute { .. })
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod rescue;
+
+pub use rescue::{
+    Assignment, RescueError, RescueMission, RescueOutcome, RescueReport, RescueUnit, UnitReport,
+};
@@ -0,0 +1,507 @@
+//! Search and Rescue
+//!
+//! A [`RescueMission`] sends units out from their bases to a victim on a
+//! [`GridMap`]. Every unit that can reach the victim's tile without
+//! collapsing is dispatched; the others stay at base. The mission weather
+//! lies over every tile without weather of its own, so a thunderstorm
+//! grounds helicopters just as it does for the [`Pathfinder`].
+//!
+//! The victim's last known position is only good to the tile, so arriving
+//! units sweep the whole tile. A unit sees a strip twice its detection
+//! range wide - scent, vision, sonar or radar, whatever reaches furthest
+//! in the weather - and units without sensors rely on plain eyesight. The
+//! victim is found when the first unit finishes its sweep.
+//!
+//! Finding the victim is not enough: a [`Carrier`] with room for a person
+//! has to carry them back to its base. The first carrier able to do so
+//! gets the job, and the victim is rescued if it arrives before the
+//! deadline.
+//!
+//! [`Carrier`]: crate::vehicles::Carrier
+
+use crate::behaviors::{Participant, Sensor, TravelMode};
+use crate::core::Weather;
+use crate::vehicles::payload::PASSENGER_WEIGHT_KG;
+use crate::world::{GridMap, GridPath, PathError, Pathfinder, Position, Tile};
+use std::fmt;
+use thiserror::Error;
+
+/// Minutes it takes to get the victim on board once both are at the scene
+const BOARDING_MINUTES: u32 = 15;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RescueError {
+    #[error("The victim at {0} is outside the map")]
+    VictimOffMap(Position),
+
+    #[error("{unit}'s base at {base} is outside the map")]
+    BaseOffMap { unit: String, base: Position },
+
+    #[error("No units to dispatch")]
+    NoUnits,
+}
+
+/// An animal or vehicle standing by at its base
+#[derive(Debug)]
+pub struct RescueUnit {
+    pub participant: Box<dyn Participant>,
+    pub base: Position,
+}
+
+impl RescueUnit {
+    pub fn new(participant: Box<dyn Participant>, base: Position) -> Self {
+        Self { participant, base }
+    }
+
+    pub fn name(&self) -> String {
+        self.participant.participant_name()
+    }
+}
+
+/// What a unit did during the mission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Assignment {
+    /// Could not reach the victim
+    StayedAtBase { reason: String },
+    /// Went out and swept the victim's tile
+    Search,
+    /// Searched, then carried the victim back to base
+    Extraction,
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Assignment::StayedAtBase { reason } => write!(f, "stayed at base ({})", reason),
+            Assignment::Search => write!(f, "search"),
+            Assignment::Extraction => write!(f, "extraction"),
+        }
+    }
+}
+
+/// Time and energy one unit spent on the mission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitReport {
+    pub unit: String,
+    pub assignment: Assignment,
+    /// Minutes from dispatch until the unit was done
+    pub minutes: u32,
+    pub energy_levels: u32,
+}
+
+/// How the mission ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RescueOutcome {
+    /// Carried back to base before the deadline
+    Rescued { minutes: u32 },
+    /// Carried back to base, but after the deadline
+    TooLate { minutes: u32 },
+    /// Found, but nobody could carry the victim out
+    Stranded { found_at: u32 },
+    /// No unit could reach the victim
+    NotFound,
+}
+
+/// Everything that happened on a mission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RescueReport {
+    pub outcome: RescueOutcome,
+    /// Minutes until the victim was found, and by whom
+    pub found: Option<(u32, String)>,
+    pub units: Vec<UnitReport>,
+}
+
+impl RescueReport {
+    pub fn succeeded(&self) -> bool {
+        matches!(self.outcome, RescueOutcome::Rescued { .. })
+    }
+
+    /// Energy spent by all units together
+    pub fn total_energy_levels(&self) -> u32 {
+        self.units.iter().map(|unit| unit.energy_levels).sum()
+    }
+
+    /// Units that left their base
+    pub fn dispatched(&self) -> Vec<&str> {
+        self.units
+            .iter()
+            .filter(|unit| !matches!(unit.assignment, Assignment::StayedAtBase { .. }))
+            .map(|unit| unit.unit.as_str())
+            .collect()
+    }
+
+    /// Unit that carried the victim out, if any
+    pub fn extracted_by(&self) -> Option<&str> {
+        self.units
+            .iter()
+            .find(|unit| unit.assignment == Assignment::Extraction)
+            .map(|unit| unit.unit.as_str())
+    }
+}
+
+impl fmt::Display for RescueReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.outcome {
+            RescueOutcome::Rescued { minutes } => writeln!(f, "Rescued after {} min", minutes)?,
+            RescueOutcome::TooLate { minutes } => {
+                writeln!(f, "Rescued too late, after {} min", minutes)?
+            }
+            RescueOutcome::Stranded { found_at } => {
+                writeln!(f, "Found after {} min, but stranded", found_at)?
+            }
+            RescueOutcome::NotFound => writeln!(f, "Victim not found")?,
+        }
+        for unit in &self.units {
+            writeln!(
+                f,
+                "  {}: {}, {} min, {} energy levels",
+                unit.unit, unit.assignment, unit.minutes, unit.energy_levels
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A unit on its way to the victim
+struct Sortie {
+    index: usize,
+    /// Minutes until the unit reaches the victim's tile
+    arrived_at: u32,
+    /// Minutes until the victim's tile has been swept
+    searched_at: u32,
+    /// Energy spent getting there and searching, in thousandths of a level
+    energy_milli: u32,
+}
+
+/// A victim somewhere on a map, and the time they have left
+#[derive(Debug, Clone)]
+pub struct RescueMission {
+    pub map: GridMap,
+    pub victim: Position,
+    /// Minutes the victim can hold out
+    pub deadline_minutes: u32,
+    pub weather: Weather,
+    pub pathfinder: Pathfinder,
+}
+
+impl RescueMission {
+    pub fn new(map: GridMap, victim: Position, deadline_minutes: u32) -> Self {
+        Self {
+            map,
+            victim,
+            deadline_minutes,
+            weather: Weather::Clear,
+            pathfinder: Pathfinder::default(),
+        }
+    }
+
+    /// Weather over every tile without weather of its own
+    pub fn in_weather(mut self, weather: Weather) -> Self {
+        self.weather = weather;
+        self
+    }
+
+    /// Dispatch the units, search for the victim and carry them out, spending the units' energy
+    pub fn run(&self, units: &mut [RescueUnit]) -> Result<RescueReport, RescueError> {
+        if units.is_empty() {
+            return Err(RescueError::NoUnits);
+        }
+        let map = self.weathered_map();
+        if !map.contains(self.victim) {
+            return Err(RescueError::VictimOffMap(self.victim));
+        }
+        if let Some(unit) = units.iter().find(|unit| !map.contains(unit.base)) {
+            return Err(RescueError::BaseOffMap {
+                unit: unit.name(),
+                base: unit.base,
+            });
+        }
+
+        let mut reports: Vec<UnitReport> = units
+            .iter()
+            .map(|unit| UnitReport {
+                unit: unit.name(),
+                assignment: Assignment::Search,
+                minutes: 0,
+                energy_levels: 0,
+            })
+            .collect();
+
+        let mut sorties = Vec::new();
+        for (index, unit) in units.iter().enumerate() {
+            match self.sortie(&map, index, unit) {
+                Ok(sortie) => sorties.push(sortie),
+                Err(reason) => reports[index].assignment = Assignment::StayedAtBase { reason },
+            }
+        }
+        for sortie in &sorties {
+            let report = &mut reports[sortie.index];
+            report.minutes = sortie.searched_at;
+            report.energy_levels = sortie.energy_milli.div_ceil(1000);
+        }
+
+        let found = sorties
+            .iter()
+            .min_by_key(|sortie| sortie.searched_at)
+            .map(|sortie| (sortie.searched_at, units[sortie.index].name()));
+        let outcome = match &found {
+            None => RescueOutcome::NotFound,
+            Some((found_at, _)) => match self.extraction(&map, units, &sorties, *found_at) {
+                None => RescueOutcome::Stranded {
+                    found_at: *found_at,
+                },
+                Some((index, minutes, energy_milli)) => {
+                    let report = &mut reports[index];
+                    report.assignment = Assignment::Extraction;
+                    report.minutes = minutes;
+                    report.energy_levels = energy_milli.div_ceil(1000);
+                    if minutes <= self.deadline_minutes {
+                        RescueOutcome::Rescued { minutes }
+                    } else {
+                        RescueOutcome::TooLate { minutes }
+                    }
+                }
+            },
+        };
+
+        for (unit, report) in units.iter_mut().zip(&reports) {
+            unit.participant
+                .consume_energy_levels(report.energy_levels.min(u8::MAX as u32) as u8);
+        }
+
+        Ok(RescueReport {
+            outcome,
+            found,
+            units: reports,
+        })
+    }
+
+    /// The map with the mission weather filled in
+    fn weathered_map(&self) -> GridMap {
+        let mut map = self.map.clone();
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                let position = Position::new(x, y);
+                if map.weather(position).is_none() {
+                    map.set_weather_region(position, position, self.weather);
+                }
+            }
+        }
+        map
+    }
+
+    /// Send a unit to the victim and sweep the tile - or say why it cannot go
+    fn sortie(&self, map: &GridMap, index: usize, unit: &RescueUnit) -> Result<Sortie, String> {
+        let participant = unit.participant.as_ref();
+        let outbound = self
+            .pathfinder
+            .find_path(map, participant, unit.base, self.victim)
+            .map_err(|error| match error {
+                PathError::NoPath { .. } => "no way to reach the victim".to_string(),
+                error => error.to_string(),
+            })?;
+
+        let step = self
+            .pathfinder
+            .step(map, participant, self.victim)
+            .ok_or_else(|| "cannot move about the victim's tile".to_string())?;
+
+        // Sweep the tile in strips as wide as the unit can see
+        // In u64: a large tile squared does not fit in u32
+        let tile_m = map.tile_km() as u64 * 1000;
+        let weather = map.weather(self.victim).unwrap_or(self.weather);
+        let strip_m = 2 * self.detection_range_m(participant, weather) as u64;
+        if strip_m == 0 {
+            return Err(format!("cannot detect anyone in {} weather", weather));
+        }
+        let track_m = (tile_m * tile_m / strip_m).max(tile_m);
+        let search_minutes = (track_m * step.seconds as u64).div_ceil(tile_m * 60);
+        let search_milli = (track_m * step.energy_milli as u64).div_ceil(tile_m);
+
+        let energy_milli = path_energy_milli(&outbound) as u64 + search_milli;
+        if energy_milli >= Self::reserve_milli(participant) as u64 {
+            return Err("would collapse before finishing the search".to_string());
+        }
+        let search_minutes = u32::try_from(search_minutes)
+            .map_err(|_| "could not finish the search in any reasonable time".to_string())?;
+
+        Ok(Sortie {
+            index,
+            arrived_at: outbound.total_minutes,
+            searched_at: outbound.total_minutes.saturating_add(search_minutes),
+            energy_milli: energy_milli as u32, // Below the reserve, so it fits
+        })
+    }
+
+    /// Earliest carrier that can take the victim home: its index, finish time and total energy
+    fn extraction(
+        &self,
+        map: &GridMap,
+        units: &[RescueUnit],
+        sorties: &[Sortie],
+        found_at: u32,
+    ) -> Option<(usize, u32, u32)> {
+        sorties
+            .iter()
+            .filter_map(|sortie| {
+                let unit = &units[sortie.index];
+                let participant = unit.participant.as_ref();
+                let carrier = participant.as_carrier()?;
+                if !carrier.can_carry(PASSENGER_WEIGHT_KG) {
+                    return None;
+                }
+
+                let homebound = self
+                    .pathfinder
+                    .find_path(map, participant, self.victim, unit.base)
+                    .ok()?;
+                let energy_milli = sortie.energy_milli + path_energy_milli(&homebound);
+                if energy_milli >= Self::reserve_milli(participant) {
+                    return None; // Would not make it back
+                }
+
+                let on_scene = sortie.arrived_at.max(found_at);
+                let minutes = on_scene + BOARDING_MINUTES + homebound.total_minutes;
+                Some((sortie.index, minutes, energy_milli))
+            })
+            .min_by_key(|(_, minutes, _)| *minutes)
+    }
+
+    /// Energy a unit can spend before it collapses, in thousandths of a level
+    fn reserve_milli(participant: &dyn Participant) -> u32 {
+        participant.energy() as u32 * 1000
+    }
+
+    /// How far the unit can make out the victim - plain eyesight if its sensors cannot
+    fn detection_range_m(&self, participant: &dyn Participant, weather: Weather) -> u32 {
+        let target = match self.map.tile(self.victim) {
+            Some(Tile::Water { .. }) => TravelMode::Swim,
+            _ => TravelMode::Walk,
+        };
+        participant
+            .as_sensor()
+            .map(|sensing| sensing.detection_range_m(weather, target))
+            .filter(|range_m| *range_m > 0)
+            .unwrap_or_else(|| Sensor::unaided().effective_range_m(weather))
+    }
+}
+
+/// Energy spent along a path, before rounding up to whole levels
+fn path_energy_milli(path: &GridPath) -> u32 {
+    path.steps.iter().map(|step| step.energy_milli).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::Dog;
+    use crate::animals::dog::DogBreed;
+    use crate::core::EnergyLevel;
+    use crate::vehicles::car::EngineType;
+    use crate::vehicles::helicopter::{HelicopterEngine, HelicopterType};
+    use crate::vehicles::{Car, Helicopter};
+
+    // A town by a lake in the west, mountains to the east
+    const ROWS: [&str; 5] = [
+        "=====*****",
+        "=...T**^^*",
+        "=...T**^^*",
+        "=~~~T*****",
+        "=~~~==****",
+    ];
+
+    const VICTIM: Position = Position { x: 8, y: 2 };
+
+    fn units() -> Vec<RescueUnit> {
+        let helicopter = Helicopter::new(
+            "Rescue 1".to_string(),
+            "Airbus".to_string(),
+            2019,
+            HelicopterType::Emergency,
+            11,
+            HelicopterEngine::Turboshaft {
+                engines: 2,
+                power_each: 600,
+            },
+        );
+        let car = Car::new(
+            "Patrol".to_string(),
+            "Volvo".to_string(),
+            2021,
+            EngineType::Diesel {
+                cylinders: 4,
+                displacement: 2.0,
+            },
+        );
+        let dog = Dog::new("Barry".to_string(), DogBreed::SaintBernard);
+        vec![
+            RescueUnit::new(Box::new(helicopter), Position::new(0, 0)),
+            RescueUnit::new(Box::new(car), Position::new(0, 4)),
+            RescueUnit::new(Box::new(dog), Position::new(6, 1)),
+        ]
+    }
+
+    fn mission(deadline_minutes: u32) -> RescueMission {
+        RescueMission::new(GridMap::from_rows(&ROWS).unwrap(), VICTIM, deadline_minutes)
+    }
+
+    #[test]
+    fn test_helicopter_flies_the_victim_out() {
+        let mut units = units();
+        let report = mission(120).run(&mut units).unwrap();
+
+        assert!(report.succeeded(), "{}", report);
+        assert_eq!(report.extracted_by(), Some("Rescue 1"));
+        assert_eq!(report.dispatched(), vec!["Rescue 1", "Barry"]);
+        assert!(matches!(
+            report.units[1].assignment,
+            Assignment::StayedAtBase { .. } // No road into the mountains
+        ));
+        assert!(report.total_energy_levels() > 0);
+
+        let RescueOutcome::Rescued { minutes } = report.outcome else {
+            unreachable!()
+        };
+        let report = mission(minutes - 1).run(&mut self::units()).unwrap();
+        assert_eq!(report.outcome, RescueOutcome::TooLate { minutes });
+    }
+
+    #[test]
+    fn test_storm_grounds_the_helicopter() {
+        let mut units = units();
+        let report = mission(240)
+            .in_weather(Weather::Thunderstorm)
+            .run(&mut units)
+            .unwrap();
+
+        // Barry still finds the victim, but cannot carry them out
+        assert_eq!(report.dispatched(), vec!["Barry"]);
+        assert_eq!(
+            report.found.as_ref().map(|(_, unit)| unit.as_str()),
+            Some("Barry")
+        );
+        assert!(matches!(report.outcome, RescueOutcome::Stranded { .. }));
+        assert!(units[2].participant.energy() < EnergyLevel::Energetic); // Barry's walk
+    }
+
+    #[test]
+    fn test_wide_tiles_do_not_overflow_the_search() {
+        let map = GridMap::from_rows(&ROWS).unwrap().with_tile_km(100);
+        let report = RescueMission::new(map, VICTIM, 60)
+            .run(&mut units())
+            .unwrap();
+        // Nobody can cover 100 km tiles within an hour
+        assert!(!report.succeeded());
+    }
+
+    #[test]
+    fn test_mission_errors() {
+        assert_eq!(mission(60).run(&mut []).unwrap_err(), RescueError::NoUnits);
+        let mut mission = mission(60);
+        mission.victim = Position::new(10, 0);
+        assert_eq!(
+            mission.run(&mut units()).unwrap_err(),
+            RescueError::VictimOffMap(Position::new(10, 0))
+        );
+    }
+}
@@ -0,0 +1,375 @@
+//! Constructors for the animals and vehicles of this crate
+//!
+//! Every kind takes a `name`. Breeds, species and vehicle types are picked
+//! by name, ignoring case and spaces; everything else has a default.
+//!
+//! | Kind                       | Parameters and defaults                           |
+//! |----------------------------|---------------------------------------------------|
+//! | `dog`                      | `breed` or a mix like "50% Labrador, 50% Husky" (Other) |
+//! | `whale`, `snake`           | `species` (Other)                                 |
+//! | `frog`                     | `stage`, adult or tadpole (adult)                 |
+//! | `duck`, `eagle`, `penguin` |                                                   |
+//! | `car`                      | `engine` (gasoline), `cylinders` (4)              |
+//! | `motorcycle`               | `type` (Sport), `engine_size` (600)               |
+//! | `airplane`                 | `type` (Commercial), `wingspan` (30)              |
+//! | `ship`                     | `type` (CruiseShip), `displacement` (1000)        |
+//! | `helicopter`               | `type` (Civilian), `rotor_diameter` (15)          |
+//! | `amphibious`               | `type` (AmphibiousCar), `mode` (land), `state` (running) |
+//! | `sled`                     | `dogs` (6), `breed` of the team (Husky)           |
+//!
+//! Vehicles also take a `manufacturer` (Generic) and `year` (2023). An
+//! amphibious vehicle is created `running` or `parked`; a parked one can only
+//! be used as a passive participant until it is downcast and started.
+
+use crate::animals::{
+    AnyFrog, BreedMix, CatalogError, Dog, Duck, Eagle, Frog, Penguin, Snake, SpeciesCatalog,
+    SpeciesKind, SpeciesProfile, Whale,
+};
+use crate::registry::{Entity, EntityRegistry, Params, RegistryError};
+use crate::vehicles::airplane::{AirplaneEngine, AirplaneType};
+use crate::vehicles::amphibious::{AmphibiousMode, AmphibiousPropulsion, AmphibiousType, HullType};
+use crate::vehicles::car::EngineType;
+use crate::vehicles::helicopter::{HelicopterEngine, HelicopterType};
+use crate::vehicles::motorcycle::MotorcycleType;
+use crate::vehicles::ship::{PropulsionType, ShipType};
+use crate::vehicles::{
+    Airplane, AmphibiousVehicle, Car, Helicopter, Lifecycle, Motorcycle, Ship, Sled,
+};
+use std::sync::Arc;
+
+const DEFAULT_MANUFACTURER: &str = "Generic";
+const DEFAULT_YEAR: u32 = 2023;
+
+/// Result of a constructor
+type Created = Result<Box<dyn Entity>, RegistryError>;
+
+pub(crate) fn register_all(registry: &mut EntityRegistry, catalog: SpeciesCatalog) {
+    let catalog = Arc::new(catalog);
+    let with_catalog = |constructor: fn(&Params, &SpeciesCatalog) -> Created| {
+        let catalog = Arc::clone(&catalog);
+        move |params: &Params| constructor(params, &catalog)
+    };
+
+    register(registry, "dog", with_catalog(dog));
+    register(registry, "whale", with_catalog(whale));
+    register(registry, "snake", with_catalog(snake));
+    register(registry, "frog", frog);
+    register(registry, "duck", duck);
+    register(registry, "eagle", eagle);
+    register(registry, "penguin", penguin);
+    register(registry, "car", car);
+    register(registry, "motorcycle", motorcycle);
+    register(registry, "airplane", airplane);
+    register(registry, "ship", ship);
+    register(registry, "helicopter", helicopter);
+    register(registry, "amphibious", amphibious);
+    register(registry, "sled", with_catalog(sled));
+}
+
+fn register(
+    registry: &mut EntityRegistry,
+    kind: &str,
+    constructor: impl Fn(&Params) -> Created + Send + Sync + 'static,
+) {
+    registry
+        .register(kind, constructor)
+        .expect("builtin kinds are registered once");
+}
+
+fn manufacturer(params: &Params) -> String {
+    params
+        .get("manufacturer")
+        .unwrap_or(DEFAULT_MANUFACTURER)
+        .to_string()
+}
+
+fn year(params: &Params) -> Result<u32, RegistryError> {
+    params.parse_or("year", DEFAULT_YEAR)
+}
+
+fn dog(params: &Params, catalog: &SpeciesCatalog) -> Created {
+    let name = params.name()?;
+    Ok(match params.get("breed") {
+        Some(ancestry) if ancestry.contains('%') => {
+            Box::new(Dog::mixed(name, BreedMix::parse(ancestry, catalog)?))
+        }
+        breed => Box::new(Dog::with_profile(
+            name,
+            profile(catalog, SpeciesKind::Dog, breed)?,
+        )),
+    })
+}
+
+fn whale(params: &Params, catalog: &SpeciesCatalog) -> Created {
+    let name = params.name()?;
+    Ok(Box::new(Whale::with_profile(
+        name,
+        profile(catalog, SpeciesKind::Whale, params.get("species"))?,
+    )))
+}
+
+fn snake(params: &Params, catalog: &SpeciesCatalog) -> Created {
+    let name = params.name()?;
+    Ok(Box::new(Snake::with_profile(
+        name,
+        profile(catalog, SpeciesKind::Snake, params.get("species"))?,
+    )))
+}
+
+fn frog(params: &Params) -> Created {
+    let name = params.name()?;
+    let tadpole = params.choose("stage", &[("adult", false), ("tadpole", true)], false)?;
+    Ok(Box::new(if tadpole {
+        AnyFrog::hatch(name)
+    } else {
+        AnyFrog::Adult(Frog::new(name))
+    }))
+}
+
+fn duck(params: &Params) -> Created {
+    Ok(Box::new(Duck::new(params.name()?)))
+}
+
+fn eagle(params: &Params) -> Created {
+    Ok(Box::new(Eagle::new(params.name()?)))
+}
+
+fn penguin(params: &Params) -> Created {
+    Ok(Box::new(Penguin::new(params.name()?)))
+}
+
+fn car(params: &Params) -> Created {
+    let name = params.name()?;
+    let cylinders = params.parse_or("cylinders", 4)?;
+    let gasoline = EngineType::Gasoline {
+        cylinders,
+        displacement: 2.0,
+    };
+    let engine = params.choose(
+        "engine",
+        &[
+            ("gasoline", gasoline.clone()),
+            (
+                "diesel",
+                EngineType::Diesel {
+                    cylinders,
+                    displacement: 2.0,
+                },
+            ),
+            (
+                "electric",
+                EngineType::Electric {
+                    battery_capacity: 75,
+                },
+            ),
+            (
+                "hybrid",
+                EngineType::Hybrid {
+                    gas_engine: Box::new(gasoline.clone()),
+                    electric_motor: 50,
+                },
+            ),
+        ],
+        gasoline,
+    )?;
+    Ok(Box::new(Car::new(
+        name,
+        manufacturer(params),
+        year(params)?,
+        engine,
+    )))
+}
+
+fn motorcycle(params: &Params) -> Created {
+    let name = params.name()?;
+    let moto_type = params.choose(
+        "type",
+        &[
+            ("Sport", MotorcycleType::Sport),
+            ("Cruiser", MotorcycleType::Cruiser),
+            ("Touring", MotorcycleType::Touring),
+            ("Dirt", MotorcycleType::Dirt),
+            ("Electric", MotorcycleType::Electric),
+        ],
+        MotorcycleType::Sport,
+    )?;
+    Ok(Box::new(Motorcycle::new(
+        name,
+        manufacturer(params),
+        year(params)?,
+        params.parse_or("engine_size", 600)?,
+        moto_type,
+    )))
+}
+
+fn airplane(params: &Params) -> Created {
+    let name = params.name()?;
+    let airplane_type = params.choose(
+        "type",
+        &[
+            ("Commercial", AirplaneType::Commercial),
+            ("Military", AirplaneType::Military),
+            ("Private", AirplaneType::Private),
+            ("Cargo", AirplaneType::Cargo),
+            ("Aerobatic", AirplaneType::Aerobatic),
+            ("Seaplane", AirplaneType::Seaplane),
+        ],
+        AirplaneType::Commercial,
+    )?;
+    Ok(Box::new(Airplane::new(
+        name,
+        manufacturer(params),
+        year(params)?,
+        airplane_type,
+        params.parse_or("wingspan", 30)?,
+        AirplaneEngine::Jet {
+            engines: 2,
+            thrust_each: 200,
+        },
+    )))
+}
+
+fn ship(params: &Params) -> Created {
+    let name = params.name()?;
+    let ship_type = params.choose(
+        "type",
+        &[
+            ("CargoShip", ShipType::CargoShip),
+            ("CruiseShip", ShipType::CruiseShip),
+            ("Warship", ShipType::Warship),
+            ("Yacht", ShipType::Yacht),
+            ("Ferry", ShipType::Ferry),
+            ("Submarine", ShipType::Submarine),
+            ("Speedboat", ShipType::Speedboat),
+        ],
+        ShipType::CruiseShip,
+    )?;
+    Ok(Box::new(Ship::new(
+        name,
+        manufacturer(params),
+        year(params)?,
+        ship_type,
+        params.parse_or("displacement", 1000)?,
+        PropulsionType::Diesel {
+            engines: 2,
+            power_each: 500,
+        },
+    )))
+}
+
+fn helicopter(params: &Params) -> Created {
+    let name = params.name()?;
+    let helicopter_type = params.choose(
+        "type",
+        &[
+            ("Emergency", HelicopterType::Emergency),
+            ("Military", HelicopterType::Military),
+            ("Civilian", HelicopterType::Civilian),
+            ("Cargo", HelicopterType::Cargo),
+            ("Police", HelicopterType::Police),
+            ("News", HelicopterType::News),
+        ],
+        HelicopterType::Civilian,
+    )?;
+    Ok(Box::new(Helicopter::new(
+        name,
+        manufacturer(params),
+        year(params)?,
+        helicopter_type,
+        params.parse_or("rotor_diameter", 15)?,
+        HelicopterEngine::Turboshaft {
+            engines: 2,
+            power_each: 1000,
+        },
+    )))
+}
+
+fn amphibious(params: &Params) -> Created {
+    let name = params.name()?;
+    let amphibious_type = params.choose(
+        "type",
+        &[
+            ("Duck", AmphibiousType::Duck),
+            ("Hovercraft", AmphibiousType::Hovercraft),
+            ("AmphibiousCar", AmphibiousType::AmphibiousCar),
+            ("LandingCraft", AmphibiousType::LandingCraft),
+            ("AmphibiousRV", AmphibiousType::AmphibiousRV),
+            ("EmergencyVehicle", AmphibiousType::EmergencyVehicle),
+        ],
+        AmphibiousType::AmphibiousCar,
+    )?;
+    let mode = params.choose(
+        "mode",
+        &[
+            ("land", AmphibiousMode::Land),
+            ("water", AmphibiousMode::Water),
+        ],
+        AmphibiousMode::Land,
+    )?;
+    let parked = params.choose("state", &[("running", false), ("parked", true)], false)?;
+
+    let (propulsion, hull_type) = match amphibious_type {
+        AmphibiousType::Hovercraft => (
+            AmphibiousPropulsion::HovercraftFan {
+                fan_power: 400,
+                cushion_pressure: 50,
+            },
+            HullType::AirCushion,
+        ),
+        _ => (
+            AmphibiousPropulsion::WheelsAndPropeller {
+                wheels: 4,
+                propeller_power: 100,
+            },
+            HullType::Sealed,
+        ),
+    };
+    let vehicle = AmphibiousVehicle::new(
+        name,
+        manufacturer(params),
+        year(params)?,
+        amphibious_type,
+        propulsion,
+        hull_type,
+    )
+    .in_mode(mode);
+
+    Ok(if parked {
+        Box::new(vehicle.shut_down())
+    } else {
+        Box::new(vehicle)
+    })
+}
+
+fn sled(params: &Params, catalog: &SpeciesCatalog) -> Created {
+    let name = params.name()?;
+    let breed = profile(
+        catalog,
+        SpeciesKind::Dog,
+        params.get("breed").or(Some("Husky")),
+    )?;
+    let dogs: u32 = params.parse_or("dogs", 6)?;
+    let sled = Sled::new(name.clone(), manufacturer(params), year(params)?);
+    Ok(Box::new((1..=dogs).fold(sled, |sled, number| {
+        sled.harness(Dog::with_profile(
+            format!("{} {}", name, number),
+            breed.clone(),
+        ))
+    })))
+}
+
+/// Profile of a breed or species, defaulting to "Other"
+fn profile(
+    catalog: &SpeciesCatalog,
+    kind: SpeciesKind,
+    requested: Option<&str>,
+) -> Result<SpeciesProfile, RegistryError> {
+    let requested = requested.unwrap_or("Other");
+    catalog.find(kind, requested).cloned().ok_or_else(|| {
+        CatalogError::UnknownSpecies {
+            kind,
+            name: requested.to_string(),
+        }
+        .into()
+    })
+}
@@ -0,0 +1,100 @@
+use crate::behaviors::{Participant, TravelMode};
+use std::any::Any;
+
+/// Anything the registry can create: a participant that can be downcast
+/// back to its concrete type
+pub trait Entity: Participant + Any {}
+
+impl<T: Participant + Any> Entity for T {}
+
+/// What an entity is able to do, read from its participant accessors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    pub walk: bool,
+    pub swim: bool,
+    pub fly: bool,
+    pub drive: bool,
+    pub jump: bool,
+}
+
+impl Capabilities {
+    pub fn of(participant: &dyn Participant) -> Self {
+        Self {
+            walk: participant.as_walker().is_some(),
+            swim: participant.as_swimmer().is_some(),
+            fly: participant.as_flyer().is_some(),
+            drive: participant.as_driver().is_some(),
+            jump: participant.as_jumper().is_some(),
+        }
+    }
+
+    /// Walking and driving both move over land
+    pub fn land_move(&self) -> bool {
+        self.walk || self.drive
+    }
+
+    pub fn travel_modes(&self) -> Vec<TravelMode> {
+        TravelMode::all_modes()
+            .into_iter()
+            .filter(|mode| match mode {
+                TravelMode::Walk => self.walk,
+                TravelMode::Drive => self.drive,
+                TravelMode::Swim => self.swim,
+                TravelMode::Fly => self.fly,
+            })
+            .collect()
+    }
+}
+
+/// An entity created by the registry, along with the kind it was created as
+#[derive(Debug)]
+pub struct Spawned {
+    pub kind: String,
+    entity: Box<dyn Entity>,
+}
+
+impl Spawned {
+    pub fn new(kind: impl Into<String>, entity: Box<dyn Entity>) -> Self {
+        Self {
+            kind: kind.into(),
+            entity,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.entity.participant_name()
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::of(self.participant())
+    }
+
+    pub fn participant(&self) -> &dyn Participant {
+        self.entity.as_ref()
+    }
+
+    pub fn participant_mut(&mut self) -> &mut dyn Participant {
+        self.entity.as_mut()
+    }
+
+    pub fn into_entity(self) -> Box<dyn Entity> {
+        self.entity
+    }
+
+    pub fn downcast_ref<T: Entity>(&self) -> Option<&T> {
+        (self.entity.as_ref() as &dyn Any).downcast_ref()
+    }
+
+    pub fn downcast_mut<T: Entity>(&mut self) -> Option<&mut T> {
+        (self.entity.as_mut() as &mut dyn Any).downcast_mut()
+    }
+
+    /// Take the concrete entity out, or get the spawned entity back if it is another type
+    pub fn downcast<T: Entity>(self) -> Result<T, Self> {
+        if !(self.entity.as_ref() as &dyn Any).is::<T>() {
+            return Err(self);
+        }
+        let any: Box<dyn Any> = self.entity;
+        Ok(*any.downcast().expect("type was checked"))
+    }
+}
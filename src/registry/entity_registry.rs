@@ -0,0 +1,286 @@
+use crate::animals::{CatalogError, MixError, SpeciesCatalog};
+use crate::core::EnergyLevel;
+use crate::registry::{Entity, Params, Spawned, builtins};
+use std::collections::BTreeMap;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    #[error("Unknown entity kind '{0}'")]
+    UnknownKind(String),
+
+    #[error("Entity kind '{0}' is already registered")]
+    DuplicateKind(String),
+
+    #[error("Missing parameter '{0}'")]
+    MissingParam(String),
+
+    #[error("Invalid value '{value}' for parameter '{param}'")]
+    InvalidParam { param: String, value: String },
+
+    #[error(transparent)]
+    Catalog(#[from] CatalogError),
+
+    #[error(transparent)]
+    Mix(#[from] MixError),
+}
+
+/// Builds an entity of one kind from its parameters
+pub type Constructor = Box<dyn Fn(&Params) -> Result<Box<dyn Entity>, RegistryError> + Send + Sync>;
+
+/// Creates entities by kind name at runtime
+///
+/// Every kind registers a constructor under a name such as `"dog"` or
+/// `"airplane"`. Other crates add their own kinds with [`register`], as
+/// long as the type implements `Participant`.
+///
+/// [`register`]: EntityRegistry::register
+pub struct EntityRegistry {
+    constructors: BTreeMap<String, Constructor>,
+}
+
+impl Default for EntityRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+impl fmt::Debug for EntityRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EntityRegistry")
+            .field("kinds", &self.kinds())
+            .finish()
+    }
+}
+
+impl EntityRegistry {
+    /// Registry without any kinds
+    pub fn new() -> Self {
+        Self {
+            constructors: BTreeMap::new(),
+        }
+    }
+
+    /// Registry of the animals and vehicles of this crate, using the compiled-in species
+    pub fn with_builtins() -> Self {
+        Self::with_catalog(SpeciesCatalog::builtin())
+    }
+
+    /// Registry of the animals and vehicles of this crate, taking dog breeds
+    /// and whale and snake species from a catalog
+    pub fn with_catalog(catalog: SpeciesCatalog) -> Self {
+        let mut registry = Self::new();
+        builtins::register_all(&mut registry, catalog);
+        registry
+    }
+
+    /// Register a constructor under a kind name; names are not case-sensitive
+    pub fn register<F>(&mut self, kind: &str, constructor: F) -> Result<(), RegistryError>
+    where
+        F: Fn(&Params) -> Result<Box<dyn Entity>, RegistryError> + Send + Sync + 'static,
+    {
+        let kind = kind.to_ascii_lowercase();
+        if self.constructors.contains_key(&kind) {
+            return Err(RegistryError::DuplicateKind(kind));
+        }
+        self.constructors.insert(kind, Box::new(constructor));
+        Ok(())
+    }
+
+    pub fn contains(&self, kind: &str) -> bool {
+        self.constructors.contains_key(&kind.to_ascii_lowercase())
+    }
+
+    /// Registered kind names in alphabetical order
+    pub fn kinds(&self) -> Vec<&str> {
+        self.constructors.keys().map(String::as_str).collect()
+    }
+
+    /// Create an entity of a registered kind
+    ///
+    /// Besides its own parameters every kind accepts an `energy` level
+    /// such as "Tired", which replaces the level the entity starts with.
+    pub fn create(&self, kind: &str, params: &Params) -> Result<Spawned, RegistryError> {
+        let kind = kind.to_ascii_lowercase();
+        let constructor = self
+            .constructors
+            .get(&kind)
+            .ok_or_else(|| RegistryError::UnknownKind(kind.clone()))?;
+
+        let mut entity = constructor(params)?;
+        if params.get("energy").is_some() {
+            let levels = EnergyLevel::ALL.map(|level| (level.name(), level));
+            entity.set_energy(params.choose("energy", &levels, entity.energy())?);
+        }
+        Ok(Spawned::new(kind, entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::dog::DogBreed;
+    use crate::animals::{AnyFrog, Dog};
+    use crate::behaviors::{Participant, TravelMode};
+    use crate::core::{EnergyLevel, HasEnergy};
+    use crate::vehicles::amphibious::AmphibiousMode;
+    use crate::vehicles::lifecycle::Parked;
+    use crate::vehicles::{Airplane, AmphibiousVehicle, Sled};
+
+    #[test]
+    fn test_create_builtin_kinds() {
+        let registry = EntityRegistry::with_builtins();
+
+        let dog = registry
+            .create(
+                "Dog",
+                &Params::named("Rex")
+                    .with("breed", "GoldenRetriever")
+                    .with("energy", "tired"),
+            )
+            .unwrap();
+        assert_eq!(dog.kind, "dog");
+        assert_eq!(dog.name(), "Rex");
+        assert!(dog.capabilities().land_move() && !dog.capabilities().fly);
+        let dog: Dog = dog.downcast().unwrap();
+        assert_eq!(dog.breed, DogBreed::GoldenRetriever);
+        assert_eq!(dog.energy(), EnergyLevel::Tired);
+
+        let plane = registry
+            .create(
+                "airplane",
+                &Params::named("Sky")
+                    .with("type", "military")
+                    .with("year", 1999),
+            )
+            .unwrap();
+        assert_eq!(
+            plane.capabilities().travel_modes(),
+            vec![TravelMode::Drive, TravelMode::Fly]
+        );
+        let plane = plane.downcast::<Dog>().unwrap_err(); // Not a dog
+        assert!(plane.downcast_ref::<Airplane>().is_some());
+
+        let tadpole = registry
+            .create("frog", &Params::named("Polly").with("stage", "tadpole"))
+            .unwrap();
+        assert!(!tadpole.capabilities().jump);
+        assert!(!tadpole.downcast_ref::<AnyFrog>().unwrap().is_adult());
+
+        let sled = registry
+            .create("sled", &Params::named("Balto").with("dogs", 4))
+            .unwrap();
+        assert_eq!(sled.capabilities().travel_modes(), vec![TravelMode::Drive]);
+        let sled: Sled = sled.downcast().unwrap();
+        assert_eq!(sled.team().len(), 4);
+        assert_eq!(sled.team()[3].breed, DogBreed::Husky);
+    }
+
+    #[test]
+    fn test_create_amphibious_vehicle() {
+        let registry = EntityRegistry::with_builtins();
+
+        let boat = registry
+            .create("amphibious", &Params::named("Nessie").with("mode", "water"))
+            .unwrap();
+        assert_eq!(
+            boat.capabilities().travel_modes(),
+            vec![TravelMode::Drive, TravelMode::Swim]
+        );
+        let boat: AmphibiousVehicle = boat.downcast().unwrap();
+        assert_eq!(boat.mode(), AmphibiousMode::Water);
+
+        let parked = registry
+            .create(
+                "amphibious",
+                &Params::named("Dry Dock")
+                    .with("type", "Hovercraft")
+                    .with("state", "parked"),
+            )
+            .unwrap();
+        assert!(parked.capabilities().travel_modes().is_empty());
+        assert!(parked.downcast_ref::<AmphibiousVehicle<Parked>>().is_some());
+
+        assert!(matches!(
+            registry
+                .create("amphibious", &Params::named("Nessie").with("mode", "air"))
+                .unwrap_err(),
+            RegistryError::InvalidParam { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parameter_errors() {
+        let registry = EntityRegistry::with_builtins();
+        let error = |kind: &str, params: Params| registry.create(kind, &params).unwrap_err();
+
+        assert_eq!(
+            error("unicorn", Params::named("Sparkle")),
+            RegistryError::UnknownKind("unicorn".to_string())
+        );
+        assert_eq!(
+            error("duck", Params::new()),
+            RegistryError::MissingParam("name".to_string())
+        );
+        assert!(matches!(
+            error("car", Params::named("Herbie").with("year", "soon")),
+            RegistryError::InvalidParam { .. }
+        ));
+        assert!(matches!(
+            error("whale", Params::named("Moby").with("species", "Kraken")),
+            RegistryError::Catalog(CatalogError::UnknownSpecies { .. })
+        ));
+    }
+
+    /// A kind defined outside the crate's own animals and vehicles
+    #[derive(Debug)]
+    struct Robot {
+        name: String,
+        energy: EnergyLevel,
+    }
+
+    impl HasEnergy for Robot {
+        fn energy(&self) -> EnergyLevel {
+            self.energy
+        }
+
+        fn set_energy(&mut self, level: EnergyLevel) {
+            self.energy = level;
+        }
+    }
+
+    impl Participant for Robot {
+        fn participant_name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn weight_kg(&self) -> u32 {
+            120
+        }
+    }
+
+    #[test]
+    fn test_register_custom_kind() {
+        let mut registry = EntityRegistry::with_builtins();
+        registry
+            .register("robot", |params| {
+                Ok(Box::new(Robot {
+                    name: params.name()?,
+                    energy: params
+                        .parse_or("energy_points", 60)
+                        .map(EnergyLevel::from_points)?,
+                }))
+            })
+            .unwrap();
+        assert!(registry.kinds().contains(&"robot"));
+        assert_eq!(
+            registry.register("Dog", |_| unreachable!()).unwrap_err(),
+            RegistryError::DuplicateKind("dog".to_string())
+        );
+
+        let robot = registry.create("robot", &Params::named("R2")).unwrap();
+        assert_eq!(robot.participant().weight_kg(), 120);
+        assert_eq!(robot.capabilities(), Default::default());
+    }
+}
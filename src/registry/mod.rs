@@ -0,0 +1,8 @@
+pub mod builtins;
+pub mod entity;
+pub mod entity_registry;
+pub mod params;
+
+pub use entity::{Capabilities, Entity, Spawned};
+pub use entity_registry::{Constructor, EntityRegistry, RegistryError};
+pub use params::{Params, same_name};
@@ -0,0 +1,86 @@
+use crate::registry::RegistryError;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Check if two names match, ignoring case and spaces ("BlueWhale" is "Blue Whale")
+pub fn same_name(a: &str, b: &str) -> bool {
+    let letters = |name: &str| {
+        name.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+    letters(a) == letters(b)
+}
+
+/// Named string parameters passed to an entity constructor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parameters for an entity with a name
+    pub fn named(name: impl Into<String>) -> Self {
+        Self::new().with("name", name.into())
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl ToString) {
+        self.values.insert(key.into(), value.to_string());
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    pub fn require(&self, key: &str) -> Result<&str, RegistryError> {
+        self.get(key)
+            .ok_or_else(|| RegistryError::MissingParam(key.to_string()))
+    }
+
+    /// The entity's name, which every constructor needs
+    pub fn name(&self) -> Result<String, RegistryError> {
+        self.require("name").map(str::to_string)
+    }
+
+    /// Parse a parameter, falling back to a default when it is not given
+    pub fn parse_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, RegistryError> {
+        match self.get(key) {
+            Some(value) => value.parse().map_err(|_| self.invalid(key, value)),
+            None => Ok(default),
+        }
+    }
+
+    /// Pick one of several named options, falling back to a default when not given
+    pub fn choose<T: Clone>(
+        &self,
+        key: &str,
+        options: &[(&str, T)],
+        default: T,
+    ) -> Result<T, RegistryError> {
+        let Some(value) = self.get(key) else {
+            return Ok(default);
+        };
+        options
+            .iter()
+            .find(|(name, _)| same_name(name, value))
+            .map(|(_, option)| option.clone())
+            .ok_or_else(|| self.invalid(key, value))
+    }
+
+    fn invalid(&self, key: &str, value: &str) -> RegistryError {
+        RegistryError::InvalidParam {
+            param: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
@@ -0,0 +1,572 @@
+//! Long-Distance Migration
+//!
+//! A [`MigrationPlanner`] follows an animal along a [`Route`] day by day.
+//! Each travel day is split into hourly legs priced with the route planner's
+//! cost model, so a journey easily spans hundreds of legs. Energy is tracked
+//! in thousandths of a level and taken from the animal as whole levels add
+//! up, while food eaten earlier keeps digesting on the way.
+//!
+//! An animal never sets out on a leg that would leave it below Exhausted,
+//! so it never collapses on the way. A day that starts below Normal energy,
+//! or in which the animal cannot afford a single leg, is a stopover: the
+//! animal feeds at the stopover's [`FeedingStation`] and rests overnight,
+//! which also heals its injuries.
+//!
+//! Every day's weather is drawn from the [`Season`] with a seeded RNG, so
+//! the same seed always produces the same journey. Weather that is unsafe
+//! for flying grounds flyers, rough weather makes flying harder, and both
+//! season and weather shift the temperature the animal has to endure.
+
+use crate::animals::{Feeding, FeedingStation};
+use crate::behaviors::{Participant, TravelMode};
+use crate::core::{EnergyLevel, HasEnergy, HasHealth, SeededRng, Weather};
+use crate::routes::planner::{ModeProfile, RouteError, RoutePlanner};
+use crate::routes::segment::{Route, Segment};
+use std::fmt;
+
+/// Rests an animal takes during a stopover night
+const STOPOVER_RESTS: u32 = 2;
+
+/// Minutes of digestion during a stopover night
+const STOPOVER_DIGESTION_MINUTES: u32 = 12 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// Weather that can occur in this season, with relative weights
+    pub fn weather_table(&self) -> &'static [(Weather, u32)] {
+        match self {
+            Season::Spring => &[
+                (Weather::Clear, 3),
+                (Weather::PartlyCloudy, 3),
+                (Weather::LightRain, 3),
+                (Weather::Rain, 2),
+                (Weather::Windy, 2),
+                (Weather::Fog, 1),
+                (Weather::Thunderstorm, 1),
+            ],
+            Season::Summer => &[
+                (Weather::Sunny, 5),
+                (Weather::Clear, 3),
+                (Weather::PartlyCloudy, 2),
+                (Weather::Thunderstorm, 1),
+            ],
+            Season::Autumn => &[
+                (Weather::Overcast, 3),
+                (Weather::Windy, 3),
+                (Weather::Rain, 3),
+                (Weather::HeavyWind, 2),
+                (Weather::Fog, 2),
+                (Weather::Storm, 1),
+                (Weather::Hurricane, 1),
+            ],
+            Season::Winter => &[
+                (Weather::Overcast, 3),
+                (Weather::Snow, 3),
+                (Weather::Clear, 2),
+                (Weather::Cloudy, 2),
+                (Weather::HeavyWind, 1),
+                (Weather::Blizzard, 1),
+            ],
+        }
+    }
+
+    /// Draw the weather for one day
+    pub fn draw_weather(&self, rng: &mut SeededRng) -> Weather {
+        let table = self.weather_table();
+        let total: u32 = table.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.below(total);
+        for (weather, weight) in table {
+            if roll < *weight {
+                return *weather;
+            }
+            roll -= weight;
+        }
+        table[0].0
+    }
+
+    /// How much the season shifts the temperature, in degrees Celsius
+    pub fn temperature_shift_c(&self) -> i32 {
+        match self {
+            Season::Spring => 0,
+            Season::Summer => 10,
+            Season::Autumn => -3,
+            Season::Winter => -12,
+        }
+    }
+}
+
+impl fmt::Display for Season {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What an animal did on one day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DayActivity {
+    Travelled {
+        km: u32,
+        legs: u32,
+        modes: Vec<TravelMode>,
+    },
+    /// Fed and rested; `meal` describes what was eaten, or why nothing was
+    Stopover { meal: String },
+    /// No mode could be used in today's weather
+    Grounded,
+}
+
+/// One day of a migration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationDay {
+    pub day: u32,
+    pub weather: Weather,
+    pub activity: DayActivity,
+    pub energy_at_end: EnergyLevel,
+    pub remaining_km: u32,
+}
+
+impl fmt::Display for MigrationDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} ({}): ", self.day, self.weather)?;
+        match &self.activity {
+            DayActivity::Travelled { km, legs, modes } => {
+                let modes: Vec<String> = modes.iter().map(|mode| mode.to_string()).collect();
+                write!(f, "{} km in {} legs ({})", km, legs, modes.join("/"))?
+            }
+            DayActivity::Stopover { meal } => write!(f, "stopover - {}", meal)?,
+            DayActivity::Grounded => write!(f, "grounded by the weather")?,
+        }
+        write!(
+            f,
+            ", {} km to go, energy {}",
+            self.remaining_km, self.energy_at_end
+        )
+    }
+}
+
+/// How a migration ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationOutcome {
+    Arrived { day: u32 },
+    OutOfTime { remaining_km: u32 },
+}
+
+impl fmt::Display for MigrationOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationOutcome::Arrived { day } => write!(f, "arrived on day {}", day),
+            MigrationOutcome::OutOfTime { remaining_km } => {
+                write!(f, "ran out of time with {} km to go", remaining_km)
+            }
+        }
+    }
+}
+
+/// Day-by-day record of a migration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationLog {
+    pub animal: String,
+    pub season: Season,
+    pub days: Vec<MigrationDay>,
+    pub total_legs: u32,
+    pub outcome: MigrationOutcome,
+}
+
+impl MigrationLog {
+    pub fn arrived(&self) -> bool {
+        matches!(self.outcome, MigrationOutcome::Arrived { .. })
+    }
+
+    /// Number of stopover days
+    pub fn stopovers(&self) -> usize {
+        self.days
+            .iter()
+            .filter(|day| matches!(day.activity, DayActivity::Stopover { .. }))
+            .count()
+    }
+}
+
+impl fmt::Display for MigrationLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}'s {} migration", self.animal, self.season)?;
+        for day in &self.days {
+            writeln!(f, "  {}", day)?;
+        }
+        write!(
+            f,
+            "{} {} after {} legs",
+            self.animal, self.outcome, self.total_legs
+        )
+    }
+}
+
+/// Simulates long journeys with stopovers over a multi-day timeline
+#[derive(Debug, Clone)]
+pub struct MigrationPlanner {
+    pub season: Season,
+    /// Food available at every stopover
+    pub stopover: FeedingStation,
+    pub travel_hours_per_day: u32,
+    pub max_days: u32,
+    pub seed: u64,
+}
+
+impl MigrationPlanner {
+    pub fn new(season: Season, stopover: FeedingStation) -> Self {
+        Self {
+            season,
+            stopover,
+            travel_hours_per_day: 10,
+            max_days: 365,
+            seed: 0,
+        }
+    }
+
+    /// Set the seed for the weather
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Give up after this many days
+    pub fn with_max_days(mut self, max_days: u32) -> Self {
+        self.max_days = max_days;
+        self
+    }
+
+    /// Simulate the migration, changing the animal's energy along the way
+    pub fn simulate<T: Participant + Feeding + HasHealth>(
+        &self,
+        animal: &mut T,
+        route: &Route,
+    ) -> Result<MigrationLog, RouteError> {
+        if route.segments.is_empty() {
+            return Err(RouteError::EmptyRoute);
+        }
+        // Segments that cannot be crossed even in fair weather never will be
+        for (index, segment) in route.segments.iter().enumerate() {
+            if RoutePlanner::default().options(animal, segment).is_empty() {
+                return Err(RouteError::Impassable {
+                    participant: animal.participant_name(),
+                    index,
+                    segment: *segment,
+                });
+            }
+        }
+
+        let mut rng = SeededRng::new(self.seed);
+        let mut journey = Journey::new(route);
+        let mut days = Vec::new();
+        let mut total_legs = 0;
+
+        for day in 1..=self.max_days {
+            let weather = self.season.draw_weather(&mut rng);
+            let activity = if animal.energy() < EnergyLevel::Normal {
+                self.stop_over(animal)
+            } else {
+                let (km, legs, modes) = self.travel(animal, &mut journey, weather);
+                total_legs += legs;
+                if legs > 0 {
+                    DayActivity::Travelled { km, legs, modes }
+                } else if self.best_mode(animal, journey.segment(), weather).is_some() {
+                    // Fit to travel, but even one leg costs more than it has
+                    self.stop_over(animal)
+                } else {
+                    DayActivity::Grounded
+                }
+            };
+
+            days.push(MigrationDay {
+                day,
+                weather,
+                activity,
+                energy_at_end: animal.energy(),
+                remaining_km: journey.remaining_km(),
+            });
+
+            if journey.is_finished() {
+                let outcome = MigrationOutcome::Arrived { day };
+                return Ok(self.log(animal, days, total_legs, outcome));
+            }
+        }
+
+        let outcome = MigrationOutcome::OutOfTime {
+            remaining_km: journey.remaining_km(),
+        };
+        Ok(self.log(animal, days, total_legs, outcome))
+    }
+
+    /// Feed and rest through the night, healing injuries
+    fn stop_over<T: Feeding + HasHealth>(&self, animal: &mut T) -> DayActivity {
+        let meal = match self.stopover.serve(animal) {
+            Ok(message) => message,
+            Err(error) => error.to_string(),
+        };
+        animal.digest(STOPOVER_DIGESTION_MINUTES);
+        for _ in 0..STOPOVER_RESTS {
+            animal.recover();
+        }
+        DayActivity::Stopover { meal }
+    }
+
+    /// Travel hourly legs until the day ends or the animal is exhausted
+    fn travel<T: Participant + Feeding>(
+        &self,
+        animal: &mut T,
+        journey: &mut Journey,
+        weather: Weather,
+    ) -> (u32, u32, Vec<TravelMode>) {
+        let mut km = 0;
+        let mut legs = 0;
+        let mut modes = Vec::new();
+
+        for _ in 0..self.travel_hours_per_day {
+            if journey.is_finished() {
+                break;
+            }
+            let Some((mode, profile)) = self.best_mode(animal, journey.segment(), weather) else {
+                break;
+            };
+
+            let leg_km = profile.speed_kmh.min(journey.left_in_segment());
+            let energy_milli = leg_km * profile.effort * 1000 / profile.km_per_level.max(1);
+            if !journey.can_afford(animal.energy(), energy_milli) {
+                break;
+            }
+            journey.advance(leg_km);
+            journey.spend(animal, energy_milli);
+            animal.digest(60);
+
+            km += leg_km;
+            legs += 1;
+            if !modes.contains(&mode) {
+                modes.push(mode);
+            }
+        }
+
+        (km, legs, modes)
+    }
+
+    /// Fastest mode for a segment in today's weather
+    fn best_mode(
+        &self,
+        animal: &dyn Participant,
+        segment: &Segment,
+        weather: Weather,
+    ) -> Option<(TravelMode, ModeProfile)> {
+        let temperature_c = segment.kind.temperature_c()
+            + self.season.temperature_shift_c()
+            + weather.temperature_shift_c();
+
+        animal
+            .travel_modes()
+            .into_iter()
+            .filter(|mode| *mode != TravelMode::Fly || weather.is_safe_for_flying())
+            .filter_map(|mode| {
+                let mut profile =
+                    RoutePlanner::profile_at(animal, mode, segment.kind, temperature_c)?;
+                if mode == TravelMode::Fly {
+                    profile.effort += weather.energy_cost() as u32;
+                }
+                Some((mode, profile))
+            })
+            .max_by_key(|(_, profile)| profile.speed_kmh)
+    }
+
+    fn log(
+        &self,
+        animal: &dyn Participant,
+        days: Vec<MigrationDay>,
+        total_legs: u32,
+        outcome: MigrationOutcome,
+    ) -> MigrationLog {
+        MigrationLog {
+            animal: animal.participant_name(),
+            season: self.season,
+            days,
+            total_legs,
+            outcome,
+        }
+    }
+}
+
+/// Progress along the route
+struct Journey<'a> {
+    segments: &'a [Segment],
+    index: usize,
+    km_into_segment: u32,
+    /// Energy spent but not yet taken, in thousandths of a level
+    energy_milli: u32,
+}
+
+impl<'a> Journey<'a> {
+    fn new(route: &'a Route) -> Self {
+        Self {
+            segments: &route.segments,
+            index: 0,
+            km_into_segment: 0,
+            energy_milli: 0,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.index >= self.segments.len()
+    }
+
+    fn segment(&self) -> &Segment {
+        &self.segments[self.index]
+    }
+
+    fn remaining_km(&self) -> u32 {
+        let rest: u32 = self
+            .segments
+            .iter()
+            .skip(self.index)
+            .map(|segment| segment.distance_km)
+            .sum();
+        rest - self.km_into_segment
+    }
+
+    fn left_in_segment(&self) -> u32 {
+        self.segment().distance_km - self.km_into_segment
+    }
+
+    /// Cover `km` of the current segment, moving on to the next when done
+    fn advance(&mut self, km: u32) {
+        self.km_into_segment += km;
+        if self.km_into_segment == self.segment().distance_km {
+            self.index += 1;
+            self.km_into_segment = 0;
+        }
+    }
+
+    /// Check if a leg would leave the animal at least Exhausted
+    fn can_afford(&self, energy: EnergyLevel, energy_milli: u32) -> bool {
+        let levels = (self.energy_milli + energy_milli) / 1000;
+        energy as u32 >= EnergyLevel::Exhausted as u32 + levels
+    }
+
+    /// Take whole energy levels from the animal as they add up
+    fn spend(&mut self, animal: &mut impl HasEnergy, energy_milli: u32) {
+        self.energy_milli += energy_milli;
+        while self.energy_milli >= 1000 {
+            self.energy_milli -= 1000;
+            animal.consume_energy();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::dog::DogBreed;
+    use crate::animals::whale::WhaleSpecies;
+    use crate::animals::{Dog, Duck, Food, FoodKind, Whale};
+    use crate::core::{Mishap, Terrain};
+    use crate::routes::SegmentKind;
+
+    fn stopover() -> FeedingStation {
+        FeedingStation::new(
+            vec![
+                Food::new(FoodKind::Seeds, 30),
+                Food::new(FoodKind::Fish, 40),
+            ],
+            60,
+        )
+    }
+
+    #[test]
+    fn test_duck_flies_south_with_stopovers() {
+        let route =
+            Route::new("South".to_string()).segment(SegmentKind::Air { altitude: 1000 }, 3000);
+        let mut duck = Duck::new("Drake".to_string());
+        let planner = MigrationPlanner::new(Season::Autumn, stopover()).with_seed(7);
+
+        let log = planner.simulate(&mut duck, &route).unwrap();
+        assert!(log.arrived(), "{}", log);
+        assert!(log.stopovers() > 0);
+        assert_eq!(log.days.last().unwrap().remaining_km, 0);
+
+        // The same seed replays the same journey
+        let mut twin = Duck::new("Drake".to_string());
+        assert_eq!(planner.simulate(&mut twin, &route).unwrap(), log);
+    }
+
+    #[test]
+    fn test_humpback_crosses_an_ocean() {
+        let route = Route::new("Ocean".to_string()).segment(SegmentKind::Water { depth: 0 }, 5000);
+        let mut whale = Whale::new("Migaloo".to_string(), WhaleSpecies::Humpback);
+        let log = MigrationPlanner::new(Season::Winter, stopover())
+            .simulate(&mut whale, &route)
+            .unwrap();
+
+        assert!(log.arrived(), "{}", log);
+        assert!(log.total_legs > 100);
+        assert!(whale.energy() >= EnergyLevel::Exhausted);
+    }
+
+    #[test]
+    fn test_unaffordable_leg_is_a_stopover() {
+        // Every hour in the spring desert costs a Labrador three levels,
+        // more than it can spend at Normal energy
+        let route = Route::new("Dunes".to_string()).segment(SegmentKind::Land(Terrain::Desert), 21);
+        let mut dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        dog.set_energy(EnergyLevel::Normal);
+        let log = MigrationPlanner::new(Season::Spring, stopover())
+            .with_max_days(20)
+            .simulate(&mut dog, &route)
+            .unwrap();
+
+        assert!(log.arrived(), "{}", log);
+        assert!(
+            log.days
+                .iter()
+                .all(|day| !matches!(day.activity, DayActivity::Grounded))
+        );
+        assert!(matches!(log.days[0].activity, DayActivity::Stopover { .. }));
+    }
+
+    #[test]
+    fn test_injuries_heal_over_stopovers() {
+        let planner = MigrationPlanner::new(Season::Spring, stopover());
+        let mut dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        dog.injure(Mishap::Terrain(Terrain::Glacier)); // Moderate, three rests
+
+        planner.stop_over(&mut dog);
+        assert_eq!(dog.health().worst_injury().unwrap().rests_left, 1);
+        planner.stop_over(&mut dog);
+        assert!(!dog.is_injured());
+    }
+
+    #[test]
+    fn test_migration_can_run_out_of_time() {
+        let route = Route::new("Far".to_string()).segment(SegmentKind::Water { depth: 0 }, 5000);
+        let mut whale = Whale::new("Migaloo".to_string(), WhaleSpecies::Humpback);
+        let log = MigrationPlanner::new(Season::Summer, stopover())
+            .with_max_days(10)
+            .simulate(&mut whale, &route)
+            .unwrap();
+
+        assert_eq!(log.days.len(), 10);
+        assert!(matches!(log.outcome, MigrationOutcome::OutOfTime { .. }));
+
+        let land = Route::new("Land".to_string()).segment(SegmentKind::Land(Terrain::Grass), 10);
+        assert!(matches!(
+            MigrationPlanner::new(Season::Summer, stopover()).simulate(&mut whale, &land),
+            Err(RouteError::Impassable { .. })
+        ));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod migration;
+pub mod planner;
+pub mod segment;
+
+pub use migration::{
+    DayActivity, MigrationDay, MigrationLog, MigrationOutcome, MigrationPlanner, Season,
+};
+pub use planner::{ModeProfile, PlanPreference, PlannedLeg, RouteError, RoutePlan, RoutePlanner};
+pub use segment::{Route, Segment, SegmentKind};
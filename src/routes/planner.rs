@@ -0,0 +1,567 @@
+//! Route Planning
+//!
+//! The planner walks a [`Route`] segment by segment, asks the participant
+//! which travel modes it supports, and estimates each feasible mode:
+//!
+//! - **Drive**: fuel use follows `fuel_efficiency()` (km per energy level),
+//!   scaled by the road's `energy_cost()`; speed is `max_speed()`.
+//! - **Walk / Swim / Fly**: one energy level per two hours of travel at the
+//!   entity's cruising speed; walking is scaled by `1 + energy_cost()` of
+//!   the terrain.
+//!
+//! Participants with a thermal range pay one extra unit of effort per
+//! started 10 degrees outside it, and cannot cross segments more than 20
+//! degrees outside it; cold-blooded animals are also slowed by the cold.
+//!
+//! For entities with several modes the planner picks one per segment
+//! according to the [`PlanPreference`]. Changing modes can cost energy of
+//! its own, such as an amphibious vehicle converting between land and
+//! water; that cost is added to the leg that needs the new mode. With a [`Carrier`] along, riding on
+//! board is one more option per segment; the carrier pays the energy for it.
+
+use crate::behaviors::driving::RoadType;
+use crate::behaviors::{Participant, TravelMode};
+use crate::core::EnergyLevel;
+use crate::routes::segment::{Route, Segment, SegmentKind};
+use crate::vehicles::Carrier;
+use thiserror::Error;
+
+/// Hours of walking, swimming or flying that cost one energy level
+const HOURS_PER_ENERGY_LEVEL: u32 = 2;
+
+/// What the planner optimises for when several modes are possible
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlanPreference {
+    #[default]
+    Fastest,
+    LeastEnergy,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    #[error("Route has no segments")]
+    EmptyRoute,
+
+    #[error("{participant} cannot cross segment {index} ({segment})")]
+    Impassable {
+        participant: String,
+        index: usize,
+        segment: Segment,
+    },
+
+    #[error("{carrier} has no room for {participant}")]
+    CannotBoard {
+        participant: String,
+        carrier: String,
+    },
+}
+
+/// How one segment will be covered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedLeg {
+    pub segment: Segment,
+    pub mode: TravelMode,
+    pub energy_levels: u32,
+    pub minutes: u32,
+    /// Covered on board a carrier, which spends `energy_levels`
+    pub carried: bool,
+    /// Energy spent switching into `mode`, included in `energy_levels`
+    pub switch_levels: u32,
+}
+
+/// The full plan for a participant on a route
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePlan {
+    pub participant: String,
+    pub legs: Vec<PlannedLeg>,
+    pub starting_energy: EnergyLevel,
+    /// Energy the participant spends on its own legs
+    pub total_energy_levels: u32,
+    /// Energy the carrier spends on carried legs
+    pub carrier_energy_levels: u32,
+    pub total_minutes: u32,
+    /// Index of the leg on which the participant would collapse, if any
+    pub runs_out_at: Option<usize>,
+}
+
+impl RoutePlan {
+    /// Check if the participant can finish without collapsing
+    pub fn is_completable(&self) -> bool {
+        self.runs_out_at.is_none()
+    }
+
+    /// Get the mode chosen for each leg
+    pub fn modes(&self) -> Vec<TravelMode> {
+        self.legs.iter().map(|leg| leg.mode).collect()
+    }
+}
+
+/// How fast and how costly a travel mode is on one kind of segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModeProfile {
+    pub speed_kmh: u32,
+    /// Multiplier applied to the distance before it is converted to energy
+    pub effort: u32,
+    /// Kilometers of plain effort per energy level
+    pub km_per_level: u32,
+}
+
+impl ModeProfile {
+    /// Profile for muscle- or engine-powered travel limited by endurance
+    fn endurance(speed_kmh: u32, effort: u32) -> Self {
+        Self {
+            speed_kmh,
+            effort,
+            km_per_level: speed_kmh.saturating_mul(HOURS_PER_ENERGY_LEVEL),
+        }
+    }
+
+    /// Energy levels needed to cover a distance
+    pub fn energy_levels(&self, distance_km: u32) -> u32 {
+        let levels =
+            (distance_km as u64 * self.effort as u64).div_ceil(self.km_per_level.max(1) as u64);
+        u32::try_from(levels).unwrap_or(u32::MAX)
+    }
+
+    /// Minutes needed to cover a distance
+    pub fn minutes(&self, distance_km: u32) -> u32 {
+        let minutes = (distance_km as u64 * 60).div_ceil(self.speed_kmh.max(1) as u64);
+        u32::try_from(minutes).unwrap_or(u32::MAX)
+    }
+}
+
+/// Plans multi-modal journeys
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoutePlanner {
+    pub preference: PlanPreference,
+}
+
+impl RoutePlanner {
+    pub fn new(preference: PlanPreference) -> Self {
+        Self { preference }
+    }
+
+    /// Describe how a mode performs on a kind of segment - `None` if it cannot cross
+    pub fn profile(
+        participant: &dyn Participant,
+        mode: TravelMode,
+        kind: SegmentKind,
+    ) -> Option<ModeProfile> {
+        Self::profile_at(participant, mode, kind, kind.temperature_c())
+    }
+
+    /// Like [`RoutePlanner::profile`], at a given ambient temperature
+    pub fn profile_at(
+        participant: &dyn Participant,
+        mode: TravelMode,
+        kind: SegmentKind,
+        temperature_c: i32,
+    ) -> Option<ModeProfile> {
+        let mut profile = match (mode, kind) {
+            (TravelMode::Drive, kind) => {
+                let driver = participant.as_driver()?;
+                let road_type = match kind {
+                    SegmentKind::Road(road_type) => road_type,
+                    SegmentKind::Land(terrain) => RoadType::for_terrain(terrain)?,
+                    _ => return None,
+                };
+                if road_type.requires_off_road_capability() && !driver.has_off_road_capability() {
+                    return None;
+                }
+
+                ModeProfile {
+                    speed_kmh: driver.max_speed(),
+                    effort: road_type.energy_cost() as u32,
+                    km_per_level: driver.fuel_efficiency(),
+                }
+            }
+            (TravelMode::Walk, kind) => {
+                let walker = participant.as_walker()?;
+                // Terrain cost is the extra effort on top of plain walking
+                let effort = match kind {
+                    SegmentKind::Road(_) => 1,
+                    SegmentKind::Land(terrain) if terrain.walkable() => 1 + terrain.energy_cost(),
+                    _ => return None,
+                };
+                ModeProfile::endurance(walker.walking_speed(), effort as u32)
+            }
+            (TravelMode::Swim, SegmentKind::Water { depth }) => {
+                let swimmer = participant.as_swimmer()?;
+                if depth > swimmer.max_depth() {
+                    return None;
+                }
+                ModeProfile::endurance(swimmer.swimming_speed(), 1)
+            }
+            (TravelMode::Swim, _) => return None,
+            (TravelMode::Fly, kind) => {
+                let flyer = participant.as_flyer()?;
+                if let SegmentKind::Air { altitude } = kind
+                    && altitude > flyer.max_altitude()
+                {
+                    return None;
+                }
+                ModeProfile::endurance(flyer.flying_speed(), 1)
+            }
+        };
+
+        if let Some(thermal) = participant.as_thermal() {
+            let exposure = thermal.exposure(temperature_c);
+            if exposure.is_unbearable() {
+                return None;
+            }
+            profile.effort += exposure.energy_cost() as u32;
+            profile.speed_kmh = thermal.thermal_speed(profile.speed_kmh, temperature_c);
+        }
+
+        (profile.speed_kmh > 0).then_some(profile)
+    }
+
+    /// Estimate a single mode on a segment - `None` if the mode cannot cross it
+    pub fn estimate(
+        &self,
+        participant: &dyn Participant,
+        mode: TravelMode,
+        segment: &Segment,
+    ) -> Option<PlannedLeg> {
+        let profile = Self::profile(participant, mode, segment.kind)?;
+
+        Some(PlannedLeg {
+            segment: *segment,
+            mode,
+            energy_levels: profile.energy_levels(segment.distance_km),
+            minutes: profile.minutes(segment.distance_km),
+            carried: false,
+            switch_levels: 0,
+        })
+    }
+
+    /// Get every feasible way to cross a segment
+    pub fn options(&self, participant: &dyn Participant, segment: &Segment) -> Vec<PlannedLeg> {
+        participant
+            .travel_modes()
+            .into_iter()
+            .filter_map(|mode| self.estimate(participant, mode, segment))
+            .collect()
+    }
+
+    /// Plan a route, picking the preferred mode for every segment
+    pub fn plan(
+        &self,
+        participant: &dyn Participant,
+        route: &Route,
+    ) -> Result<RoutePlan, RouteError> {
+        if route.segments.is_empty() {
+            return Err(RouteError::EmptyRoute);
+        }
+
+        let mut legs = Vec::with_capacity(route.segments.len());
+        for (index, segment) in route.segments.iter().enumerate() {
+            let best = self
+                .best_leg(participant, segment)
+                .ok_or_else(|| Self::impassable(participant, index, segment))?;
+            legs.push(best);
+        }
+
+        Ok(Self::summarize(participant, legs))
+    }
+
+    /// Plan a route with a carrier standing by, such as a ferry or an airlift
+    ///
+    /// Each segment is covered on board whenever that beats the participant's
+    /// own modes under the preference, or when it cannot cross on its own.
+    pub fn plan_with_carrier<C: Carrier + Participant>(
+        &self,
+        participant: &dyn Participant,
+        carrier: &C,
+        route: &Route,
+    ) -> Result<RoutePlan, RouteError> {
+        if route.segments.is_empty() {
+            return Err(RouteError::EmptyRoute);
+        }
+
+        let can_board = carrier.can_board(participant);
+        let mut legs = Vec::with_capacity(route.segments.len());
+        for (index, segment) in route.segments.iter().enumerate() {
+            let own = self.best_leg(participant, segment);
+            let carried = can_board
+                .then(|| self.best_leg(carrier, segment))
+                .flatten()
+                .map(|leg| PlannedLeg {
+                    carried: true,
+                    ..leg
+                });
+
+            let best = match (own, carried) {
+                (Some(own), Some(carried)) => {
+                    if self.rank(&carried) < self.rank(&own) {
+                        carried
+                    } else {
+                        own
+                    }
+                }
+                (Some(leg), None) | (None, Some(leg)) => leg,
+                (None, None) if !can_board => {
+                    return Err(RouteError::CannotBoard {
+                        participant: participant.participant_name(),
+                        carrier: carrier.participant_name(),
+                    });
+                }
+                (None, None) => return Err(Self::impassable(participant, index, segment)),
+            };
+            legs.push(best);
+        }
+
+        Ok(Self::summarize(participant, legs))
+    }
+
+    /// Pick the preferred way to cross a segment
+    fn best_leg(&self, participant: &dyn Participant, segment: &Segment) -> Option<PlannedLeg> {
+        self.options(participant, segment)
+            .into_iter()
+            .min_by_key(|leg| self.rank(leg))
+    }
+
+    /// Ordering key for a leg; carried legs cost the participant no energy
+    fn rank(&self, leg: &PlannedLeg) -> (u32, u32) {
+        let energy_levels = if leg.carried { 0 } else { leg.energy_levels };
+        match self.preference {
+            PlanPreference::Fastest => (leg.minutes, energy_levels),
+            PlanPreference::LeastEnergy => (energy_levels, leg.minutes),
+        }
+    }
+
+    fn impassable(participant: &dyn Participant, index: usize, segment: &Segment) -> RouteError {
+        RouteError::Impassable {
+            participant: participant.participant_name(),
+            index,
+            segment: *segment,
+        }
+    }
+
+    fn summarize(participant: &dyn Participant, mut legs: Vec<PlannedLeg>) -> RoutePlan {
+        // Energy levels above Collapsed are what the participant can spend
+        let starting_energy = participant.energy();
+        let available = starting_energy as u32;
+        let mut spent: u32 = 0;
+        let mut carrier_spent: u32 = 0;
+        let mut runs_out_at = None;
+        let mut previous_mode = None;
+        for (index, leg) in legs.iter_mut().enumerate() {
+            if leg.carried {
+                carrier_spent = carrier_spent.saturating_add(leg.energy_levels);
+                continue;
+            }
+            leg.switch_levels = participant.mode_switch_energy(previous_mode, leg.mode);
+            leg.energy_levels = leg.energy_levels.saturating_add(leg.switch_levels);
+            previous_mode = Some(leg.mode);
+
+            spent = spent.saturating_add(leg.energy_levels);
+            if spent >= available && runs_out_at.is_none() {
+                runs_out_at = Some(index);
+            }
+        }
+
+        RoutePlan {
+            participant: participant.participant_name(),
+            total_energy_levels: spent,
+            carrier_energy_levels: carrier_spent,
+            total_minutes: legs
+                .iter()
+                .fold(0u32, |total, leg| total.saturating_add(leg.minutes)),
+            legs,
+            starting_energy,
+            runs_out_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::{Dog, Duck, dog::DogBreed};
+    use crate::core::{HasEnergy, Terrain};
+    use crate::vehicles::amphibious::{AmphibiousPropulsion, AmphibiousType, HullType};
+    use crate::vehicles::ship::{PropulsionType, ShipType};
+    use crate::vehicles::{AmphibiousVehicle, Car, Ship, car::EngineType};
+
+    fn car() -> Car {
+        Car::new(
+            "Test".to_string(),
+            "Test Motors".to_string(),
+            2022,
+            EngineType::Electric {
+                battery_capacity: 100,
+            },
+        )
+    }
+
+    #[test]
+    fn test_empty_route() {
+        let planner = RoutePlanner::default();
+        assert_eq!(
+            planner.plan(&car(), &Route::new("Nowhere".to_string())),
+            Err(RouteError::EmptyRoute)
+        );
+    }
+
+    #[test]
+    fn test_car_cannot_cross_water() {
+        let route = Route::new("Coast".to_string())
+            .road(RoadType::Highway, 50)
+            .water(0, 5);
+
+        let error = RoutePlanner::default().plan(&car(), &route).unwrap_err();
+        assert!(matches!(error, RouteError::Impassable { index: 1, .. }));
+    }
+
+    #[test]
+    fn test_car_fuel_and_time() {
+        let mut car = car();
+        car.set_energy(EnergyLevel::Hyperactive);
+        let route = Route::new("Commute".to_string())
+            .road(RoadType::Highway, 110)
+            .land(Terrain::Grass, 20);
+
+        let plan = RoutePlanner::default().plan(&car, &route).unwrap();
+        assert_eq!(plan.modes(), vec![TravelMode::Drive, TravelMode::Drive]);
+        assert_eq!(plan.total_energy_levels, 2); // 120 km per level, road costs 1
+        assert_eq!(plan.total_minutes, 60 + 11); // 110 km/h top speed
+        assert!(plan.is_completable());
+    }
+
+    #[test]
+    fn test_amphibious_switches_modes() {
+        let vehicle = AmphibiousVehicle::new(
+            "Test".to_string(),
+            "Test Marine".to_string(),
+            2022,
+            AmphibiousType::AmphibiousCar,
+            AmphibiousPropulsion::WheelsAndPropeller {
+                wheels: 4,
+                propeller_power: 50,
+            },
+            HullType::Sealed,
+        );
+        let route = Route::new("Lake crossing".to_string())
+            .road(RoadType::Country, 10)
+            .water(0, 4)
+            .road(RoadType::Country, 10);
+
+        let plan = RoutePlanner::default().plan(&vehicle, &route).unwrap();
+        assert_eq!(
+            plan.modes(),
+            vec![TravelMode::Drive, TravelMode::Swim, TravelMode::Drive]
+        );
+
+        // Starts in land mode, converts into the water and back out
+        let switches: Vec<u32> = plan.legs.iter().map(|leg| leg.switch_levels).collect();
+        assert_eq!(switches, vec![0, 1, 1]);
+        let travel: u32 = route
+            .segments
+            .iter()
+            .zip(plan.modes())
+            .map(|(segment, mode)| {
+                RoutePlanner::default()
+                    .estimate(&vehicle, mode, segment)
+                    .unwrap()
+                    .energy_levels
+            })
+            .sum();
+        assert_eq!(plan.total_energy_levels, travel + 2);
+    }
+
+    #[test]
+    fn test_very_long_segment_saturates() {
+        let dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        let route = Route::new("Around the world".to_string())
+            .land(Terrain::Mountain, u32::MAX)
+            .land(Terrain::Grass, u32::MAX);
+
+        let plan = RoutePlanner::default().plan(&dog, &route).unwrap();
+        assert_eq!(plan.total_minutes, u32::MAX);
+        assert!(plan.total_energy_levels > 1_000_000);
+        assert!(!plan.is_completable());
+    }
+
+    #[test]
+    fn test_duck_prefers_flying_when_fastest() {
+        let duck = Duck::new("Donald".to_string());
+        let route = Route::new("Pond hop".to_string())
+            .land(Terrain::Grass, 2)
+            .water(0, 1)
+            .air(500, 20);
+
+        let plan = RoutePlanner::default().plan(&duck, &route).unwrap();
+        assert_eq!(plan.modes(), vec![TravelMode::Fly; 3]);
+
+        let too_high = Route::new("Mountain pass".to_string()).air(2000, 20);
+        assert!(RoutePlanner::default().plan(&duck, &too_high).is_err());
+    }
+
+    #[test]
+    fn test_least_energy_preference() {
+        let dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        let route = Route::new("Walk".to_string()).land(Terrain::Mountain, 14);
+
+        let plan = RoutePlanner::new(PlanPreference::LeastEnergy)
+            .plan(&dog, &route)
+            .unwrap();
+        assert_eq!(plan.legs[0].mode, TravelMode::Walk);
+        assert_eq!(plan.legs[0].minutes, 120);
+        assert_eq!(plan.legs[0].energy_levels, 3); // Mountain costs 3 per 14 km
+    }
+
+    #[test]
+    fn test_ferry_crossing_with_carrier() {
+        let mut dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        dog.set_energy(EnergyLevel::Normal);
+        let ferry = Ship::new(
+            "Ferry".to_string(),
+            "Test Yard".to_string(),
+            2015,
+            ShipType::Ferry,
+            5000,
+            PropulsionType::Diesel {
+                engines: 2,
+                power_each: 3000,
+            },
+        );
+        let route = Route::new("Island trip".to_string())
+            .land(Terrain::Dirt, 7)
+            .water(0, 50)
+            .land(Terrain::Dirt, 7);
+
+        // Fifty km of open water is too much for the dog on its own
+        let alone = RoutePlanner::default().plan(&dog, &route).unwrap();
+        assert!(!alone.is_completable());
+
+        let plan = RoutePlanner::default()
+            .plan_with_carrier(&dog, &ferry, &route)
+            .unwrap();
+        assert_eq!(
+            plan.legs.iter().map(|leg| leg.carried).collect::<Vec<_>>(),
+            vec![false, true, false]
+        );
+        assert_eq!(plan.total_energy_levels, 2);
+        assert_eq!(plan.carrier_energy_levels, 1);
+        assert!(plan.is_completable());
+
+        let no_room = RoutePlanner::default().plan_with_carrier(&ferry, &car(), &route);
+        assert!(matches!(no_room, Err(RouteError::CannotBoard { .. })));
+    }
+
+    #[test]
+    fn test_long_route_runs_out_of_energy() {
+        let mut dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        dog.set_energy(EnergyLevel::Normal);
+        let route = Route::new("Trek".to_string())
+            .land(Terrain::Dirt, 14)
+            .land(Terrain::Dirt, 14)
+            .land(Terrain::Dirt, 14);
+
+        let plan = RoutePlanner::default().plan(&dog, &route).unwrap();
+        assert_eq!(plan.total_energy_levels, 3);
+        assert_eq!(plan.runs_out_at, Some(2));
+        assert!(!plan.is_completable());
+    }
+}
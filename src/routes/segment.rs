@@ -0,0 +1,113 @@
+use crate::behaviors::driving::RoadType;
+use crate::core::Terrain;
+use crate::core::temperature::{MILD_TEMPERATURE_C, temperature_at_altitude};
+use std::fmt;
+
+/// What a stretch of a journey consists of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentKind {
+    /// A road of the given type
+    Road(RoadType),
+    /// Open land without a road
+    Land(Terrain),
+    /// A water crossing; `depth` is how deep the route runs (0 = surface)
+    Water { depth: u32 },
+    /// An air corridor flown at the given altitude in meters
+    Air { altitude: u32 },
+}
+
+/// Surface temperature of open water in degrees Celsius
+const WATER_TEMPERATURE_C: i32 = 12;
+
+/// Temperature deep water settles at in degrees Celsius
+const DEEP_WATER_TEMPERATURE_C: i32 = 4;
+
+impl SegmentKind {
+    /// Typical temperature along this kind of segment in degrees Celsius
+    pub fn temperature_c(&self) -> i32 {
+        match self {
+            SegmentKind::Road(_) => MILD_TEMPERATURE_C,
+            SegmentKind::Land(terrain) => terrain.temperature_c(),
+            // Water cools with depth until it settles
+            SegmentKind::Water { depth } => {
+                (WATER_TEMPERATURE_C - (*depth / 50) as i32).max(DEEP_WATER_TEMPERATURE_C)
+            }
+            SegmentKind::Air { altitude } => temperature_at_altitude(MILD_TEMPERATURE_C, *altitude),
+        }
+    }
+}
+
+impl fmt::Display for SegmentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SegmentKind::Road(road_type) => write!(f, "{} road", road_type),
+            SegmentKind::Land(terrain) => write!(f, "{} terrain", terrain),
+            SegmentKind::Water { depth: 0 } => write!(f, "water"),
+            SegmentKind::Water { depth } => write!(f, "water at {}m depth", depth),
+            SegmentKind::Air { altitude } => write!(f, "air corridor at {}m", altitude),
+        }
+    }
+}
+
+/// One stretch of a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub kind: SegmentKind,
+    pub distance_km: u32,
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} km of {}", self.distance_km, self.kind)
+    }
+}
+
+/// A journey made of consecutive segments
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Route {
+    pub name: String,
+    pub segments: Vec<Segment>,
+}
+
+impl Route {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Add a segment of any kind
+    pub fn segment(mut self, kind: SegmentKind, distance_km: u32) -> Self {
+        self.segments.push(Segment { kind, distance_km });
+        self
+    }
+
+    /// Add a road segment
+    pub fn road(self, road_type: RoadType, distance_km: u32) -> Self {
+        self.segment(SegmentKind::Road(road_type), distance_km)
+    }
+
+    /// Add an off-road land segment
+    pub fn land(self, terrain: Terrain, distance_km: u32) -> Self {
+        self.segment(SegmentKind::Land(terrain), distance_km)
+    }
+
+    /// Add a water segment
+    pub fn water(self, depth: u32, distance_km: u32) -> Self {
+        self.segment(SegmentKind::Water { depth }, distance_km)
+    }
+
+    /// Add an air corridor
+    pub fn air(self, altitude: u32, distance_km: u32) -> Self {
+        self.segment(SegmentKind::Air { altitude }, distance_km)
+    }
+
+    /// Total length of the route in km
+    pub fn total_distance(&self) -> u32 {
+        self.segments
+            .iter()
+            .map(|segment| segment.distance_km)
+            .sum()
+    }
+}
@@ -1,9 +1,31 @@
-use crate::behaviors::{driving::Driving, flying::Flying, land_move::LandMove, moving::Moving};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::{
+    driving::{Driving, DrivingError, RoadType},
+    flying::{FlightCategory, Flying, FlyingError},
+    land_move::LandMove,
+    moving::Moving,
+    participant::Participant,
+    sensing::{Sensing, Sensor, SensorKind},
+};
+use crate::core::{EnergyLevel, HasEnergy, Mishap, SeededRng, ThermalRange, ThermalTolerance};
+use crate::crew::{Crewed, License, Operator, VehicleClass};
 use crate::vehicles::Vehicle;
+use crate::vehicles::carrier::{Carrier, PassengerDeck};
+use crate::vehicles::lifecycle::sealed::Transition;
+use crate::vehicles::lifecycle::{
+    EngineRunning, EngineState, Ignition, Lifecycle, Parked, Running,
+};
+use crate::vehicles::maintenance::{Condition, Maintainable, mechanical_reason};
+use crate::vehicles::payload::{CargoHold, Payload};
+use crate::world::infrastructure::{EnergySupply, Requirement, Site, UsesInfrastructure};
+
+/// Kilometers between scheduled services
+const SERVICE_INTERVAL_KM: u32 = 50_000;
+
+/// Distance of a typical flight, logged as crew duty and airframe wear
+const FLIGHT_LEG_KM: u32 = 400;
 
 #[derive(Debug)]
-pub struct Airplane {
+pub struct Airplane<S = EngineRunning> {
     name: String,
     manufacturer: String,
     year: u32,
@@ -12,6 +34,11 @@ pub struct Airplane {
     wingspan: u32, // in meters
     engine_type: AirplaneEngine,
     can_taxi: bool, // Can it drive on runways?
+    condition: Condition,
+    cargo_hold: CargoHold,
+    passenger_deck: PassengerDeck,
+    crew: Option<Box<dyn Operator>>,
+    ignition: Ignition<S>,
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +59,58 @@ pub enum AirplaneEngine {
     Electric { motors: u8, battery_capacity: u32 }, // battery in kWh
 }
 
+impl AirplaneEngine {
+    /// Temperatures the engine runs in without extra consumption
+    pub fn thermal_range(&self) -> ThermalRange {
+        match self {
+            AirplaneEngine::Jet { .. } | AirplaneEngine::Turboprop { .. } => {
+                ThermalRange::new(-60, 50)
+            }
+            // Thin, cold air at altitude starves piston engines
+            AirplaneEngine::Piston { .. } => ThermalRange::new(-10, 45),
+            AirplaneEngine::Electric { .. } => ThermalRange::new(0, 40),
+        }
+    }
+}
+
+impl AirplaneType {
+    /// Weight of the empty airplane in kg
+    pub fn empty_weight_kg(&self) -> u32 {
+        match self {
+            AirplaneType::Commercial => 45_000,
+            AirplaneType::Military => 12_000,
+            AirplaneType::Private => 800,
+            AirplaneType::Cargo => 130_000,
+            AirplaneType::Aerobatic => 600,
+            AirplaneType::Seaplane => 1_000,
+        }
+    }
+
+    /// Passengers and cargo the airplane can take
+    pub fn payload_capacity_kg(&self) -> u32 {
+        match self {
+            AirplaneType::Commercial => 20_000, // Passengers and luggage
+            AirplaneType::Military => 8_000,
+            AirplaneType::Private => 400,
+            AirplaneType::Cargo => 100_000, // Freight hauler
+            AirplaneType::Aerobatic => 100, // Pilot only
+            AirplaneType::Seaplane => 600,
+        }
+    }
+
+    /// Ground run in meters before lifting off, not counting the wingspan
+    pub fn takeoff_run_m(&self) -> u32 {
+        match self {
+            AirplaneType::Commercial => 1800,
+            AirplaneType::Military => 1200,
+            AirplaneType::Private => 400,
+            AirplaneType::Cargo => 2400, // Heavy and slow to lift
+            AirplaneType::Aerobatic => 300,
+            AirplaneType::Seaplane => 400, // On water
+        }
+    }
+}
+
 impl Airplane {
     pub fn new(
         name: String,
@@ -44,6 +123,9 @@ impl Airplane {
         // Most airplanes can taxi on runways (drive slowly on ground)
         let can_taxi = true;
 
+        let condition = Condition::new(SERVICE_INTERVAL_KM, SeededRng::from_label(&name));
+        let cargo_hold = CargoHold::new(airplane_type.payload_capacity_kg());
+
         Self {
             name,
             manufacturer,
@@ -53,9 +135,38 @@ impl Airplane {
             wingspan,
             engine_type,
             can_taxi,
+            condition,
+            cargo_hold,
+            passenger_deck: PassengerDeck::new(),
+            crew: None,
+            ignition: Ignition::new(),
         }
     }
+}
+
+impl Airplane<Parked> {
+    /// Create an airplane with its engine off
+    pub fn parked(
+        name: String,
+        manufacturer: String,
+        year: u32,
+        airplane_type: AirplaneType,
+        wingspan: u32,
+        engine_type: AirplaneEngine,
+    ) -> Self {
+        Airplane::new(
+            name,
+            manufacturer,
+            year,
+            airplane_type,
+            wingspan,
+            engine_type,
+        )
+        .shut_down()
+    }
+}
 
+impl<S: EngineState> Airplane<S> {
     pub fn airplane_type(&self) -> &AirplaneType {
         &self.airplane_type
     }
@@ -72,13 +183,57 @@ impl Airplane {
         self.can_taxi
     }
 
-    /// Refuel the airplane
-    pub fn refuel(&mut self) {
-        self.energy = EnergyLevel::Hyperactive;
+    /// Runway length in meters needed to take off and land - bigger wings need a longer one
+    pub fn required_runway_m(&self) -> u32 {
+        self.airplane_type.takeoff_run_m() + self.wingspan * 10
+    }
+
+    /// Check if the airplane can taxi at a site - it needs a runway, or water for a seaplane
+    pub fn can_taxi_at(&self, site: &Site) -> bool {
+        let surface = match self.airplane_type {
+            AirplaneType::Seaplane => site.deepest_berth_m().is_some(),
+            _ => site.longest_runway_m().is_some(),
+        };
+        self.can_taxi && surface
     }
 }
 
-impl Vehicle for Airplane {
+impl<S: EngineState> Transition for Airplane<S> {
+    type State = S;
+    type With<T: EngineState> = Airplane<T>;
+
+    fn ignition(&self) -> &Ignition<S> {
+        &self.ignition
+    }
+
+    fn ignition_mut(&mut self) -> &mut Ignition<S> {
+        &mut self.ignition
+    }
+
+    fn into_state<T: EngineState>(self) -> Airplane<T> {
+        Airplane {
+            name: self.name,
+            manufacturer: self.manufacturer,
+            year: self.year,
+            energy: self.energy,
+            airplane_type: self.airplane_type,
+            wingspan: self.wingspan,
+            engine_type: self.engine_type,
+            can_taxi: self.can_taxi,
+            condition: self.condition,
+            cargo_hold: self.cargo_hold,
+            passenger_deck: self.passenger_deck,
+            crew: self.crew,
+            ignition: self.ignition.transition(),
+        }
+    }
+}
+
+impl<S: EngineState> Lifecycle for Airplane<S> {
+    const IDLE_MINUTES_PER_LEVEL: u32 = 10;
+}
+
+impl<S: EngineState> Vehicle for Airplane<S> {
     fn name(&self) -> String {
         self.name.clone()
     }
@@ -128,7 +283,7 @@ impl Vehicle for Airplane {
     }
 }
 
-impl HasEnergy for Airplane {
+impl<S: EngineState> HasEnergy for Airplane<S> {
     fn energy(&self) -> EnergyLevel {
         self.energy
     }
@@ -138,10 +293,209 @@ impl HasEnergy for Airplane {
     }
 }
 
-impl Moving for Airplane {}
-impl LandMove for Airplane {}
+impl<S: EngineState> ThermalTolerance for Airplane<S> {
+    fn thermal_range(&self) -> ThermalRange {
+        self.engine_type.thermal_range()
+    }
+}
+
+impl<S: EngineState> Maintainable for Airplane<S> {
+    fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    fn condition_mut(&mut self) -> &mut Condition {
+        &mut self.condition
+    }
+
+    fn service_cost_per_point(&self) -> u32 {
+        80
+    }
+}
+
+impl<S: EngineState> Payload for Airplane<S> {
+    fn cargo_hold(&self) -> &CargoHold {
+        &self.cargo_hold
+    }
+
+    fn cargo_hold_mut(&mut self) -> &mut CargoHold {
+        &mut self.cargo_hold
+    }
+
+    fn empty_weight_kg(&self) -> u32 {
+        self.airplane_type.empty_weight_kg()
+    }
+}
+
+impl<S: EngineState> Carrier for Airplane<S> {
+    fn passenger_deck(&self) -> &PassengerDeck {
+        &self.passenger_deck
+    }
+
+    fn passenger_deck_mut(&mut self) -> &mut PassengerDeck {
+        &mut self.passenger_deck
+    }
+}
+
+impl<S: EngineState> Crewed for Airplane<S> {
+    fn vehicle_class(&self) -> VehicleClass {
+        VehicleClass::Airplane
+    }
+
+    fn required_license(&self) -> License {
+        match self.airplane_type {
+            AirplaneType::Private | AirplaneType::Aerobatic | AirplaneType::Seaplane => {
+                License::PrivatePilot
+            }
+            _ => License::CommercialPilot,
+        }
+    }
+
+    fn crew_seat(&self) -> &Option<Box<dyn Operator>> {
+        &self.crew
+    }
+
+    fn crew_seat_mut(&mut self) -> &mut Option<Box<dyn Operator>> {
+        &mut self.crew
+    }
+}
+
+impl<S: EngineState> UsesInfrastructure for Airplane<S> {
+    fn requirement(&self) -> Requirement {
+        match self.airplane_type {
+            // Floats draw next to nothing
+            AirplaneType::Seaplane => Requirement::Berth { draft_m: 1 },
+            _ => Requirement::Runway {
+                length_m: self.required_runway_m(),
+            },
+        }
+    }
+
+    fn energy_supplies(&self) -> Vec<EnergySupply> {
+        match self.engine_type {
+            AirplaneEngine::Electric { .. } => vec![EnergySupply::Charging],
+            _ => vec![EnergySupply::Fuel],
+        }
+    }
+}
+
+/// A parked airplane cannot move on its own, but it can still be carried
+impl Participant for Airplane<Parked> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+}
+
+impl<S: Running> Participant for Airplane<S> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+
+    fn as_carrier(&self) -> Option<&dyn Carrier> {
+        Some(self)
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_driver(&self) -> Option<&dyn Driving> {
+        Some(self)
+    }
+
+    fn as_flyer(&self) -> Option<&dyn Flying> {
+        Some(self)
+    }
+
+    fn as_sensor(&self) -> Option<&dyn Sensing> {
+        Some(self)
+    }
+}
+
+/// Radar needs the engines running to power it
+impl<S: Running> Sensing for Airplane<S> {
+    fn sensors(&self) -> Vec<Sensor> {
+        let range_m = match self.airplane_type {
+            AirplaneType::Military => 300_000,
+            AirplaneType::Commercial | AirplaneType::Cargo => 150_000,
+            // Light aircraft carry a weather radar at most
+            AirplaneType::Private | AirplaneType::Aerobatic | AirplaneType::Seaplane => 50_000,
+        };
+        vec![Sensor::new(SensorKind::Radar, range_m)]
+    }
+}
+
+impl<S: Running> Moving for Airplane<S> {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.take_damage(mishap.severity());
+    }
+}
+impl<S: Running> LandMove for Airplane<S> {}
+
+impl<S: Running> Flying for Airplane<S> {
+    fn check_flying_conditions(&self) -> Result<(), FlyingError> {
+        self.check_condition()
+            .map_err(|error| FlyingError::MechanicalFailure {
+                reason: mechanical_reason(error),
+            })?;
+        self.check_crew().map_err(|error| FlyingError::CrewNotFit {
+            reason: error.to_string(),
+        })
+    }
+
+    fn log_flight(&mut self) -> Result<(), FlyingError> {
+        self.log_crew_duty(FLIGHT_LEG_KM);
+        let speed_kmh = self.flying_speed();
+        self.record_wear(FLIGHT_LEG_KM, speed_kmh, 1)
+            .map_err(|error| FlyingError::MechanicalFailure {
+                reason: mechanical_reason(error),
+            })
+    }
+
+    fn flight_category(&self) -> FlightCategory {
+        match self.airplane_type {
+            AirplaneType::Commercial | AirplaneType::Cargo | AirplaneType::Military => {
+                FlightCategory::Airliner
+            }
+            AirplaneType::Private | AirplaneType::Aerobatic | AirplaneType::Seaplane => {
+                FlightCategory::LightAircraft
+            }
+        }
+    }
+
+    fn flying_speed(&self) -> u32 {
+        let cruising_speed = match self.airplane_type {
+            AirplaneType::Commercial => 850,
+            AirplaneType::Military => 1500,
+            AirplaneType::Private => 250,
+            AirplaneType::Cargo => 800,
+            AirplaneType::Aerobatic => 300,
+            AirplaneType::Seaplane => 250,
+        };
+
+        self.crew_limited(self.cargo_hold.loaded_speed(cruising_speed))
+    }
 
-impl Flying for Airplane {
     fn max_altitude(&self) -> u32 {
         let base_altitude = match self.airplane_type {
             AirplaneType::Commercial => 12000, // Cruise altitude
@@ -169,30 +523,157 @@ impl Flying for Airplane {
             AirplaneEngine::Electric { motors, .. } => (*motors as u32) * 200, // Limited by battery
         };
 
-        base_altitude + engine_bonus
+        self.crew_limited(
+            self.cargo_hold
+                .loaded_altitude(base_altitude + engine_bonus),
+        )
     }
 }
 
-impl Driving for Airplane {
+impl<S: Running> Driving for Airplane<S> {
+    fn check_driving_conditions(&self) -> Result<(), DrivingError> {
+        self.check_condition()?;
+        self.check_crew().map_err(|error| DrivingError::CrewNotFit {
+            reason: error.to_string(),
+        })
+    }
+
+    fn apply_wear(
+        &mut self,
+        distance_km: u32,
+        speed_kmh: u32,
+        road_type: RoadType,
+    ) -> Result<(), DrivingError> {
+        self.log_crew_duty(distance_km);
+        self.record_wear(distance_km, speed_kmh, road_type.difficulty_level())
+    }
+
+    fn driving_skill(&self) -> u8 {
+        self.crew_skill().unwrap_or(3) // Uncrewed: moderate skill
+    }
+
     fn max_speed(&self) -> u32 {
         // Taxi speed on ground - much slower than flight speed
-        match self.airplane_type {
+        let taxi_speed = match self.airplane_type {
             AirplaneType::Commercial => 30, // Large planes taxi slowly
             AirplaneType::Military => 40,   // Military can taxi faster
             AirplaneType::Private => 25,    // Small planes
             AirplaneType::Cargo => 25,      // Heavy and slow
             AirplaneType::Aerobatic => 35,  // Agile on ground too
             AirplaneType::Seaplane => 20,   // Careful on water/land
-        }
+        };
+
+        self.crew_limited(self.cargo_hold.loaded_speed(taxi_speed))
     }
 
     fn fuel_efficiency(&self) -> u32 {
         // Very low efficiency when driving/taxiing (engines not optimized for ground)
-        match &self.engine_type {
+        let efficiency = match &self.engine_type {
             AirplaneEngine::Jet { .. } => 5, // Jets are terrible for ground
             AirplaneEngine::Turboprop { .. } => 8, // Slightly better
             AirplaneEngine::Piston { .. } => 12, // Better for low speed
             AirplaneEngine::Electric { .. } => 20, // Most efficient
+        };
+
+        self.cargo_hold.loaded_efficiency(efficiency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crew::{CrewError, CrewMember};
+
+    fn airliner() -> Airplane {
+        Airplane::new(
+            "Test".to_string(),
+            "Test Aero".to_string(),
+            2022,
+            AirplaneType::Commercial,
+            35,
+            AirplaneEngine::Jet {
+                engines: 2,
+                thrust_each: 120,
+            },
+        )
+    }
+
+    fn captain() -> CrewMember {
+        CrewMember::new("Sam".to_string())
+            .with_license(License::CommercialPilot)
+            .with_skill(VehicleClass::Airplane, 5)
+    }
+
+    #[test]
+    fn test_wrecked_airplane_cannot_fly() {
+        let mut airplane = airliner();
+        airplane.set_energy(EnergyLevel::Hyperactive);
+        airplane.condition_mut().set_percent(0);
+
+        let result = airplane.fly();
+        assert!(matches!(result, Err(FlyingError::MechanicalFailure { .. })));
+        assert_eq!(airplane.energy(), EnergyLevel::Hyperactive);
+    }
+
+    #[test]
+    fn test_tired_pilot_cannot_fly_to_altitude() {
+        let mut airplane = airliner();
+        airplane.set_energy(EnergyLevel::Hyperactive);
+
+        let mut pilot = captain();
+        pilot.set_energy(EnergyLevel::Tired);
+        airplane.assign_operator(Box::new(pilot)).unwrap();
+
+        let result = airplane.fly_to_altitude(10000);
+        assert!(matches!(result, Err(FlyingError::CrewNotFit { .. })));
+        assert_eq!(airplane.energy(), EnergyLevel::Hyperactive); // Tank still full
+
+        airplane.unassign_operator();
+        assert!(airplane.fly_to_altitude(10000).is_ok()); // Uncrewed works as before
+    }
+
+    #[test]
+    fn test_flights_tire_the_pilot() {
+        let mut airplane = airliner();
+        airplane.assign_operator(Box::new(captain())).unwrap();
+
+        for _ in 0..2 {
+            airplane.set_energy(EnergyLevel::Hyperactive);
+            airplane.fly().unwrap();
         }
+        assert_eq!(airplane.operator().unwrap().energy(), EnergyLevel::Normal);
+    }
+
+    #[test]
+    fn test_pilot_needs_matching_license() {
+        let mut airplane = airliner();
+        let private_pilot = CrewMember::new("Kim".to_string())
+            .with_license(License::PrivatePilot)
+            .with_skill(VehicleClass::Airplane, 3);
+
+        let error = airplane
+            .assign_operator(Box::new(private_pilot))
+            .unwrap_err();
+        assert_eq!(
+            error.reason,
+            CrewError::NotLicensed {
+                operator: "Kim".to_string(),
+                required: License::CommercialPilot,
+            }
+        );
+        assert_eq!(error.operator.name(), "Kim"); // Pilot is handed back
+    }
+
+    #[test]
+    fn test_skill_limits_altitude() {
+        let mut airplane = airliner();
+        let ceiling = airplane.max_altitude();
+
+        let novice = CrewMember::new("Kim".to_string())
+            .with_license(License::CommercialPilot)
+            .with_skill(VehicleClass::Airplane, 2);
+        airplane.assign_operator(Box::new(novice)).unwrap();
+
+        assert_eq!(airplane.max_altitude(), ceiling * 70 / 100);
     }
 }
@@ -1,9 +1,30 @@
-use crate::behaviors::{driving::Driving, land_move::LandMove, moving::Moving, swimming::Swimming};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::driving::{Driving, DrivingError, RoadType};
+use crate::behaviors::swimming::{Swimming, SwimmingError};
+use crate::behaviors::{
+    land_move::LandMove,
+    moving::{Medium, Moving, MovingError},
+    participant::{Participant, TravelMode},
+};
+use crate::core::{
+    AirSupply, DiveState, EnergyLevel, HasEnergy, Mishap, SeededRng, ThermalRange, ThermalTolerance,
+};
 use crate::vehicles::Vehicle;
+use crate::vehicles::carrier::{Carrier, PassengerDeck};
+use crate::vehicles::lifecycle::sealed::Transition;
+use crate::vehicles::lifecycle::{
+    EngineRunning, EngineState, Ignition, Lifecycle, Parked, Running,
+};
+use crate::vehicles::maintenance::{Condition, Maintainable};
+use crate::vehicles::payload::{CargoHold, Payload};
+use crate::world::infrastructure::{EnergySupply, Requirement, UsesInfrastructure};
+use std::fmt;
+use thiserror::Error;
+
+/// Kilometers between scheduled services
+const SERVICE_INTERVAL_KM: u32 = 5_000;
 
 #[derive(Debug)]
-pub struct AmphibiousVehicle {
+pub struct AmphibiousVehicle<S = EngineRunning> {
     name: String,
     manufacturer: String,
     year: u32,
@@ -11,6 +32,70 @@ pub struct AmphibiousVehicle {
     amphibious_type: AmphibiousType,
     propulsion: AmphibiousPropulsion,
     hull_type: HullType,
+    dive_state: DiveState,
+    mode: AmphibiousMode,
+    condition: Condition,
+    cargo_hold: CargoHold,
+    passenger_deck: PassengerDeck,
+    ignition: Ignition<S>,
+}
+
+/// Which medium an amphibious vehicle is currently configured for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AmphibiousMode {
+    Land,  // Wheels or tracks down, propulsion for roads
+    Water, // Hull sealed, propeller or water jet engaged
+}
+
+impl AmphibiousMode {
+    /// Get the display name for this mode
+    pub fn name(&self) -> &'static str {
+        match self {
+            AmphibiousMode::Land => "land",
+            AmphibiousMode::Water => "water",
+        }
+    }
+
+    /// Mode needed for a travel mode, if the vehicle supports it
+    pub fn for_travel(mode: TravelMode) -> Option<AmphibiousMode> {
+        match mode {
+            TravelMode::Drive => Some(AmphibiousMode::Land),
+            TravelMode::Swim => Some(AmphibiousMode::Water),
+            TravelMode::Walk | TravelMode::Fly => None,
+        }
+    }
+
+    /// Get the opposite mode
+    pub fn other(&self) -> Self {
+        match self {
+            AmphibiousMode::Land => AmphibiousMode::Water,
+            AmphibiousMode::Water => AmphibiousMode::Land,
+        }
+    }
+}
+
+impl fmt::Display for AmphibiousMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ModeTransitionError {
+    #[error("Already in {0} mode")]
+    AlreadyInMode(AmphibiousMode),
+
+    #[error(
+        "Insufficient energy to switch to {target} mode: need at least {required}, have {current}"
+    )]
+    InsufficientEnergy {
+        target: AmphibiousMode,
+        required: EnergyLevel,
+        current: EnergyLevel,
+    },
+
+    #[error("Cannot switch to {target} mode while submerged at {depth}m")]
+    Submerged { target: AmphibiousMode, depth: u32 },
 }
 
 #[derive(Debug, Clone)]
@@ -23,6 +108,49 @@ pub enum AmphibiousType {
     EmergencyVehicle, // Rescue operations
 }
 
+impl AmphibiousType {
+    /// Energy levels consumed when switching between land and water mode
+    pub fn transition_energy(&self) -> u8 {
+        match self {
+            AmphibiousType::Duck => 1,             // Simple transition
+            AmphibiousType::Hovercraft => 0,       // No transition needed
+            AmphibiousType::AmphibiousCar => 1,    // Simple
+            AmphibiousType::LandingCraft => 1,     // Military efficiency
+            AmphibiousType::AmphibiousRV => 2,     // More complex
+            AmphibiousType::EmergencyVehicle => 1, // Quick response needed
+        }
+    }
+
+    /// Check if this type has to be reconfigured to change medium
+    pub fn needs_transition(&self) -> bool {
+        !matches!(self, AmphibiousType::Hovercraft) // Air cushion works on any surface
+    }
+
+    /// Weight of the empty vehicle in kg
+    pub fn empty_weight_kg(&self) -> u32 {
+        match self {
+            AmphibiousType::Duck => 6_500,
+            AmphibiousType::Hovercraft => 3_000,
+            AmphibiousType::AmphibiousCar => 1_500,
+            AmphibiousType::LandingCraft => 25_000,
+            AmphibiousType::AmphibiousRV => 4_000,
+            AmphibiousType::EmergencyVehicle => 3_500,
+        }
+    }
+
+    /// Passengers and cargo the vehicle can take
+    pub fn payload_capacity_kg(&self) -> u32 {
+        match self {
+            AmphibiousType::Duck => 2_500, // Built to haul troops and supplies
+            AmphibiousType::Hovercraft => 1_500, // Weight-sensitive air cushion
+            AmphibiousType::AmphibiousCar => 400, // Car-sized
+            AmphibiousType::LandingCraft => 10_000, // Vehicles and troops
+            AmphibiousType::AmphibiousRV => 1_200, // Family and camping gear
+            AmphibiousType::EmergencyVehicle => 1_000, // Crew, patients and equipment
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum AmphibiousPropulsion {
     WheelsAndPropeller {
@@ -61,6 +189,9 @@ impl AmphibiousVehicle {
         propulsion: AmphibiousPropulsion,
         hull_type: HullType,
     ) -> Self {
+        let condition = Condition::new(SERVICE_INTERVAL_KM, SeededRng::from_label(&name));
+        let cargo_hold = CargoHold::new(amphibious_type.payload_capacity_kg());
+
         Self {
             name,
             manufacturer,
@@ -69,9 +200,39 @@ impl AmphibiousVehicle {
             amphibious_type,
             propulsion,
             hull_type,
+            dive_state: DiveState::at_surface(),
+            mode: AmphibiousMode::Land,
+            condition,
+            cargo_hold,
+            passenger_deck: PassengerDeck::new(),
+            ignition: Ignition::new(),
         }
     }
+}
 
+impl AmphibiousVehicle<Parked> {
+    /// Create a vehicle with its engine off
+    pub fn parked(
+        name: String,
+        manufacturer: String,
+        year: u32,
+        amphibious_type: AmphibiousType,
+        propulsion: AmphibiousPropulsion,
+        hull_type: HullType,
+    ) -> Self {
+        AmphibiousVehicle::new(
+            name,
+            manufacturer,
+            year,
+            amphibious_type,
+            propulsion,
+            hull_type,
+        )
+        .shut_down()
+    }
+}
+
+impl<S: EngineState> AmphibiousVehicle<S> {
     pub fn amphibious_type(&self) -> &AmphibiousType {
         &self.amphibious_type
     }
@@ -84,36 +245,93 @@ impl AmphibiousVehicle {
         &self.hull_type
     }
 
+    /// Get the mode the vehicle is currently configured for
+    pub fn mode(&self) -> AmphibiousMode {
+        self.mode
+    }
+
+    /// Start out configured for `mode`, without paying for a transition
+    pub fn in_mode(mut self, mode: AmphibiousMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Check if the vehicle can operate in `mode` without switching first
+    pub fn is_configured_for(&self, mode: AmphibiousMode) -> bool {
+        !self.amphibious_type.needs_transition() || self.mode == mode
+    }
+
     /// Switch between land and water mode
-    pub fn switch_mode(&mut self, to_water: bool) -> Result<String, String> {
-        let mode = if to_water { "water" } else { "land" };
+    pub fn switch_mode(&mut self, target: AmphibiousMode) -> Result<String, ModeTransitionError> {
+        if self.mode == target {
+            return Err(ModeTransitionError::AlreadyInMode(target));
+        }
 
-        // Different amphibious types have different transition capabilities
-        let transition_energy = match self.amphibious_type {
-            AmphibiousType::Duck => 1,             // Simple transition
-            AmphibiousType::Hovercraft => 0,       // No transition needed
-            AmphibiousType::AmphibiousCar => 1,    // Simple
-            AmphibiousType::LandingCraft => 1,     // Military efficiency
-            AmphibiousType::AmphibiousRV => 2,     // More complex
-            AmphibiousType::EmergencyVehicle => 1, // Quick response needed
-        };
+        // Hovercraft ride on an air cushion over both land and water
+        if !self.amphibious_type.needs_transition() {
+            self.mode = target;
+            return Ok(format!("Glides into {} mode without transition", target));
+        }
+
+        if self.dive_state.is_submerged() {
+            return Err(ModeTransitionError::Submerged {
+                target,
+                depth: self.dive_state.depth,
+            });
+        }
 
-        if self.energy() < EnergyLevel::Tired {
-            return Err(format!("Insufficient energy to switch to {} mode", mode));
+        let required = EnergyLevel::Tired;
+        if self.energy() < required {
+            return Err(ModeTransitionError::InsufficientEnergy {
+                target,
+                required,
+                current: self.energy(),
+            });
         }
 
-        self.consume_energy_levels(transition_energy);
+        self.consume_energy_levels(self.amphibious_type.transition_energy());
+        self.mode = target;
+
+        Ok(format!("Successfully switched to {} mode", target))
+    }
+}
+
+impl<S: EngineState> Transition for AmphibiousVehicle<S> {
+    type State = S;
+    type With<T: EngineState> = AmphibiousVehicle<T>;
+
+    fn ignition(&self) -> &Ignition<S> {
+        &self.ignition
+    }
 
-        Ok(format!("Successfully switched to {} mode", mode))
+    fn ignition_mut(&mut self) -> &mut Ignition<S> {
+        &mut self.ignition
     }
 
-    /// Refuel the vehicle
-    pub fn refuel(&mut self) {
-        self.energy = EnergyLevel::Hyperactive;
+    fn into_state<T: EngineState>(self) -> AmphibiousVehicle<T> {
+        AmphibiousVehicle {
+            name: self.name,
+            manufacturer: self.manufacturer,
+            year: self.year,
+            energy: self.energy,
+            amphibious_type: self.amphibious_type,
+            propulsion: self.propulsion,
+            hull_type: self.hull_type,
+            dive_state: self.dive_state,
+            mode: self.mode,
+            condition: self.condition,
+            cargo_hold: self.cargo_hold,
+            passenger_deck: self.passenger_deck,
+            ignition: self.ignition.transition(),
+        }
     }
 }
 
-impl Vehicle for AmphibiousVehicle {
+impl<S: EngineState> Lifecycle for AmphibiousVehicle<S> {
+    const IDLE_MINUTES_PER_LEVEL: u32 = 30;
+}
+
+impl<S: EngineState> Vehicle for AmphibiousVehicle<S> {
     fn name(&self) -> String {
         self.name.clone()
     }
@@ -144,7 +362,7 @@ impl Vehicle for AmphibiousVehicle {
     }
 }
 
-impl HasEnergy for AmphibiousVehicle {
+impl<S: EngineState> HasEnergy for AmphibiousVehicle<S> {
     fn energy(&self) -> EnergyLevel {
         self.energy
     }
@@ -154,10 +372,166 @@ impl HasEnergy for AmphibiousVehicle {
     }
 }
 
-impl Moving for AmphibiousVehicle {}
-impl LandMove for AmphibiousVehicle {}
+impl<S: EngineState> ThermalTolerance for AmphibiousVehicle<S> {
+    fn thermal_range(&self) -> ThermalRange {
+        ThermalRange::new(-20, 45)
+    }
+}
+
+impl<S: EngineState> Maintainable for AmphibiousVehicle<S> {
+    fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    fn condition_mut(&mut self) -> &mut Condition {
+        &mut self.condition
+    }
+
+    fn service_cost_per_point(&self) -> u32 {
+        25
+    }
+}
+
+impl<S: EngineState> Payload for AmphibiousVehicle<S> {
+    fn cargo_hold(&self) -> &CargoHold {
+        &self.cargo_hold
+    }
+
+    fn cargo_hold_mut(&mut self) -> &mut CargoHold {
+        &mut self.cargo_hold
+    }
+
+    fn empty_weight_kg(&self) -> u32 {
+        self.amphibious_type.empty_weight_kg()
+    }
+}
+
+impl<S: EngineState> Carrier for AmphibiousVehicle<S> {
+    fn passenger_deck(&self) -> &PassengerDeck {
+        &self.passenger_deck
+    }
+
+    fn passenger_deck_mut(&mut self) -> &mut PassengerDeck {
+        &mut self.passenger_deck
+    }
+}
+
+/// Amphibious vehicles drive straight up the beach, no port needed
+impl<S: EngineState> UsesInfrastructure for AmphibiousVehicle<S> {
+    fn requirement(&self) -> Requirement {
+        Requirement::Nothing
+    }
+
+    fn energy_supplies(&self) -> Vec<EnergySupply> {
+        vec![EnergySupply::Fuel]
+    }
+}
+
+/// A parked amphibious vehicle cannot move on its own, but it can still be carried
+impl Participant for AmphibiousVehicle<Parked> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+}
+
+impl<S: Running> Participant for AmphibiousVehicle<S> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+
+    fn as_carrier(&self) -> Option<&dyn Carrier> {
+        Some(self)
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_driver(&self) -> Option<&dyn Driving> {
+        Some(self)
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        Some(self)
+    }
+
+    fn mode_switch_energy(&self, from: Option<TravelMode>, to: TravelMode) -> u32 {
+        let current = match from {
+            Some(from) => AmphibiousMode::for_travel(from),
+            None => Some(self.mode),
+        };
+        match (current, AmphibiousMode::for_travel(to)) {
+            (Some(current), Some(target))
+                if current != target && self.amphibious_type.needs_transition() =>
+            {
+                self.amphibious_type.transition_energy() as u32
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl<S: Running> Moving for AmphibiousVehicle<S> {
+    fn check_medium(&self, medium: Medium) -> Result<(), MovingError> {
+        let required = match medium {
+            Medium::Land => AmphibiousMode::Land,
+            Medium::Water => AmphibiousMode::Water,
+            Medium::Air => return Ok(()),
+        };
+        if !self.is_configured_for(required) {
+            return Err(MovingError::WrongMode {
+                mode: self.mode.name(),
+                medium,
+            });
+        }
+        Ok(())
+    }
+
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.take_damage(mishap.severity());
+    }
+}
+impl<S: Running> LandMove for AmphibiousVehicle<S> {}
+
+impl<S: Running> Driving for AmphibiousVehicle<S> {
+    fn check_driving_conditions(&self) -> Result<(), DrivingError> {
+        if !self.is_configured_for(AmphibiousMode::Land) {
+            return Err(DrivingError::WrongMode {
+                mode: self.mode.name(),
+            });
+        }
+        self.check_condition()
+    }
+
+    fn apply_wear(
+        &mut self,
+        distance_km: u32,
+        speed_kmh: u32,
+        road_type: RoadType,
+    ) -> Result<(), DrivingError> {
+        self.record_wear(distance_km, speed_kmh, road_type.difficulty_level())
+    }
 
-impl Driving for AmphibiousVehicle {
     fn max_speed(&self) -> u32 {
         let base_speed = match self.amphibious_type {
             AmphibiousType::Duck => 80,              // Military utility
@@ -184,7 +558,8 @@ impl Driving for AmphibiousVehicle {
             AmphibiousPropulsion::HybridSystem { .. } => 15,  // Optimized for both
         };
 
-        base_speed + propulsion_modifier
+        self.cargo_hold
+            .loaded_speed(base_speed + propulsion_modifier)
     }
 
     fn fuel_efficiency(&self) -> u32 {
@@ -206,11 +581,12 @@ impl Driving for AmphibiousVehicle {
             HullType::Sealed => 1u32,       // Minimal penalty
         };
 
-        base_efficiency.saturating_sub(hull_penalty)
+        self.cargo_hold
+            .loaded_efficiency(base_efficiency.saturating_sub(hull_penalty))
     }
 }
 
-impl Swimming for AmphibiousVehicle {
+impl<S: Running> Swimming for AmphibiousVehicle<S> {
     fn max_depth(&self) -> u32 {
         match self.amphibious_type {
             AmphibiousType::Duck => 2,             // Shallow water operations
@@ -221,4 +597,151 @@ impl Swimming for AmphibiousVehicle {
             AmphibiousType::EmergencyVehicle => 2, // Flood rescue operations
         }
     }
+
+    fn swimming_speed(&self) -> u32 {
+        let water_speed = match self.hull_type {
+            HullType::PlanningHull => 40, // Built to plane on the surface
+            HullType::AirCushion => 50,   // Hovercraft glide over water
+            HullType::Catamaran => 20,
+            HullType::BoatHull => 12,
+            HullType::Sealed => 8, // Car body pushed through water
+        };
+
+        self.cargo_hold.loaded_speed(water_speed)
+    }
+
+    fn dive_state(&self) -> DiveState {
+        self.dive_state
+    }
+
+    fn set_dive_state(&mut self, state: DiveState) {
+        self.dive_state = state;
+    }
+
+    fn air_supply(&self) -> AirSupply {
+        AirSupply::Surface // Air intake stays above the waterline
+    }
+
+    fn check_swimming_conditions(&self) -> Result<(), SwimmingError> {
+        if !self.is_configured_for(AmphibiousMode::Water) {
+            return Err(SwimmingError::WrongMode {
+                mode: self.mode.name(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::behaviors::land_move::LandMoveError;
+
+    fn vehicle(amphibious_type: AmphibiousType) -> AmphibiousVehicle {
+        AmphibiousVehicle::new(
+            "Test".to_string(),
+            "Test Marine".to_string(),
+            2022,
+            amphibious_type,
+            AmphibiousPropulsion::WheelsAndPropeller {
+                wheels: 4,
+                propeller_power: 100,
+            },
+            HullType::Sealed,
+        )
+    }
+
+    #[test]
+    fn test_starts_in_land_mode() {
+        let mut car = vehicle(AmphibiousType::AmphibiousCar);
+        assert_eq!(car.mode(), AmphibiousMode::Land);
+
+        assert!(car.drive().is_ok());
+        assert!(matches!(
+            car.swim(),
+            Err(SwimmingError::WrongMode { mode: "land" })
+        ));
+    }
+
+    #[test]
+    fn test_switch_to_water_mode() {
+        let mut car = vehicle(AmphibiousType::AmphibiousCar);
+        car.set_energy(EnergyLevel::Hyperactive);
+
+        assert!(car.switch_mode(AmphibiousMode::Water).is_ok());
+        assert_eq!(car.mode(), AmphibiousMode::Water);
+        assert_eq!(car.energy(), EnergyLevel::Energetic); // Transition cost 1
+
+        assert!(car.swim().is_ok());
+        assert!(matches!(
+            car.drive(),
+            Err(DrivingError::WrongMode { mode: "water" })
+        ));
+    }
+
+    #[test]
+    fn test_every_movement_respects_the_mode() {
+        let mut car = vehicle(AmphibiousType::AmphibiousCar);
+        car.set_energy(EnergyLevel::Hyperactive);
+
+        // On land the vehicle cannot hold or leave a depth it never reached
+        assert!(matches!(
+            car.stay_submerged(10),
+            Err(SwimmingError::MovementError(MovingError::WrongMode {
+                mode: "land",
+                medium: Medium::Water,
+            }))
+        ));
+        assert!(car.ascend(1, 1).is_err());
+
+        car.switch_mode(AmphibiousMode::Water).unwrap();
+        let energy = car.energy();
+        assert!(matches!(
+            car.land_move(),
+            Err(LandMoveError::MovementError(MovingError::WrongMode {
+                mode: "water",
+                medium: Medium::Land,
+            }))
+        ));
+        assert_eq!(car.energy(), energy); // Refused before spending energy
+        assert!(car.stay_submerged(10).is_ok());
+
+        // Plain movement does not name a medium, so any mode will do
+        assert!(car.do_move_in(Medium::Land).is_err());
+        assert!(car.do_move_in(Medium::Water).is_ok());
+        assert!(car.do_move().is_ok());
+    }
+
+    #[test]
+    fn test_transition_errors() {
+        let mut rv = vehicle(AmphibiousType::AmphibiousRV);
+        assert_eq!(
+            rv.switch_mode(AmphibiousMode::Land),
+            Err(ModeTransitionError::AlreadyInMode(AmphibiousMode::Land))
+        );
+
+        rv.set_energy(EnergyLevel::Exhausted);
+        assert_eq!(
+            rv.switch_mode(AmphibiousMode::Water),
+            Err(ModeTransitionError::InsufficientEnergy {
+                target: AmphibiousMode::Water,
+                required: EnergyLevel::Tired,
+                current: EnergyLevel::Exhausted,
+            })
+        );
+        assert_eq!(rv.mode(), AmphibiousMode::Land);
+    }
+
+    #[test]
+    fn test_hovercraft_needs_no_transition() {
+        let mut hovercraft = vehicle(AmphibiousType::Hovercraft);
+        hovercraft.set_energy(EnergyLevel::Exhausted);
+
+        assert!(hovercraft.switch_mode(AmphibiousMode::Water).is_ok());
+        assert_eq!(hovercraft.energy(), EnergyLevel::Exhausted);
+
+        // Hovercraft can still drive while in water mode
+        hovercraft.set_energy(EnergyLevel::Hyperactive);
+        assert!(hovercraft.drive().is_ok());
+    }
 }
@@ -1,15 +1,43 @@
-use crate::behaviors::{driving::Driving, land_move::LandMove, moving::Moving};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::{
+    driving::{Driving, DrivingError, RoadType},
+    land_move::LandMove,
+    moving::Moving,
+    participant::Participant,
+};
+use crate::core::{EnergyLevel, HasEnergy, Mishap, SeededRng, ThermalRange, ThermalTolerance};
+use crate::crew::{Crewed, License, Operator, VehicleClass};
 use crate::vehicles::Vehicle;
+use crate::vehicles::carrier::{Carrier, PassengerDeck};
+use crate::vehicles::lifecycle::sealed::Transition;
+use crate::vehicles::lifecycle::{
+    EngineRunning, EngineState, Ignition, Lifecycle, Parked, Running,
+};
+use crate::vehicles::maintenance::{Condition, Maintainable};
+use crate::vehicles::payload::{CargoHold, Payload};
+use crate::world::infrastructure::{EnergySupply, Requirement, UsesInfrastructure};
+
+/// Kilometers between scheduled services
+const SERVICE_INTERVAL_KM: u32 = 15_000;
+
+/// Passengers and luggage a car can take
+const PAYLOAD_CAPACITY_KG: u32 = 450;
+
+/// Weight of the empty car
+const CURB_WEIGHT_KG: u32 = 1_500;
 
 #[derive(Debug)]
-pub struct Car {
+pub struct Car<S = EngineRunning> {
     name: String,
     manufacturer: String,
     year: u32,
     energy: EnergyLevel, // Represents fuel level
     max_speed: u32,
     engine_type: EngineType,
+    condition: Condition,
+    cargo_hold: CargoHold,
+    passenger_deck: PassengerDeck,
+    crew: Option<Box<dyn Operator>>,
+    ignition: Ignition<S>,
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +59,18 @@ pub enum EngineType {
     },
 }
 
+/// Electric batteries lose range in the cold
+impl EngineType {
+    /// Temperatures the engine runs in without extra consumption
+    pub fn thermal_range(&self) -> ThermalRange {
+        match self {
+            EngineType::Electric { .. } => ThermalRange::new(0, 45),
+            EngineType::Hybrid { .. } => ThermalRange::new(-10, 45),
+            EngineType::Gasoline { .. } | EngineType::Diesel { .. } => ThermalRange::new(-30, 50),
+        }
+    }
+}
+
 impl Car {
     pub fn new(name: String, manufacturer: String, year: u32, engine_type: EngineType) -> Self {
         let max_speed = match &engine_type {
@@ -40,6 +80,9 @@ impl Car {
             EngineType::Diesel { cylinders, .. } => 110 + (cylinders * 8) as u32,
         };
 
+        let condition = Condition::new(SERVICE_INTERVAL_KM, SeededRng::from_label(&name));
+        let cargo_hold = CargoHold::new(PAYLOAD_CAPACITY_KG);
+
         Self {
             name,
             manufacturer,
@@ -47,16 +90,25 @@ impl Car {
             energy: EnergyLevel::Normal, // Start with half tank
             max_speed,
             engine_type,
+            condition,
+            cargo_hold,
+            passenger_deck: PassengerDeck::new(),
+            crew: None,
+            ignition: Ignition::new(),
         }
     }
+}
 
-    pub fn engine_type(&self) -> &EngineType {
-        &self.engine_type
+impl Car<Parked> {
+    /// Create a car with its engine off
+    pub fn parked(name: String, manufacturer: String, year: u32, engine_type: EngineType) -> Self {
+        Car::new(name, manufacturer, year, engine_type).shut_down()
     }
+}
 
-    /// Refuel the car
-    pub fn refuel(&mut self) {
-        self.energy = EnergyLevel::Hyperactive; // Full tank
+impl<S: EngineState> Car<S> {
+    pub fn engine_type(&self) -> &EngineType {
+        &self.engine_type
     }
 
     /// Get fuel level as percentage
@@ -72,7 +124,40 @@ impl Car {
     }
 }
 
-impl Vehicle for Car {
+impl<S: EngineState> Transition for Car<S> {
+    type State = S;
+    type With<T: EngineState> = Car<T>;
+
+    fn ignition(&self) -> &Ignition<S> {
+        &self.ignition
+    }
+
+    fn ignition_mut(&mut self) -> &mut Ignition<S> {
+        &mut self.ignition
+    }
+
+    fn into_state<T: EngineState>(self) -> Car<T> {
+        Car {
+            name: self.name,
+            manufacturer: self.manufacturer,
+            year: self.year,
+            energy: self.energy,
+            max_speed: self.max_speed,
+            engine_type: self.engine_type,
+            condition: self.condition,
+            cargo_hold: self.cargo_hold,
+            passenger_deck: self.passenger_deck,
+            crew: self.crew,
+            ignition: self.ignition.transition(),
+        }
+    }
+}
+
+impl<S: EngineState> Lifecycle for Car<S> {
+    const IDLE_MINUTES_PER_LEVEL: u32 = 30;
+}
+
+impl<S: EngineState> Vehicle for Car<S> {
     fn name(&self) -> String {
         self.name.clone()
     }
@@ -116,7 +201,7 @@ impl Vehicle for Car {
     }
 }
 
-impl HasEnergy for Car {
+impl<S: EngineState> HasEnergy for Car<S> {
     fn energy(&self) -> EnergyLevel {
         self.energy
     }
@@ -126,16 +211,160 @@ impl HasEnergy for Car {
     }
 }
 
-impl Moving for Car {}
-impl LandMove for Car {}
+impl<S: EngineState> ThermalTolerance for Car<S> {
+    fn thermal_range(&self) -> ThermalRange {
+        self.engine_type.thermal_range()
+    }
+}
+
+impl<S: EngineState> Maintainable for Car<S> {
+    fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    fn condition_mut(&mut self) -> &mut Condition {
+        &mut self.condition
+    }
+}
+
+impl<S: EngineState> Payload for Car<S> {
+    fn cargo_hold(&self) -> &CargoHold {
+        &self.cargo_hold
+    }
+
+    fn cargo_hold_mut(&mut self) -> &mut CargoHold {
+        &mut self.cargo_hold
+    }
+
+    fn empty_weight_kg(&self) -> u32 {
+        CURB_WEIGHT_KG
+    }
+}
+
+impl<S: EngineState> Carrier for Car<S> {
+    fn passenger_deck(&self) -> &PassengerDeck {
+        &self.passenger_deck
+    }
+
+    fn passenger_deck_mut(&mut self) -> &mut PassengerDeck {
+        &mut self.passenger_deck
+    }
+}
+
+impl<S: EngineState> Crewed for Car<S> {
+    fn vehicle_class(&self) -> VehicleClass {
+        VehicleClass::Car
+    }
+
+    fn required_license(&self) -> License {
+        License::Car
+    }
+
+    fn crew_seat(&self) -> &Option<Box<dyn Operator>> {
+        &self.crew
+    }
+
+    fn crew_seat_mut(&mut self) -> &mut Option<Box<dyn Operator>> {
+        &mut self.crew
+    }
+}
+
+/// Cars need no facility to park, only a station to fill up
+impl<S: EngineState> UsesInfrastructure for Car<S> {
+    fn requirement(&self) -> Requirement {
+        Requirement::Nothing
+    }
+
+    fn energy_supplies(&self) -> Vec<EnergySupply> {
+        match self.engine_type {
+            EngineType::Electric { .. } => vec![EnergySupply::Charging],
+            // A plug-in hybrid takes either
+            EngineType::Hybrid { .. } => vec![EnergySupply::Fuel, EnergySupply::Charging],
+            EngineType::Gasoline { .. } | EngineType::Diesel { .. } => vec![EnergySupply::Fuel],
+        }
+    }
+}
+
+/// A parked car cannot move on its own, but it can still be carried
+impl Participant for Car<Parked> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+}
+
+impl<S: Running> Participant for Car<S> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+
+    fn as_carrier(&self) -> Option<&dyn Carrier> {
+        Some(self)
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_driver(&self) -> Option<&dyn Driving> {
+        Some(self)
+    }
+}
+
+impl<S: Running> Moving for Car<S> {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.take_damage(mishap.severity());
+    }
+}
+impl<S: Running> LandMove for Car<S> {}
+
+impl<S: Running> Driving for Car<S> {
+    fn check_driving_conditions(&self) -> Result<(), DrivingError> {
+        self.check_condition()?;
+        self.check_crew().map_err(|error| DrivingError::CrewNotFit {
+            reason: error.to_string(),
+        })
+    }
+
+    fn apply_wear(
+        &mut self,
+        distance_km: u32,
+        speed_kmh: u32,
+        road_type: RoadType,
+    ) -> Result<(), DrivingError> {
+        self.log_crew_duty(distance_km);
+        self.record_wear(distance_km, speed_kmh, road_type.difficulty_level())
+    }
+
+    fn driving_skill(&self) -> u8 {
+        self.crew_skill().unwrap_or(3) // Uncrewed: moderate skill
+    }
 
-impl Driving for Car {
     fn max_speed(&self) -> u32 {
-        self.max_speed
+        self.crew_limited(self.cargo_hold.loaded_speed(self.max_speed))
     }
 
     fn fuel_efficiency(&self) -> u32 {
-        match &self.engine_type {
+        let base_efficiency = match &self.engine_type {
             EngineType::Gasoline {
                 cylinders,
                 displacement,
@@ -147,7 +376,9 @@ impl Driving for Car {
             EngineType::Electric { .. } => 120, // Electric cars are very efficient
             EngineType::Hybrid { .. } => 90,    // Hybrids are quite efficient
             EngineType::Diesel { .. } => 70,    // Diesel is more efficient than gasoline
-        }
+        };
+
+        self.cargo_hold.loaded_efficiency(base_efficiency)
     }
 
     // Add this method to explicitly indicate no off-road capability
@@ -155,3 +386,147 @@ impl Driving for Car {
         false // Regular cars cannot handle off-road terrain
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crew::CrewMember;
+    use crate::vehicles::PayloadError;
+
+    fn parked_car() -> Car<Parked> {
+        Car::parked(
+            "Test".to_string(),
+            "Test Motors".to_string(),
+            2022,
+            EngineType::Diesel {
+                cylinders: 4,
+                displacement: 2.0,
+            },
+        )
+    }
+
+    #[test]
+    fn test_lifecycle_transitions() {
+        let car = parked_car();
+        assert_eq!(car.engine_state(), "Parked");
+
+        let car = car.start().unwrap();
+        assert_eq!(car.engine_state(), "Engine Running");
+
+        let mut car = car.pull_away();
+        assert_eq!(car.engine_state(), "In Motion");
+        assert!(car.drive().is_ok());
+
+        let car = car.stop().shut_down();
+        assert_eq!(car.engine_state(), "Parked");
+    }
+
+    #[test]
+    fn test_cannot_start_with_empty_tank() {
+        let mut car = parked_car();
+        car.set_energy(EnergyLevel::Collapsed);
+
+        let error = car.start().unwrap_err();
+        assert_eq!(error.to_string(), "Cannot start Test: fuel tank is empty");
+
+        let mut car = *error.vehicle;
+        car.set_energy(EnergyLevel::Hyperactive);
+        assert!(car.start().is_ok());
+    }
+
+    #[test]
+    fn test_driving_wears_the_car() {
+        let mut car = parked_car().start().unwrap();
+        car.set_energy(EnergyLevel::Hyperactive);
+        car.condition_mut().set_age_years(3);
+
+        car.drive_distance(200).unwrap();
+        assert_eq!(car.condition().odometer_km(), 200);
+        assert_eq!(car.condition().percent(), 98); // 3 years old wears 15% faster
+    }
+
+    #[test]
+    fn test_poor_condition_causes_mechanical_failure() {
+        let mut car = parked_car().start().unwrap();
+        car.set_energy(EnergyLevel::Hyperactive);
+        car.condition_mut().set_percent(10);
+
+        let result = car.drive();
+        assert!(matches!(
+            result,
+            Err(DrivingError::MechanicalFailure { .. })
+        ));
+        assert_eq!(car.energy(), EnergyLevel::Hyperactive); // Never left
+    }
+
+    #[test]
+    fn test_maintain_restores_condition_at_a_cost() {
+        let mut car = parked_car().start().unwrap();
+        car.condition_mut().set_percent(10);
+        car.condition_mut().set_age_years(3);
+
+        let report = car.maintain();
+        assert_eq!(car.max_restorable_condition(), 99); // 3 years old
+        assert_eq!(report.condition, 99);
+        assert_eq!(report.restored, 89);
+        assert_eq!(report.cost, 89 * 10 * 106 / 100);
+        assert!(car.check_condition().is_ok());
+
+        // A new car costs no age surcharge and services back to 100%
+        let mut new_car = parked_car().start().unwrap();
+        new_car.condition_mut().set_percent(10);
+        assert_eq!(new_car.age_years(), 0);
+        assert_eq!(new_car.maintain().cost, 90 * 10);
+    }
+
+    #[test]
+    fn test_load_slows_the_car() {
+        let mut car = parked_car().start().unwrap();
+        let empty_speed = car.max_speed();
+        let empty_efficiency = car.fuel_efficiency();
+
+        car.board_passengers(5).unwrap(); // 450 kg - fully loaded
+        assert_eq!(car.cargo_hold().load_percentage(), 100);
+        assert_eq!(car.max_speed(), empty_speed * 70 / 100);
+        assert!(car.fuel_efficiency() < empty_efficiency);
+
+        assert!(matches!(
+            car.load_cargo(1),
+            Err(PayloadError::Overloaded { .. })
+        ));
+    }
+
+    #[test]
+    fn test_driver_skill_and_fatigue() {
+        let mut car = parked_car().start().unwrap();
+        let top_speed = car.max_speed();
+
+        let driver = CrewMember::new("Alex".to_string())
+            .with_license(License::Car)
+            .with_skill(VehicleClass::Car, 4);
+        car.assign_operator(Box::new(driver)).unwrap();
+        assert_eq!(car.driving_skill(), 4);
+        assert_eq!(car.max_speed(), top_speed * 90 / 100);
+
+        // A long day at the wheel wears the driver out
+        for _ in 0..3 {
+            car.set_energy(EnergyLevel::Hyperactive);
+            car.drive_distance(200).unwrap();
+        }
+        assert_eq!(car.operator().unwrap().energy(), EnergyLevel::Exhausted);
+
+        car.set_energy(EnergyLevel::Hyperactive);
+        assert!(matches!(car.drive(), Err(DrivingError::CrewNotFit { .. })));
+    }
+
+    #[test]
+    fn test_idling_burns_fuel() {
+        let mut car = parked_car().start().unwrap();
+        car.set_energy(EnergyLevel::Hyperactive);
+
+        car.idle(20);
+        assert_eq!(car.energy(), EnergyLevel::Hyperactive);
+        car.idle(40);
+        assert_eq!(car.energy(), EnergyLevel::Normal); // 60 minutes = 2 levels
+    }
+}
@@ -0,0 +1,271 @@
+//! Carrying Other Participants
+//!
+//! A [`Carrier`] takes any [`Participant`] on board, animals and vehicles
+//! alike: a ship ferries a car, a helicopter airlifts a dog. Passengers are
+//! moved by the carrier's own behaviors, so they keep their energy while the
+//! carrier pays the fuel. Each passenger's weight counts against the
+//! carrier's [`Payload`] capacity until it is unloaded.
+
+use crate::behaviors::Participant;
+use crate::vehicles::payload::{Payload, PayloadError};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CarrierError {
+    #[error(transparent)]
+    Payload(#[from] PayloadError),
+
+    #[error("{name} is already on board")]
+    AlreadyAboard { name: String },
+
+    #[error("{name} is not on board")]
+    NotAboard { name: String },
+}
+
+/// Returned when a passenger cannot board; gives the passenger back
+#[derive(Error, Debug)]
+#[error("{reason}")]
+pub struct BoardError {
+    pub reason: CarrierError,
+    pub passenger: Box<dyn Participant>,
+}
+
+#[derive(Debug)]
+struct Boarded {
+    weight_kg: u32,
+    passenger: Box<dyn Participant>,
+}
+
+/// Participants currently on board a carrier
+#[derive(Debug, Default)]
+pub struct PassengerDeck {
+    aboard: Vec<Boarded>,
+}
+
+impl PassengerDeck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.aboard.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aboard.is_empty()
+    }
+
+    /// Get the passengers in boarding order
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Participant> {
+        self.aboard.iter().map(|boarded| boarded.passenger.as_ref())
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.aboard
+            .iter()
+            .position(|boarded| boarded.passenger.participant_name() == name)
+    }
+}
+
+/// Vehicles that can take other participants on board
+pub trait Carrier: Payload {
+    /// Get the passenger deck
+    fn passenger_deck(&self) -> &PassengerDeck;
+
+    /// Get mutable access to the passenger deck
+    fn passenger_deck_mut(&mut self) -> &mut PassengerDeck;
+
+    /// Names of everyone on board
+    fn passengers(&self) -> Vec<String> {
+        self.passenger_deck()
+            .iter()
+            .map(|passenger| passenger.participant_name())
+            .collect()
+    }
+
+    /// Look up a passenger by name
+    fn passenger(&self, name: &str) -> Option<&dyn Participant> {
+        let index = self.passenger_deck().position(name)?;
+        Some(self.passenger_deck().aboard[index].passenger.as_ref())
+    }
+
+    /// Check if a participant would fit
+    fn can_board(&self, passenger: &dyn Participant) -> bool {
+        self.can_carry(passenger.weight_kg())
+    }
+
+    /// Take a participant on board
+    fn board(&mut self, passenger: Box<dyn Participant>) -> Result<String, BoardError> {
+        let name = passenger.participant_name();
+        if self.passenger_deck().position(&name).is_some() {
+            return Err(BoardError {
+                reason: CarrierError::AlreadyAboard { name },
+                passenger,
+            });
+        }
+
+        let weight_kg = passenger.weight_kg();
+        if let Err(error) = self.cargo_hold_mut().load(weight_kg) {
+            return Err(BoardError {
+                reason: error.into(),
+                passenger,
+            });
+        }
+
+        self.passenger_deck_mut().aboard.push(Boarded {
+            weight_kg,
+            passenger,
+        });
+        Ok(format!(
+            "{} boards {} ({} kg)",
+            name,
+            self.name(),
+            weight_kg
+        ))
+    }
+
+    /// Let a passenger off, handing it back
+    fn unload_passenger(&mut self, name: &str) -> Result<Box<dyn Participant>, CarrierError> {
+        let index =
+            self.passenger_deck()
+                .position(name)
+                .ok_or_else(|| CarrierError::NotAboard {
+                    name: name.to_string(),
+                })?;
+
+        let boarded = self.passenger_deck_mut().aboard.remove(index);
+        self.cargo_hold_mut().unload(boarded.weight_kg)?;
+        Ok(boarded.passenger)
+    }
+
+    /// Let everyone off, in boarding order
+    fn unload_all(&mut self) -> Vec<Box<dyn Participant>> {
+        let aboard = std::mem::take(&mut self.passenger_deck_mut().aboard);
+        aboard
+            .into_iter()
+            .map(|boarded| {
+                // Boarding loaded exactly this weight, so unloading cannot fail
+                let _ = self.cargo_hold_mut().unload(boarded.weight_kg);
+                boarded.passenger
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::{Dog, dog::DogBreed};
+    use crate::behaviors::Flying;
+    use crate::core::{EnergyLevel, HasEnergy};
+    use crate::vehicles::helicopter::{HelicopterEngine, HelicopterType};
+    use crate::vehicles::ship::{PropulsionType, ShipType};
+    use crate::vehicles::{Car, Helicopter, Lifecycle, Ship, car::EngineType};
+
+    fn helicopter(helicopter_type: HelicopterType) -> Helicopter {
+        let mut helicopter = Helicopter::new(
+            "Rescue 1".to_string(),
+            "Test Aero".to_string(),
+            2020,
+            helicopter_type,
+            11,
+            HelicopterEngine::Turboshaft {
+                engines: 2,
+                power_each: 600,
+            },
+        );
+        helicopter.set_energy(EnergyLevel::Hyperactive);
+        helicopter
+    }
+
+    #[test]
+    fn test_airlift_keeps_passenger_energy() {
+        let mut helicopter = helicopter(HelicopterType::Emergency);
+        let mut dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        dog.set_energy(EnergyLevel::Exhausted);
+
+        helicopter.board(Box::new(dog)).unwrap();
+        assert_eq!(helicopter.current_load(), 32);
+
+        let fuel_before = helicopter.energy();
+        helicopter.fly().unwrap();
+        assert!(helicopter.energy() < fuel_before);
+
+        let dog = helicopter.unload_passenger("Rex").unwrap();
+        assert_eq!(dog.energy(), EnergyLevel::Exhausted);
+        assert_eq!(helicopter.current_load(), 0);
+    }
+
+    #[test]
+    fn test_ferry_takes_parked_car() {
+        let mut ferry = Ship::new(
+            "Ferry".to_string(),
+            "Test Yard".to_string(),
+            2015,
+            ShipType::Ferry,
+            5000,
+            PropulsionType::Diesel {
+                engines: 2,
+                power_each: 3000,
+            },
+        );
+        let car = Car::new(
+            "Commuter".to_string(),
+            "Test Motors".to_string(),
+            2022,
+            EngineType::Electric {
+                battery_capacity: 80,
+            },
+        )
+        .shut_down();
+
+        ferry.board(Box::new(car)).unwrap();
+        assert_eq!(ferry.passengers(), vec!["Commuter".to_string()]);
+        assert!(
+            ferry
+                .passenger("Commuter")
+                .unwrap()
+                .travel_modes()
+                .is_empty()
+        );
+
+        let error = ferry.unload_passenger("Nobody").unwrap_err();
+        assert_eq!(
+            error,
+            CarrierError::NotAboard {
+                name: "Nobody".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_overweight_passenger_is_handed_back() {
+        let mut helicopter = helicopter(HelicopterType::News);
+        let car = Car::new(
+            "Heavy".to_string(),
+            "Test Motors".to_string(),
+            2022,
+            EngineType::Electric {
+                battery_capacity: 80,
+            },
+        );
+
+        let error = helicopter.board(Box::new(car)).unwrap_err();
+        assert!(matches!(error.reason, CarrierError::Payload(_)));
+        assert_eq!(error.passenger.participant_name(), "Heavy");
+        assert!(helicopter.passengers().is_empty());
+
+        let dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        let twin = Dog::new("Rex".to_string(), DogBreed::Husky);
+        helicopter.board(Box::new(dog)).unwrap();
+        assert!(matches!(
+            helicopter.board(Box::new(twin)),
+            Err(BoardError {
+                reason: CarrierError::AlreadyAboard { .. },
+                ..
+            })
+        ));
+        assert_eq!(helicopter.unload_all().len(), 1);
+        assert_eq!(helicopter.current_load(), 0);
+    }
+}
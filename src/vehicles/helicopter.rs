@@ -1,9 +1,27 @@
-use crate::behaviors::{flying::Flying, moving::Moving};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::{
+    flying::{FlightCategory, Flying, FlyingError},
+    moving::Moving,
+    participant::Participant,
+};
+use crate::core::{EnergyLevel, HasEnergy, Mishap, SeededRng, ThermalRange, ThermalTolerance};
 use crate::vehicles::Vehicle;
+use crate::vehicles::carrier::{Carrier, PassengerDeck};
+use crate::vehicles::lifecycle::sealed::Transition;
+use crate::vehicles::lifecycle::{
+    EngineRunning, EngineState, Ignition, Lifecycle, Parked, Running,
+};
+use crate::vehicles::maintenance::{Condition, Maintainable, mechanical_reason};
+use crate::vehicles::payload::{CargoHold, Payload};
+use crate::world::infrastructure::{EnergySupply, Requirement, UsesInfrastructure};
+
+/// Kilometers between scheduled services
+const SERVICE_INTERVAL_KM: u32 = 20_000;
+
+/// Distance of a typical flight, logged as airframe wear
+const FLIGHT_LEG_KM: u32 = 150;
 
 #[derive(Debug)]
-pub struct Helicopter {
+pub struct Helicopter<S = EngineRunning> {
     name: String,
     manufacturer: String,
     year: u32,
@@ -11,6 +29,10 @@ pub struct Helicopter {
     helicopter_type: HelicopterType,
     rotor_diameter: u32, // in meters
     engine_type: HelicopterEngine,
+    condition: Condition,
+    cargo_hold: CargoHold,
+    passenger_deck: PassengerDeck,
+    ignition: Ignition<S>,
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +52,43 @@ pub enum HelicopterEngine {
     Electric { motors: u8, battery_capacity: u32 }, // battery in kWh
 }
 
+impl HelicopterEngine {
+    /// Temperatures the engine runs in without extra consumption
+    pub fn thermal_range(&self) -> ThermalRange {
+        match self {
+            HelicopterEngine::Turboshaft { .. } => ThermalRange::new(-40, 50),
+            HelicopterEngine::Piston { .. } => ThermalRange::new(-10, 45),
+            HelicopterEngine::Electric { .. } => ThermalRange::new(0, 40),
+        }
+    }
+}
+
+impl HelicopterType {
+    /// Weight of the empty helicopter in kg
+    pub fn empty_weight_kg(&self) -> u32 {
+        match self {
+            HelicopterType::Emergency => 3_000,
+            HelicopterType::Military => 7_000,
+            HelicopterType::Civilian => 1_200,
+            HelicopterType::Cargo => 12_000,
+            HelicopterType::Police => 1_500,
+            HelicopterType::News => 1_100,
+        }
+    }
+
+    /// Crew, passengers and cargo the helicopter can lift
+    pub fn payload_capacity_kg(&self) -> u32 {
+        match self {
+            HelicopterType::Emergency => 1_200, // Patient, medics and equipment
+            HelicopterType::Military => 4_000,  // Troop transport
+            HelicopterType::Civilian => 600,
+            HelicopterType::Cargo => 10_000, // Heavy lift
+            HelicopterType::Police => 500,
+            HelicopterType::News => 400,
+        }
+    }
+}
+
 impl Helicopter {
     pub fn new(
         name: String,
@@ -39,6 +98,9 @@ impl Helicopter {
         rotor_diameter: u32,
         engine_type: HelicopterEngine,
     ) -> Self {
+        let condition = Condition::new(SERVICE_INTERVAL_KM, SeededRng::from_label(&name));
+        let cargo_hold = CargoHold::new(helicopter_type.payload_capacity_kg());
+
         Self {
             name,
             manufacturer,
@@ -47,9 +109,37 @@ impl Helicopter {
             helicopter_type,
             rotor_diameter,
             engine_type,
+            condition,
+            cargo_hold,
+            passenger_deck: PassengerDeck::new(),
+            ignition: Ignition::new(),
         }
     }
+}
+
+impl Helicopter<Parked> {
+    /// Create a helicopter with its engine off
+    pub fn parked(
+        name: String,
+        manufacturer: String,
+        year: u32,
+        helicopter_type: HelicopterType,
+        rotor_diameter: u32,
+        engine_type: HelicopterEngine,
+    ) -> Self {
+        Helicopter::new(
+            name,
+            manufacturer,
+            year,
+            helicopter_type,
+            rotor_diameter,
+            engine_type,
+        )
+        .shut_down()
+    }
+}
 
+impl<S: EngineState> Helicopter<S> {
     pub fn helicopter_type(&self) -> &HelicopterType {
         &self.helicopter_type
     }
@@ -61,14 +151,42 @@ impl Helicopter {
     pub fn engine_type(&self) -> &HelicopterEngine {
         &self.engine_type
     }
+}
+
+impl<S: EngineState> Transition for Helicopter<S> {
+    type State = S;
+    type With<T: EngineState> = Helicopter<T>;
+
+    fn ignition(&self) -> &Ignition<S> {
+        &self.ignition
+    }
 
-    /// Refuel the helicopter
-    pub fn refuel(&mut self) {
-        self.energy = EnergyLevel::Hyperactive;
+    fn ignition_mut(&mut self) -> &mut Ignition<S> {
+        &mut self.ignition
     }
+
+    fn into_state<T: EngineState>(self) -> Helicopter<T> {
+        Helicopter {
+            name: self.name,
+            manufacturer: self.manufacturer,
+            year: self.year,
+            energy: self.energy,
+            helicopter_type: self.helicopter_type,
+            rotor_diameter: self.rotor_diameter,
+            engine_type: self.engine_type,
+            condition: self.condition,
+            cargo_hold: self.cargo_hold,
+            passenger_deck: self.passenger_deck,
+            ignition: self.ignition.transition(),
+        }
+    }
+}
+
+impl<S: EngineState> Lifecycle for Helicopter<S> {
+    const IDLE_MINUTES_PER_LEVEL: u32 = 15;
 }
 
-impl Vehicle for Helicopter {
+impl<S: EngineState> Vehicle for Helicopter<S> {
     fn name(&self) -> String {
         self.name.clone()
     }
@@ -114,7 +232,7 @@ impl Vehicle for Helicopter {
     }
 }
 
-impl HasEnergy for Helicopter {
+impl<S: EngineState> HasEnergy for Helicopter<S> {
     fn energy(&self) -> EnergyLevel {
         self.energy
     }
@@ -124,9 +242,144 @@ impl HasEnergy for Helicopter {
     }
 }
 
-impl Moving for Helicopter {}
+impl<S: EngineState> ThermalTolerance for Helicopter<S> {
+    fn thermal_range(&self) -> ThermalRange {
+        self.engine_type.thermal_range()
+    }
+}
+
+impl<S: EngineState> Maintainable for Helicopter<S> {
+    fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    fn condition_mut(&mut self) -> &mut Condition {
+        &mut self.condition
+    }
+
+    fn service_cost_per_point(&self) -> u32 {
+        60
+    }
+}
+
+impl<S: EngineState> Payload for Helicopter<S> {
+    fn cargo_hold(&self) -> &CargoHold {
+        &self.cargo_hold
+    }
+
+    fn cargo_hold_mut(&mut self) -> &mut CargoHold {
+        &mut self.cargo_hold
+    }
+
+    fn empty_weight_kg(&self) -> u32 {
+        self.helicopter_type.empty_weight_kg()
+    }
+}
+
+impl<S: EngineState> Carrier for Helicopter<S> {
+    fn passenger_deck(&self) -> &PassengerDeck {
+        &self.passenger_deck
+    }
+
+    fn passenger_deck_mut(&mut self) -> &mut PassengerDeck {
+        &mut self.passenger_deck
+    }
+}
+
+impl<S: EngineState> UsesInfrastructure for Helicopter<S> {
+    fn requirement(&self) -> Requirement {
+        Requirement::Helipad
+    }
+
+    fn energy_supplies(&self) -> Vec<EnergySupply> {
+        match self.engine_type {
+            HelicopterEngine::Electric { .. } => vec![EnergySupply::Charging],
+            _ => vec![EnergySupply::Fuel],
+        }
+    }
+}
+
+/// A parked helicopter cannot move on its own, but it can still be carried
+impl Participant for Helicopter<Parked> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+}
+
+impl<S: Running> Participant for Helicopter<S> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+
+    fn as_carrier(&self) -> Option<&dyn Carrier> {
+        Some(self)
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_flyer(&self) -> Option<&dyn Flying> {
+        Some(self)
+    }
+}
+
+impl<S: Running> Moving for Helicopter<S> {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.take_damage(mishap.severity());
+    }
+}
+
+impl<S: Running> Flying for Helicopter<S> {
+    fn check_flying_conditions(&self) -> Result<(), FlyingError> {
+        self.check_condition()
+            .map_err(|error| FlyingError::MechanicalFailure {
+                reason: mechanical_reason(error),
+            })
+    }
+
+    fn log_flight(&mut self) -> Result<(), FlyingError> {
+        let speed_kmh = self.flying_speed();
+        self.record_wear(FLIGHT_LEG_KM, speed_kmh, 1)
+            .map_err(|error| FlyingError::MechanicalFailure {
+                reason: mechanical_reason(error),
+            })
+    }
+
+    fn flight_category(&self) -> FlightCategory {
+        FlightCategory::Rotorcraft
+    }
+
+    fn flying_speed(&self) -> u32 {
+        let cruising_speed = match self.helicopter_type {
+            HelicopterType::Military => 280,
+            HelicopterType::Cargo => 220,
+            _ => 250,
+        };
+
+        self.cargo_hold.loaded_speed(cruising_speed)
+    }
 
-impl Flying for Helicopter {
     fn max_altitude(&self) -> u32 {
         let base_altitude = match self.helicopter_type {
             HelicopterType::Emergency => 3000, // Need to reach mountain rescues
@@ -148,6 +401,52 @@ impl Flying for Helicopter {
             HelicopterEngine::Electric { motors, .. } => (*motors as u32) * 100,
         };
 
-        base_altitude + rotor_bonus + engine_bonus
+        self.cargo_hold
+            .loaded_altitude(base_altitude + rotor_bonus + engine_bonus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rescue_helicopter() -> Helicopter {
+        Helicopter::new(
+            "Test".to_string(),
+            "Test Rotor".to_string(),
+            2020,
+            HelicopterType::Emergency,
+            11,
+            HelicopterEngine::Turboshaft {
+                engines: 2,
+                power_each: 600,
+            },
+        )
+    }
+
+    #[test]
+    fn test_flying_wears_the_helicopter() {
+        let mut helicopter = rescue_helicopter();
+        helicopter.set_energy(EnergyLevel::Hyperactive);
+
+        helicopter.fly().unwrap();
+        assert_eq!(helicopter.condition().odometer_km(), FLIGHT_LEG_KM);
+        assert!(helicopter.condition().percent() < 100);
+    }
+
+    #[test]
+    fn test_worn_helicopter_breaks_down() {
+        let mut helicopter = rescue_helicopter();
+        helicopter.set_energy(EnergyLevel::Hyperactive);
+        helicopter.condition_mut().set_percent(20);
+
+        // The leg wears it below the limit, so the breakdown is certain
+        let result = helicopter.fly();
+        assert!(matches!(result, Err(FlyingError::MechanicalFailure { .. })));
+
+        let energy = helicopter.energy();
+        let result = helicopter.fly();
+        assert!(matches!(result, Err(FlyingError::MechanicalFailure { .. })));
+        assert_eq!(helicopter.energy(), energy); // Never took off
     }
 }
@@ -0,0 +1,246 @@
+//! Engine Lifecycle Typestates
+//!
+//! Vehicles carry their engine state as a type parameter, so operations that
+//! need a running engine only compile on running vehicles:
+//!
+//! ```text
+//! Parked --start()--> EngineRunning --pull_away()--> InMotion
+//!        <-shut_down()--             <----stop()----
+//! ```
+//!
+//! The default state is `EngineRunning`, so `Car::new(...)` keeps working as
+//! before. Use the `parked(...)` constructors to opt into the full lifecycle.
+//!
+//! The transitions are provided by the [`Lifecycle`] trait; a vehicle only
+//! says how to carry its fields over into another state.
+
+use crate::core::{EnergyLevel, HasEnergy};
+use crate::vehicles::Vehicle;
+use std::fmt;
+use std::marker::PhantomData;
+use thiserror::Error;
+
+pub(super) mod sealed {
+    use super::{EngineState, Ignition};
+
+    pub trait Sealed {}
+
+    /// Moves a vehicle between engine states; hidden so that only the
+    /// [`Lifecycle`](super::Lifecycle) transitions can change the state
+    pub trait Transition: Sized {
+        /// Current engine state
+        type State: EngineState;
+
+        /// The same vehicle in engine state `T`
+        type With<T: EngineState>: Transition<State = T>;
+
+        fn ignition(&self) -> &Ignition<Self::State>;
+
+        fn ignition_mut(&mut self) -> &mut Ignition<Self::State>;
+
+        /// Carry the vehicle over into engine state `T`
+        fn into_state<T: EngineState>(self) -> Self::With<T>;
+    }
+}
+
+use sealed::Transition;
+
+/// Marker trait for all engine states
+pub trait EngineState: sealed::Sealed + fmt::Debug {
+    /// Display name of this state
+    const NAME: &'static str;
+}
+
+/// Marker trait for states in which the engine is turned on
+pub trait Running: EngineState {}
+
+/// Engine off, vehicle stationary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parked;
+
+/// Engine on, vehicle stationary (idling)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineRunning;
+
+/// Engine on, vehicle under way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InMotion;
+
+impl sealed::Sealed for Parked {}
+impl sealed::Sealed for EngineRunning {}
+impl sealed::Sealed for InMotion {}
+
+impl EngineState for Parked {
+    const NAME: &'static str = "Parked";
+}
+
+impl EngineState for EngineRunning {
+    const NAME: &'static str = "Engine Running";
+}
+
+impl EngineState for InMotion {
+    const NAME: &'static str = "In Motion";
+}
+
+impl Running for EngineRunning {}
+impl Running for InMotion {}
+
+/// Returned when an engine cannot be started; gives the parked vehicle back
+#[derive(Error, Debug)]
+#[error("Cannot start {name}: fuel tank is empty")]
+pub struct StartError<V: fmt::Debug> {
+    pub name: String,
+    pub vehicle: Box<V>,
+}
+
+/// Engine state carried by each vehicle, tracking fuel burnt while idling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ignition<S> {
+    idle_minutes: u32,
+    _state: PhantomData<S>,
+}
+
+impl<S: EngineState> Ignition<S> {
+    pub fn new() -> Self {
+        Self {
+            idle_minutes: 0,
+            _state: PhantomData,
+        }
+    }
+
+    /// Get the display name of the current state
+    pub fn state_name(&self) -> &'static str {
+        S::NAME
+    }
+
+    /// Move to another engine state
+    pub fn transition<T: EngineState>(self) -> Ignition<T> {
+        Ignition {
+            idle_minutes: self.idle_minutes,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<S: EngineState> Default for Ignition<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ignition<EngineRunning> {
+    /// Accumulate idle time and return how many energy levels were burnt
+    pub fn idle(&mut self, minutes: u32, minutes_per_level: u32) -> u8 {
+        let minutes_per_level = minutes_per_level.max(1);
+        let before = self.idle_minutes / minutes_per_level;
+        self.idle_minutes = self.idle_minutes.saturating_add(minutes);
+        let after = self.idle_minutes / minutes_per_level;
+
+        (after - before).min(u8::MAX as u32) as u8
+    }
+}
+
+/// Vehicles that carry their engine state as a type parameter
+pub trait Lifecycle: Transition + Vehicle + HasEnergy + fmt::Debug {
+    /// Minutes of idling that burn one energy level
+    const IDLE_MINUTES_PER_LEVEL: u32;
+
+    /// Get the display name of the current engine state
+    fn engine_state(&self) -> &'static str {
+        self.ignition().state_name()
+    }
+
+    /// Start the engine - fails with an empty tank
+    fn start(self) -> Result<Self::With<EngineRunning>, StartError<Self>>
+    where
+        Self: Transition<State = Parked>,
+    {
+        if self.energy() == EnergyLevel::Collapsed {
+            return Err(StartError {
+                name: self.name(),
+                vehicle: Box::new(self),
+            });
+        }
+        Ok(self.into_state())
+    }
+
+    /// Let the engine idle, burning fuel without moving
+    fn idle(&mut self, minutes: u32) -> String
+    where
+        Self: Transition<State = EngineRunning>,
+    {
+        let burnt = self
+            .ignition_mut()
+            .idle(minutes, Self::IDLE_MINUTES_PER_LEVEL);
+        self.consume_energy_levels(burnt);
+        format!("{} idles for {} minutes", self.name(), minutes)
+    }
+
+    /// Turn the engine off
+    fn shut_down(self) -> Self::With<Parked>
+    where
+        Self: Transition<State = EngineRunning>,
+    {
+        self.into_state()
+    }
+
+    /// Start moving
+    fn pull_away(self) -> Self::With<InMotion>
+    where
+        Self: Transition<State = EngineRunning>,
+    {
+        self.into_state()
+    }
+
+    /// Come to a stop with the engine still running
+    fn stop(self) -> Self::With<EngineRunning>
+    where
+        Self: Transition<State = InMotion>,
+    {
+        self.into_state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_names() {
+        assert_eq!(Ignition::<Parked>::new().state_name(), "Parked");
+        assert_eq!(
+            Ignition::<EngineRunning>::new().state_name(),
+            "Engine Running"
+        );
+        assert_eq!(Ignition::<InMotion>::new().state_name(), "In Motion");
+    }
+
+    #[test]
+    fn test_idle_accumulates() {
+        let mut ignition = Ignition::<EngineRunning>::new();
+
+        assert_eq!(ignition.idle(20, 30), 0);
+        assert_eq!(ignition.idle(20, 30), 1); // 40 minutes crosses one level
+        assert_eq!(ignition.idle(80, 30), 3); // 120 minutes total
+    }
+
+    #[test]
+    fn test_transition_keeps_idle_time() {
+        let mut ignition = Ignition::<EngineRunning>::new();
+        ignition.idle(20, 30);
+
+        let mut ignition = ignition
+            .transition::<InMotion>()
+            .transition::<EngineRunning>();
+        assert_eq!(ignition.idle(10, 30), 1);
+    }
+
+    #[test]
+    fn test_idle_time_saturates() {
+        let mut ignition = Ignition::<EngineRunning>::new();
+        ignition.idle(u32::MAX - 5, 1);
+
+        assert_eq!(ignition.idle(10, 1), 5);
+        assert_eq!(ignition.idle(10, 1), 0);
+    }
+}
@@ -0,0 +1,382 @@
+//! Mechanical Wear and Maintenance
+//!
+//! Every vehicle carries a [`Condition`] that wears down with use. Wear grows
+//! with distance, speed, road difficulty and the vehicle's age. Below 60%
+//! condition, or with the service overdue, each leg has a chance to end in a
+//! breakdown; below 20% the vehicle refuses to move at all. Breakdowns are
+//! rolled with a seeded RNG so simulations are reproducible.
+//!
+//! The age is kept on the [`Condition`] rather than worked out from the
+//! model year, so results do not depend on the current date. Vehicles start
+//! out new; `condition_mut().set_age_years(..)` models an older one.
+//!
+//! Failed risky activities - driving onto a volcano, flying into a
+//! hurricane - damage the condition directly, depending on their severity.
+//!
+//! `maintain()` restores condition (older vehicles never get back to 100%)
+//! and reports what the service cost.
+
+use crate::behaviors::driving::DrivingError;
+use crate::core::{SeededRng, Severity};
+use crate::vehicles::Vehicle;
+use std::fmt;
+
+/// Condition (in per mille) below which a vehicle will not move
+const POOR_CONDITION: u32 = 200;
+
+/// Condition (in per mille) below which random breakdowns can happen
+const WORN_CONDITION: u32 = 600;
+
+/// Wear units that make up one per mille of condition (20 km at baseline)
+const WEAR_UNITS_PER_MILLE: u64 = 200_000;
+
+/// Reason behind a mechanical failure, to report it from flying or swimming too
+pub(crate) fn mechanical_reason(error: DrivingError) -> String {
+    match error {
+        DrivingError::MechanicalFailure { reason } => reason,
+        error => error.to_string(),
+    }
+}
+
+/// Mechanical state of a vehicle
+#[derive(Debug, Clone)]
+pub struct Condition {
+    condition: u32, // per mille, 1000 = factory new
+    age_years: u32,
+    odometer_km: u32,
+    km_since_service: u32,
+    service_interval_km: u32,
+    wear_units: u64,
+    rng: SeededRng,
+}
+
+impl Condition {
+    /// Create a factory-new condition
+    pub fn new(service_interval_km: u32, rng: SeededRng) -> Self {
+        Self {
+            condition: 1000,
+            age_years: 0,
+            odometer_km: 0,
+            km_since_service: 0,
+            service_interval_km: service_interval_km.max(1),
+            wear_units: 0,
+            rng,
+        }
+    }
+
+    /// Get the condition as a percentage (100 = factory new)
+    pub fn percent(&self) -> u8 {
+        (self.condition / 10) as u8
+    }
+
+    /// Set the condition as a percentage, e.g. to model a used vehicle
+    pub fn set_percent(&mut self, percent: u8) {
+        self.condition = percent.min(100) as u32 * 10;
+    }
+
+    pub fn age_years(&self) -> u32 {
+        self.age_years
+    }
+
+    /// Set how many years the vehicle has been in use
+    pub fn set_age_years(&mut self, years: u32) {
+        self.age_years = years;
+    }
+
+    pub fn odometer_km(&self) -> u32 {
+        self.odometer_km
+    }
+
+    pub fn km_since_service(&self) -> u32 {
+        self.km_since_service
+    }
+
+    pub fn service_interval_km(&self) -> u32 {
+        self.service_interval_km
+    }
+
+    /// Check if the service interval has been reached
+    pub fn is_service_due(&self) -> bool {
+        self.km_since_service >= self.service_interval_km
+    }
+
+    /// Check if the vehicle is too worn to move
+    pub fn is_poor(&self) -> bool {
+        self.condition < POOR_CONDITION
+    }
+
+    /// Replace the random number generator, e.g. to replay a simulation
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = SeededRng::new(seed);
+    }
+
+    /// Chance (per mille) that the next leg ends in a breakdown
+    pub fn failure_chance(&self, age_years: u32) -> u32 {
+        if self.is_poor() {
+            return 1000;
+        }
+
+        let mut chance = 0;
+        if self.condition < WORN_CONDITION {
+            chance += (WORN_CONDITION - self.condition) / 2 + age_years;
+        }
+
+        // Every full interval past the service adds 5%
+        let overdue_intervals = self.km_since_service / self.service_interval_km;
+        chance += overdue_intervals.saturating_sub(1) * 50;
+        if self.is_service_due() {
+            chance += 50;
+        }
+
+        chance.min(1000)
+    }
+
+    /// Wear down for a completed leg and roll for a breakdown
+    ///
+    /// Returns the breakdown reason if the vehicle failed.
+    pub fn wear(
+        &mut self,
+        distance_km: u32,
+        speed_kmh: u32,
+        road_difficulty: u8,
+        age_years: u32,
+    ) -> Option<String> {
+        let speed_factor = 100 + speed_kmh.saturating_sub(80).min(200) as u64;
+        let age_factor = 100 + 5 * age_years.min(40) as u64;
+        let difficulty = road_difficulty.max(1) as u64;
+
+        self.wear_units += distance_km as u64 * speed_factor * age_factor * difficulty;
+        let lost = (self.wear_units / WEAR_UNITS_PER_MILLE) as u32;
+        self.wear_units %= WEAR_UNITS_PER_MILLE;
+
+        self.condition = self.condition.saturating_sub(lost);
+        self.odometer_km = self.odometer_km.saturating_add(distance_km);
+        self.km_since_service = self.km_since_service.saturating_add(distance_km);
+
+        let chance = self.failure_chance(age_years);
+        if self.rng.chance_per_mille(chance) {
+            Some(self.breakdown_reason())
+        } else {
+            None
+        }
+    }
+
+    /// Service the vehicle up to `max_percent`, returning the points restored
+    pub fn restore(&mut self, max_percent: u8) -> u8 {
+        let before = self.percent();
+        let target = max_percent.min(100) as u32 * 10;
+
+        self.condition = self.condition.max(target);
+        self.km_since_service = 0;
+        self.wear_units = 0;
+
+        self.percent() - before
+    }
+
+    fn breakdown_reason(&self) -> String {
+        if self.is_poor() {
+            format!("condition critical ({}%)", self.percent())
+        } else if self.is_service_due() {
+            format!(
+                "service overdue by {} km",
+                self.km_since_service - self.service_interval_km
+            )
+        } else {
+            format!("worn component failed at {}% condition", self.percent())
+        }
+    }
+}
+
+/// Outcome of a maintenance visit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceReport {
+    /// Condition points restored
+    pub restored: u8,
+    /// Condition after the service, in percent
+    pub condition: u8,
+    /// Price of the service in credits
+    pub cost: u32,
+}
+
+impl fmt::Display for MaintenanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Restored {} points to {}% condition for {} credits",
+            self.restored, self.condition, self.cost
+        )
+    }
+}
+
+/// Vehicles that wear down and can be serviced
+pub trait Maintainable: Vehicle {
+    /// Get the current mechanical condition
+    fn condition(&self) -> &Condition;
+
+    /// Get mutable access to the mechanical condition
+    fn condition_mut(&mut self) -> &mut Condition;
+
+    /// Service price per restored condition point
+    fn service_cost_per_point(&self) -> u32 {
+        10 // Default: 10 credits per point
+    }
+
+    /// Age of the vehicle in years, as kept on its condition
+    fn age_years(&self) -> u32 {
+        self.condition().age_years()
+    }
+
+    /// Best condition a service can achieve - old vehicles never run like new
+    fn max_restorable_condition(&self) -> u8 {
+        100 - (self.age_years().min(40) / 2) as u8
+    }
+
+    /// Refuse to move when condition is too poor
+    fn check_condition(&self) -> Result<(), DrivingError> {
+        if self.condition().is_poor() {
+            return Err(DrivingError::MechanicalFailure {
+                reason: format!(
+                    "{} is in poor condition ({}%) and needs maintenance",
+                    self.name(),
+                    self.condition().percent()
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Record wear for a completed leg; the vehicle may break down
+    fn record_wear(
+        &mut self,
+        distance_km: u32,
+        speed_kmh: u32,
+        road_difficulty: u8,
+    ) -> Result<(), DrivingError> {
+        let age_years = self.age_years();
+        match self
+            .condition_mut()
+            .wear(distance_km, speed_kmh, road_difficulty, age_years)
+        {
+            Some(reason) => Err(DrivingError::MechanicalFailure {
+                reason: format!("{} broke down: {}", self.name(), reason),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Take damage from a failed risky activity
+    fn take_damage(&mut self, severity: Severity) {
+        let percent = self.condition().percent();
+        self.condition_mut()
+            .set_percent(percent.saturating_sub(severity.damage_percent()));
+    }
+
+    /// Service the vehicle, restoring condition at a cost
+    fn maintain(&mut self) -> MaintenanceReport {
+        let max_condition = self.max_restorable_condition();
+        let restored = self.condition_mut().restore(max_condition);
+
+        // Parts for older vehicles are harder to find
+        let age_surcharge = 100 + 2 * self.age_years().min(50);
+        let cost = restored as u32 * self.service_cost_per_point() * age_surcharge / 100;
+
+        MaintenanceReport {
+            restored,
+            condition: self.condition().percent(),
+            cost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition() -> Condition {
+        Condition::new(10_000, SeededRng::new(1))
+    }
+
+    #[test]
+    fn test_wear_grows_with_speed_road_and_age() {
+        let mut baseline = condition();
+        baseline.wear(2_000, 80, 1, 0);
+        assert_eq!(baseline.percent(), 90); // 20 km per mille
+
+        let mut fast = condition();
+        fast.wear(2_000, 180, 1, 0);
+        assert_eq!(fast.percent(), 80);
+
+        let mut mountain = condition();
+        mountain.wear(2_000, 80, 3, 0);
+        assert_eq!(mountain.percent(), 70);
+
+        let mut old = condition();
+        old.wear(2_000, 80, 1, 20);
+        assert_eq!(old.percent(), 80);
+    }
+
+    #[test]
+    fn test_short_legs_accumulate() {
+        let mut condition = condition();
+
+        for _ in 0..200 {
+            condition.wear(10, 80, 1, 0);
+        }
+        assert_eq!(condition.percent(), 90);
+        assert_eq!(condition.odometer_km(), 2_000);
+    }
+
+    #[test]
+    fn test_failure_chance() {
+        let mut condition = condition();
+        assert_eq!(condition.failure_chance(0), 0);
+
+        condition.set_percent(40);
+        assert_eq!(condition.failure_chance(0), 100);
+        assert_eq!(condition.failure_chance(10), 110);
+
+        condition.set_percent(10);
+        assert_eq!(condition.failure_chance(0), 1000);
+    }
+
+    #[test]
+    fn test_overdue_service_raises_failure_chance() {
+        let mut condition = Condition::new(100, SeededRng::new(1));
+        condition.wear(99, 80, 1, 0);
+        assert_eq!(condition.failure_chance(0), 0);
+
+        condition.wear(1, 80, 1, 0);
+        assert!(condition.is_service_due());
+        assert_eq!(condition.failure_chance(0), 50);
+
+        condition.wear(100, 80, 1, 0);
+        assert_eq!(condition.failure_chance(0), 100);
+    }
+
+    #[test]
+    fn test_breakdowns_are_reproducible() {
+        let run = |seed| {
+            let mut condition = condition();
+            condition.set_percent(30);
+            condition.reseed(seed);
+            (0..20)
+                .map(|_| condition.wear(1, 80, 1, 0).is_some())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(99), run(99));
+        assert!(run(99).contains(&true));
+    }
+
+    #[test]
+    fn test_restore() {
+        let mut condition = condition();
+        condition.wear(12_000, 80, 1, 0);
+        assert_eq!(condition.percent(), 40);
+
+        assert_eq!(condition.restore(90), 50);
+        assert_eq!(condition.percent(), 90);
+        assert_eq!(condition.km_since_service(), 0);
+        assert_eq!(condition.odometer_km(), 12_000);
+    }
+}
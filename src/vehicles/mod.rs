@@ -1,15 +1,25 @@
 pub mod airplane;
 pub mod amphibious;
 pub mod car;
+pub mod carrier;
 pub mod helicopter;
+pub mod lifecycle;
+pub mod maintenance;
 pub mod motorcycle;
+pub mod payload;
 pub mod ship;
+pub mod sled;
 pub mod vehicle;
 
 pub use airplane::Airplane;
 pub use amphibious::AmphibiousVehicle;
 pub use car::Car;
+pub use carrier::{BoardError, Carrier, CarrierError, PassengerDeck};
 pub use helicopter::Helicopter;
+pub use lifecycle::{EngineRunning, InMotion, Lifecycle, Parked};
+pub use maintenance::{Condition, Maintainable, MaintenanceReport};
 pub use motorcycle::Motorcycle;
+pub use payload::{CargoHold, Payload, PayloadError};
 pub use ship::Ship;
+pub use sled::{Sled, SledError, SledRun};
 pub use vehicle::Vehicle;
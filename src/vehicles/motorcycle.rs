@@ -1,15 +1,33 @@
-use crate::behaviors::{driving::Driving, land_move::LandMove, moving::Moving};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::{
+    driving::{Driving, DrivingError, RoadType},
+    land_move::LandMove,
+    moving::Moving,
+    participant::Participant,
+};
+use crate::core::{EnergyLevel, HasEnergy, Mishap, SeededRng, ThermalRange, ThermalTolerance};
 use crate::vehicles::Vehicle;
+use crate::vehicles::lifecycle::sealed::Transition;
+use crate::vehicles::lifecycle::{
+    EngineRunning, EngineState, Ignition, Lifecycle, Parked, Running,
+};
+use crate::vehicles::maintenance::{Condition, Maintainable};
+use crate::vehicles::payload::{CargoHold, Payload};
+use crate::world::infrastructure::{EnergySupply, Requirement, UsesInfrastructure};
+
+/// Kilometers between scheduled services
+const SERVICE_INTERVAL_KM: u32 = 8_000;
 
 #[derive(Debug)]
-pub struct Motorcycle {
+pub struct Motorcycle<S = EngineRunning> {
     name: String,
     manufacturer: String,
     year: u32,
     energy: EnergyLevel,
     engine_size: u32, // in cc
     motorcycle_type: MotorcycleType,
+    condition: Condition,
+    cargo_hold: CargoHold,
+    ignition: Ignition<S>,
 }
 
 #[derive(Debug, Clone)]
@@ -21,6 +39,30 @@ pub enum MotorcycleType {
     Electric, // Quiet, efficient
 }
 
+impl MotorcycleType {
+    /// Weight of the motorcycle without rider in kg
+    pub fn empty_weight_kg(&self) -> u32 {
+        match self {
+            MotorcycleType::Sport => 190,
+            MotorcycleType::Cruiser => 300,
+            MotorcycleType::Touring => 380,
+            MotorcycleType::Dirt => 110,
+            MotorcycleType::Electric => 220,
+        }
+    }
+
+    /// Rider, passenger and luggage the motorcycle can take
+    pub fn payload_capacity_kg(&self) -> u32 {
+        match self {
+            MotorcycleType::Sport => 180,
+            MotorcycleType::Cruiser => 220,
+            MotorcycleType::Touring => 250, // Panniers and top box
+            MotorcycleType::Dirt => 150,
+            MotorcycleType::Electric => 180,
+        }
+    }
+}
+
 impl Motorcycle {
     pub fn new(
         name: String,
@@ -29,6 +71,9 @@ impl Motorcycle {
         engine_size: u32,
         moto_type: MotorcycleType,
     ) -> Self {
+        let condition = Condition::new(SERVICE_INTERVAL_KM, SeededRng::from_label(&name));
+        let cargo_hold = CargoHold::new(moto_type.payload_capacity_kg());
+
         Self {
             name,
             manufacturer,
@@ -36,9 +81,27 @@ impl Motorcycle {
             energy: EnergyLevel::Normal,
             engine_size,
             motorcycle_type: moto_type,
+            condition,
+            cargo_hold,
+            ignition: Ignition::new(),
         }
     }
+}
+
+impl Motorcycle<Parked> {
+    /// Create a motorcycle with its engine off
+    pub fn parked(
+        name: String,
+        manufacturer: String,
+        year: u32,
+        engine_size: u32,
+        moto_type: MotorcycleType,
+    ) -> Self {
+        Motorcycle::new(name, manufacturer, year, engine_size, moto_type).shut_down()
+    }
+}
 
+impl<S: EngineState> Motorcycle<S> {
     pub fn engine_size(&self) -> u32 {
         self.engine_size
     }
@@ -48,7 +111,38 @@ impl Motorcycle {
     }
 }
 
-impl Vehicle for Motorcycle {
+impl<S: EngineState> Transition for Motorcycle<S> {
+    type State = S;
+    type With<T: EngineState> = Motorcycle<T>;
+
+    fn ignition(&self) -> &Ignition<S> {
+        &self.ignition
+    }
+
+    fn ignition_mut(&mut self) -> &mut Ignition<S> {
+        &mut self.ignition
+    }
+
+    fn into_state<T: EngineState>(self) -> Motorcycle<T> {
+        Motorcycle {
+            name: self.name,
+            manufacturer: self.manufacturer,
+            year: self.year,
+            energy: self.energy,
+            engine_size: self.engine_size,
+            motorcycle_type: self.motorcycle_type,
+            condition: self.condition,
+            cargo_hold: self.cargo_hold,
+            ignition: self.ignition.transition(),
+        }
+    }
+}
+
+impl<S: EngineState> Lifecycle for Motorcycle<S> {
+    const IDLE_MINUTES_PER_LEVEL: u32 = 45;
+}
+
+impl<S: EngineState> Vehicle for Motorcycle<S> {
     fn name(&self) -> String {
         self.name.clone()
     }
@@ -78,7 +172,7 @@ impl Vehicle for Motorcycle {
     }
 }
 
-impl HasEnergy for Motorcycle {
+impl<S: EngineState> HasEnergy for Motorcycle<S> {
     fn energy(&self) -> EnergyLevel {
         self.energy
     }
@@ -88,10 +182,116 @@ impl HasEnergy for Motorcycle {
     }
 }
 
-impl Moving for Motorcycle {}
-impl LandMove for Motorcycle {}
+impl<S: EngineState> ThermalTolerance for Motorcycle<S> {
+    fn thermal_range(&self) -> ThermalRange {
+        ThermalRange::new(-10, 45) // Exposed rider and air-cooled engine
+    }
+}
+
+impl<S: EngineState> Maintainable for Motorcycle<S> {
+    fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    fn condition_mut(&mut self) -> &mut Condition {
+        &mut self.condition
+    }
+
+    fn service_cost_per_point(&self) -> u32 {
+        6
+    }
+}
+
+impl<S: EngineState> Payload for Motorcycle<S> {
+    fn cargo_hold(&self) -> &CargoHold {
+        &self.cargo_hold
+    }
+
+    fn cargo_hold_mut(&mut self) -> &mut CargoHold {
+        &mut self.cargo_hold
+    }
+
+    fn empty_weight_kg(&self) -> u32 {
+        self.motorcycle_type.empty_weight_kg()
+    }
+}
+
+/// A parked motorcycle cannot move on its own, but it can still be carried
+/// Motorcycles need no facility to park, only a station to fill up
+impl<S: EngineState> UsesInfrastructure for Motorcycle<S> {
+    fn requirement(&self) -> Requirement {
+        Requirement::Nothing
+    }
+
+    fn energy_supplies(&self) -> Vec<EnergySupply> {
+        match self.motorcycle_type {
+            MotorcycleType::Electric => vec![EnergySupply::Charging],
+            _ => vec![EnergySupply::Fuel],
+        }
+    }
+}
+
+impl Participant for Motorcycle<Parked> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+}
+
+impl<S: Running> Participant for Motorcycle<S> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_driver(&self) -> Option<&dyn Driving> {
+        Some(self)
+    }
+}
+
+impl<S: Running> Moving for Motorcycle<S> {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.take_damage(mishap.severity());
+    }
+}
+impl<S: Running> LandMove for Motorcycle<S> {}
+
+impl<S: Running> Driving for Motorcycle<S> {
+    fn check_driving_conditions(&self) -> Result<(), DrivingError> {
+        self.check_condition()
+    }
+
+    fn apply_wear(
+        &mut self,
+        distance_km: u32,
+        speed_kmh: u32,
+        road_type: RoadType,
+    ) -> Result<(), DrivingError> {
+        self.record_wear(distance_km, speed_kmh, road_type.difficulty_level())
+    }
 
-impl Driving for Motorcycle {
     fn max_speed(&self) -> u32 {
         let base_speed = match self.motorcycle_type {
             MotorcycleType::Sport => 200,
@@ -102,7 +302,8 @@ impl Driving for Motorcycle {
         };
 
         // Engine size affects top speed
-        base_speed + (self.engine_size / 50)
+        self.cargo_hold
+            .loaded_speed(base_speed + (self.engine_size / 50))
     }
 
     fn fuel_efficiency(&self) -> u32 {
@@ -115,6 +316,7 @@ impl Driving for Motorcycle {
         };
 
         // Larger engines are generally less efficient
-        base_efficiency - (self.engine_size / 100)
+        self.cargo_hold
+            .loaded_efficiency(base_efficiency - (self.engine_size / 100))
     }
 }
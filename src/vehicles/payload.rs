@@ -0,0 +1,245 @@
+//! Payload and Passenger Load
+//!
+//! Vehicles that carry cargo or passengers have a [`CargoHold`] with a
+//! maximum capacity that depends on the vehicle type. The heavier the load,
+//! the lower the vehicle's top speed, ceiling and fuel efficiency:
+//!
+//! | Stat            | Penalty at full load |
+//! |-----------------|----------------------|
+//! | max speed       | 30%                  |
+//! | max altitude    | 40%                  |
+//! | fuel efficiency | 35%                  |
+//!
+//! Loading beyond capacity is rejected with [`PayloadError::Overloaded`].
+
+use crate::vehicles::Vehicle;
+use thiserror::Error;
+
+/// Average weight of a passenger including luggage, in kg
+pub const PASSENGER_WEIGHT_KG: u32 = 90;
+
+const SPEED_PENALTY_PERCENT: u64 = 30;
+const ALTITUDE_PENALTY_PERCENT: u64 = 40;
+const EFFICIENCY_PENALTY_PERCENT: u64 = 35;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PayloadError {
+    #[error("Cannot load {requested} kg: {current} of {capacity} kg already on board")]
+    Overloaded {
+        requested: u32,
+        current: u32,
+        capacity: u32,
+    },
+
+    #[error("Cannot unload {requested} kg: only {current} kg on board")]
+    NotOnBoard { requested: u32, current: u32 },
+}
+
+/// Current load and capacity of a vehicle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CargoHold {
+    load_kg: u32,
+    capacity_kg: u32,
+}
+
+impl CargoHold {
+    /// Create an empty hold
+    pub fn new(capacity_kg: u32) -> Self {
+        Self {
+            load_kg: 0,
+            capacity_kg,
+        }
+    }
+
+    pub fn load_kg(&self) -> u32 {
+        self.load_kg
+    }
+
+    pub fn capacity_kg(&self) -> u32 {
+        self.capacity_kg
+    }
+
+    /// Remaining capacity in kg
+    pub fn free_kg(&self) -> u32 {
+        self.capacity_kg - self.load_kg
+    }
+
+    /// Load as a percentage of capacity (0-100)
+    pub fn load_percentage(&self) -> u8 {
+        if self.capacity_kg == 0 {
+            return 0;
+        }
+        (self.load_kg as u64 * 100 / self.capacity_kg as u64) as u8
+    }
+
+    /// Add weight to the hold
+    pub fn load(&mut self, kg: u32) -> Result<u32, PayloadError> {
+        if kg > self.free_kg() {
+            return Err(PayloadError::Overloaded {
+                requested: kg,
+                current: self.load_kg,
+                capacity: self.capacity_kg,
+            });
+        }
+        self.load_kg += kg;
+        Ok(self.load_kg)
+    }
+
+    /// Remove weight from the hold
+    pub fn unload(&mut self, kg: u32) -> Result<u32, PayloadError> {
+        if kg > self.load_kg {
+            return Err(PayloadError::NotOnBoard {
+                requested: kg,
+                current: self.load_kg,
+            });
+        }
+        self.load_kg -= kg;
+        Ok(self.load_kg)
+    }
+
+    /// Top speed with the current load
+    pub fn loaded_speed(&self, base_speed: u32) -> u32 {
+        self.reduce(base_speed, SPEED_PENALTY_PERCENT)
+    }
+
+    /// Ceiling with the current load
+    pub fn loaded_altitude(&self, base_altitude: u32) -> u32 {
+        self.reduce(base_altitude, ALTITUDE_PENALTY_PERCENT)
+    }
+
+    /// Fuel efficiency with the current load (never below 1)
+    pub fn loaded_efficiency(&self, base_efficiency: u32) -> u32 {
+        self.reduce(base_efficiency, EFFICIENCY_PENALTY_PERCENT)
+            .max(1)
+    }
+
+    fn reduce(&self, value: u32, max_penalty_percent: u64) -> u32 {
+        let penalty = max_penalty_percent * self.load_percentage() as u64 / 100;
+        (value as u64 * (100 - penalty) / 100) as u32
+    }
+}
+
+/// Vehicles that can carry cargo and passengers
+pub trait Payload: Vehicle {
+    /// Get the cargo hold
+    fn cargo_hold(&self) -> &CargoHold;
+
+    /// Get mutable access to the cargo hold
+    fn cargo_hold_mut(&mut self) -> &mut CargoHold;
+
+    /// Weight of the empty vehicle in kg
+    fn empty_weight_kg(&self) -> u32;
+
+    /// Weight of the vehicle including its load
+    fn gross_weight_kg(&self) -> u32 {
+        self.empty_weight_kg() + self.current_load()
+    }
+
+    /// Maximum payload in kg
+    fn payload_capacity(&self) -> u32 {
+        self.cargo_hold().capacity_kg()
+    }
+
+    /// Current payload in kg
+    fn current_load(&self) -> u32 {
+        self.cargo_hold().load_kg()
+    }
+
+    /// Load cargo, rejecting anything beyond capacity
+    fn load_cargo(&mut self, kg: u32) -> Result<String, PayloadError> {
+        let total = self.cargo_hold_mut().load(kg)?;
+        Ok(format!(
+            "{} loads {} kg ({} of {} kg)",
+            self.name(),
+            kg,
+            total,
+            self.payload_capacity()
+        ))
+    }
+
+    /// Unload cargo
+    fn unload_cargo(&mut self, kg: u32) -> Result<String, PayloadError> {
+        let total = self.cargo_hold_mut().unload(kg)?;
+        Ok(format!(
+            "{} unloads {} kg ({} kg left on board)",
+            self.name(),
+            kg,
+            total
+        ))
+    }
+
+    /// Board passengers, counted at an average weight each
+    fn board_passengers(&mut self, count: u32) -> Result<String, PayloadError> {
+        self.cargo_hold_mut()
+            .load(count.saturating_mul(PASSENGER_WEIGHT_KG))?;
+        Ok(format!("{} boards {} passengers", self.name(), count))
+    }
+
+    /// Let passengers get off
+    fn disembark_passengers(&mut self, count: u32) -> Result<String, PayloadError> {
+        self.cargo_hold_mut()
+            .unload(count.saturating_mul(PASSENGER_WEIGHT_KG))?;
+        Ok(format!("{} lets {} passengers off", self.name(), count))
+    }
+
+    /// Check if the vehicle has room for `kg` more
+    fn can_carry(&self, kg: u32) -> bool {
+        kg <= self.cargo_hold().free_kg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_and_unload() {
+        let mut hold = CargoHold::new(1000);
+
+        assert_eq!(hold.load(600), Ok(600));
+        assert_eq!(hold.load_percentage(), 60);
+        assert_eq!(hold.unload(100), Ok(500));
+        assert_eq!(hold.free_kg(), 500);
+    }
+
+    #[test]
+    fn test_overloading_is_rejected() {
+        let mut hold = CargoHold::new(1000);
+        hold.load(800).unwrap();
+
+        assert_eq!(
+            hold.load(300),
+            Err(PayloadError::Overloaded {
+                requested: 300,
+                current: 800,
+                capacity: 1000
+            })
+        );
+        assert_eq!(hold.load_kg(), 800); // Nothing was loaded
+        assert!(hold.unload(900).is_err());
+    }
+
+    #[test]
+    fn test_load_reduces_performance() {
+        let mut hold = CargoHold::new(1000);
+        assert_eq!(hold.loaded_speed(200), 200);
+
+        hold.load(500).unwrap();
+        assert_eq!(hold.loaded_speed(200), 170); // 15% slower
+        assert_eq!(hold.loaded_altitude(10000), 8000); // 20% lower
+        assert_eq!(hold.loaded_efficiency(100), 83); // 17% less efficient
+
+        hold.load(500).unwrap();
+        assert_eq!(hold.loaded_speed(200), 140);
+        assert_eq!(hold.loaded_altitude(10000), 6000);
+        assert_eq!(hold.loaded_efficiency(1), 1);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut hold = CargoHold::new(0);
+        assert_eq!(hold.load_percentage(), 0);
+        assert!(hold.load(1).is_err());
+        assert!(hold.load(0).is_ok());
+    }
+}
@@ -1,6 +1,24 @@
-use crate::behaviors::{moving::Moving, swimming::Swimming};
-use crate::core::{EnergyLevel, HasEnergy};
+use crate::behaviors::{
+    moving::Moving,
+    participant::Participant,
+    sensing::{Sensing, Sensor, SensorKind},
+    swimming::{Swimming, SwimmingError},
+};
+use crate::core::{
+    AirSupply, DiveState, EnergyLevel, HasEnergy, Mishap, SeededRng, ThermalRange, ThermalTolerance,
+};
+use crate::crew::{Crewed, License, Operator, VehicleClass};
 use crate::vehicles::Vehicle;
+use crate::vehicles::carrier::{Carrier, PassengerDeck};
+use crate::vehicles::maintenance::{Condition, Maintainable, mechanical_reason};
+use crate::vehicles::payload::{CargoHold, Payload};
+use crate::world::infrastructure::{EnergySupply, Requirement, UsesInfrastructure};
+
+/// Kilometers between scheduled services
+const SERVICE_INTERVAL_KM: u32 = 100_000;
+
+/// Distance of a typical passage, logged as hull and engine wear
+const PASSAGE_KM: u32 = 200;
 
 #[derive(Debug)]
 pub struct Ship {
@@ -11,6 +29,11 @@ pub struct Ship {
     ship_type: ShipType,
     displacement: u32, // in tons
     propulsion: PropulsionType,
+    dive_state: DiveState,
+    condition: Condition,
+    cargo_hold: CargoHold,
+    passenger_deck: PassengerDeck,
+    crew: Option<Box<dyn Operator>>,
 }
 
 #[derive(Debug, Clone)]
@@ -46,6 +69,56 @@ pub enum PropulsionType {
     },
 }
 
+impl PropulsionType {
+    /// Temperatures the propulsion works in without extra consumption
+    pub fn thermal_range(&self) -> ThermalRange {
+        match self {
+            PropulsionType::Nuclear { .. } => ThermalRange::new(-50, 50),
+            PropulsionType::Electric { .. } => ThermalRange::new(-5, 45),
+            _ => ThermalRange::new(-30, 50),
+        }
+    }
+
+    /// Supplies the propulsion refuels from - none for reactors and sails
+    pub fn energy_supplies(&self) -> Vec<EnergySupply> {
+        match self {
+            PropulsionType::Diesel { .. } => vec![EnergySupply::Fuel],
+            PropulsionType::Electric { .. } => vec![EnergySupply::Charging],
+            PropulsionType::Nuclear { .. } | PropulsionType::Wind { .. } => Vec::new(),
+            PropulsionType::Hybrid { primary, secondary } => {
+                let mut supplies = primary.energy_supplies();
+                for supply in secondary.energy_supplies() {
+                    if !supplies.contains(&supply) {
+                        supplies.push(supply);
+                    }
+                }
+                supplies
+            }
+        }
+    }
+}
+
+impl ShipType {
+    /// Share of the displacement that can be used for cargo and passengers
+    pub fn payload_share_percent(&self) -> u32 {
+        match self {
+            ShipType::CargoShip => 60, // Built around the hold
+            ShipType::CruiseShip => 10,
+            ShipType::Warship => 5,
+            ShipType::Yacht => 5,
+            ShipType::Ferry => 25, // Cars and passengers
+            ShipType::Submarine => 2,
+            ShipType::Speedboat => 20,
+        }
+    }
+
+    /// Payload capacity in kg for a ship of the given displacement (tons)
+    pub fn payload_capacity_kg(&self, displacement: u32) -> u32 {
+        let capacity = displacement as u64 * 1000 * self.payload_share_percent() as u64 / 100;
+        capacity.min(u32::MAX as u64) as u32
+    }
+}
+
 impl Ship {
     pub fn new(
         name: String,
@@ -55,6 +128,9 @@ impl Ship {
         displacement: u32,
         propulsion: PropulsionType,
     ) -> Self {
+        let condition = Condition::new(SERVICE_INTERVAL_KM, SeededRng::from_label(&name));
+        let cargo_hold = CargoHold::new(ship_type.payload_capacity_kg(displacement));
+
         Self {
             name,
             manufacturer,
@@ -63,6 +139,11 @@ impl Ship {
             ship_type,
             displacement,
             propulsion,
+            dive_state: DiveState::at_surface(),
+            condition,
+            cargo_hold,
+            passenger_deck: PassengerDeck::new(),
+            crew: None,
         }
     }
 
@@ -78,9 +159,9 @@ impl Ship {
         &self.propulsion
     }
 
-    /// Refuel the ship
-    pub fn refuel(&mut self) {
-        self.energy = EnergyLevel::Hyperactive;
+    /// Depth of the hull below the waterline in meters, growing with displacement
+    pub fn draft_m(&self) -> u32 {
+        ((self.displacement as f64).cbrt() / 2.0).ceil().max(1.0) as u32
     }
 }
 
@@ -138,9 +219,153 @@ impl HasEnergy for Ship {
     }
 }
 
-impl Moving for Ship {}
+impl ThermalTolerance for Ship {
+    fn thermal_range(&self) -> ThermalRange {
+        self.propulsion.thermal_range()
+    }
+}
+
+impl Maintainable for Ship {
+    fn condition(&self) -> &Condition {
+        &self.condition
+    }
+
+    fn condition_mut(&mut self) -> &mut Condition {
+        &mut self.condition
+    }
+
+    fn service_cost_per_point(&self) -> u32 {
+        200 // Dry dock time is expensive
+    }
+}
+
+impl Payload for Ship {
+    fn cargo_hold(&self) -> &CargoHold {
+        &self.cargo_hold
+    }
+
+    fn cargo_hold_mut(&mut self) -> &mut CargoHold {
+        &mut self.cargo_hold
+    }
+
+    fn empty_weight_kg(&self) -> u32 {
+        // Displacement is measured fully laden
+        (self.displacement.saturating_mul(1000)).saturating_sub(self.cargo_hold.capacity_kg())
+    }
+}
+
+impl Carrier for Ship {
+    fn passenger_deck(&self) -> &PassengerDeck {
+        &self.passenger_deck
+    }
+
+    fn passenger_deck_mut(&mut self) -> &mut PassengerDeck {
+        &mut self.passenger_deck
+    }
+}
+
+impl Crewed for Ship {
+    fn vehicle_class(&self) -> VehicleClass {
+        VehicleClass::Ship
+    }
+
+    fn required_license(&self) -> License {
+        License::Maritime
+    }
+
+    fn crew_seat(&self) -> &Option<Box<dyn Operator>> {
+        &self.crew
+    }
+
+    fn crew_seat_mut(&mut self) -> &mut Option<Box<dyn Operator>> {
+        &mut self.crew
+    }
+}
+
+impl Participant for Ship {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    fn weight_kg(&self) -> u32 {
+        self.gross_weight_kg()
+    }
+
+    fn as_infrastructure_user(&self) -> Option<&dyn UsesInfrastructure> {
+        Some(self)
+    }
+
+    fn as_carrier(&self) -> Option<&dyn Carrier> {
+        Some(self)
+    }
+
+    fn as_thermal(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn as_swimmer(&self) -> Option<&dyn Swimming> {
+        Some(self)
+    }
+
+    fn as_sensor(&self) -> Option<&dyn Sensing> {
+        Some(self)
+    }
+}
+
+impl UsesInfrastructure for Ship {
+    fn requirement(&self) -> Requirement {
+        Requirement::Berth {
+            draft_m: self.draft_m(),
+        }
+    }
+
+    fn energy_supplies(&self) -> Vec<EnergySupply> {
+        self.propulsion.energy_supplies()
+    }
+}
+
+impl Sensing for Ship {
+    fn sensors(&self) -> Vec<Sensor> {
+        match self.ship_type {
+            ShipType::Submarine => vec![Sensor::new(SensorKind::Sonar, 10_000)],
+            ShipType::Warship => vec![Sensor::new(SensorKind::Sonar, 8_000)],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Moving for Ship {
+    fn thermal_tolerance(&self) -> Option<&dyn ThermalTolerance> {
+        Some(self)
+    }
+
+    fn suffer(&mut self, mishap: Mishap) {
+        self.take_damage(mishap.severity());
+    }
+}
 
 impl Swimming for Ship {
+    fn check_swimming_conditions(&self) -> Result<(), SwimmingError> {
+        self.check_condition()
+            .map_err(|error| SwimmingError::MechanicalFailure {
+                reason: mechanical_reason(error),
+            })?;
+        self.check_crew()
+            .map_err(|error| SwimmingError::CrewNotFit {
+                reason: error.to_string(),
+            })
+    }
+
+    fn log_swim(&mut self) -> Result<(), SwimmingError> {
+        self.log_crew_duty(PASSAGE_KM);
+        let speed_kmh = self.swimming_speed();
+        self.record_wear(PASSAGE_KM, speed_kmh, 1).map_err(|error| {
+            SwimmingError::MechanicalFailure {
+                reason: mechanical_reason(error),
+            }
+        })
+    }
+
     fn max_depth(&self) -> u32 {
         match self.ship_type {
             ShipType::Submarine => 300, // Can dive deep
@@ -149,4 +374,156 @@ impl Swimming for Ship {
             _ => 0,                     // Most ships stay on surface
         }
     }
+
+    fn swimming_speed(&self) -> u32 {
+        let cruising_speed = match self.ship_type {
+            ShipType::CargoShip => 25,
+            ShipType::CruiseShip => 40,
+            ShipType::Warship => 55,
+            ShipType::Yacht => 30,
+            ShipType::Ferry => 35,
+            ShipType::Submarine => 40,
+            ShipType::Speedboat => 80,
+        };
+
+        self.cargo_hold.loaded_speed(cruising_speed)
+    }
+
+    fn dive_state(&self) -> DiveState {
+        self.dive_state
+    }
+
+    fn set_dive_state(&mut self, state: DiveState) {
+        self.dive_state = state;
+    }
+
+    fn air_supply(&self) -> AirSupply {
+        match self.ship_type {
+            ShipType::Submarine => {
+                // Submerged endurance comes from batteries, unless nuclear powered
+                let battery_seconds = match self.propulsion {
+                    PropulsionType::Nuclear { .. } => u32::MAX,
+                    _ => 48 * 3600,
+                };
+                // Larger hulls carry more ballast tanks
+                let ballast_tanks = (self.displacement / 1000).clamp(1, 10) as u8;
+
+                AirSupply::Ballast {
+                    battery_seconds,
+                    ballast_tanks,
+                }
+            }
+            _ => AirSupply::Surface,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crew::CrewMember;
+
+    fn ship(ship_type: ShipType, propulsion: PropulsionType) -> Ship {
+        Ship::new(
+            "Test".to_string(),
+            "Test Yard".to_string(),
+            2020,
+            ship_type,
+            3000,
+            propulsion,
+        )
+    }
+
+    #[test]
+    fn test_submarine_uses_ballast_and_battery() {
+        let diesel_sub = ship(
+            ShipType::Submarine,
+            PropulsionType::Diesel {
+                engines: 2,
+                power_each: 1000,
+            },
+        );
+        assert_eq!(
+            diesel_sub.air_supply(),
+            AirSupply::Ballast {
+                battery_seconds: 48 * 3600,
+                ballast_tanks: 3,
+            }
+        );
+
+        let nuclear_sub = ship(ShipType::Submarine, PropulsionType::Nuclear { reactors: 1 });
+        assert_eq!(nuclear_sub.air_supply().capacity_seconds(), u32::MAX);
+
+        let ferry = ship(ShipType::Ferry, PropulsionType::Nuclear { reactors: 1 });
+        assert_eq!(ferry.air_supply(), AirSupply::Surface);
+    }
+
+    #[test]
+    fn test_payload_capacity_from_displacement() {
+        let diesel = PropulsionType::Diesel {
+            engines: 2,
+            power_each: 1000,
+        };
+
+        let cargo_ship = ship(ShipType::CargoShip, diesel.clone());
+        assert_eq!(cargo_ship.payload_capacity(), 1_800_000); // 60% of 3000 t
+
+        let mut yacht = ship(ShipType::Yacht, diesel);
+        assert_eq!(yacht.payload_capacity(), 150_000);
+        assert!(yacht.load_cargo(150_001).is_err());
+    }
+
+    #[test]
+    fn test_passages_tire_the_skipper() {
+        let mut ferry = ship(
+            ShipType::Ferry,
+            PropulsionType::Diesel {
+                engines: 2,
+                power_each: 1000,
+            },
+        );
+        let skipper = CrewMember::new("Alex".to_string())
+            .with_license(License::Maritime)
+            .with_skill(VehicleClass::Ship, 4);
+        ferry.assign_operator(Box::new(skipper)).unwrap();
+
+        for _ in 0..2 {
+            ferry.set_energy(EnergyLevel::Hyperactive);
+            ferry.swim().unwrap();
+        }
+        let skipper = ferry.operator().unwrap();
+        assert_eq!(skipper.duty_km(), 2 * PASSAGE_KM);
+        assert_eq!(skipper.energy(), EnergyLevel::Tired);
+    }
+
+    #[test]
+    fn test_worn_ship_breaks_down() {
+        let mut ferry = ship(
+            ShipType::Ferry,
+            PropulsionType::Diesel {
+                engines: 2,
+                power_each: 1000,
+            },
+        );
+        ferry.set_energy(EnergyLevel::Hyperactive);
+
+        ferry.swim().unwrap();
+        assert_eq!(ferry.condition().odometer_km(), PASSAGE_KM);
+
+        // The passage wears it below the limit, so the breakdown is certain
+        ferry.condition_mut().set_percent(20);
+        let result = ferry.swim();
+        assert!(matches!(
+            result,
+            Err(SwimmingError::MechanicalFailure { .. })
+        ));
+
+        let energy = ferry.energy();
+        let result = ferry.swim();
+        assert!(matches!(
+            result,
+            Err(SwimmingError::MechanicalFailure { .. })
+        ));
+        assert_eq!(ferry.energy(), energy); // Never left port
+    }
 }
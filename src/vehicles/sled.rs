@@ -0,0 +1,372 @@
+//! Dog Sleds
+//!
+//! A [`Sled`] has no engine: a team of pullers is harnessed in front and
+//! the sled goes as fast as they can pull it. The pulled load - the sled
+//! and its cargo - is shared out by pulling capacity, so a stronger dog
+//! takes a bigger share. With everyone equally loaded, the team's combined
+//! capacity sets the pace: an unloaded team trots at its slowest dog's
+//! walking speed, a fully loaded one at half of it.
+//!
+//! Runners need snow or ice. Each stage of a run costs every dog its
+//! pulling effort, and if any dog cannot pull a stage, the sled stops.
+
+use crate::animals::Dog;
+use crate::behaviors::{
+    Driving, LandMove, Participant, Pulling,
+    driving::DrivingError,
+    moving::{Moving, MovingError},
+    pulling::PullingError,
+};
+use crate::core::{EnergyLevel, HasEnergy, Terrain};
+use crate::vehicles::Vehicle;
+use crate::vehicles::payload::{CargoHold, Payload};
+use thiserror::Error;
+
+/// Weight of an empty sled in kg
+const EMPTY_WEIGHT_KG: u32 = 30;
+
+/// Most cargo a sled's basket holds, in kg
+const CARGO_CAPACITY_KG: u32 = 300;
+
+/// Kilometers in one stage of a run, between rests for the team
+pub const STAGE_KM: u32 = 25;
+
+#[derive(Error, Debug)]
+pub enum SledError {
+    #[error("{sled} has no team harnessed")]
+    NoTeam { sled: String },
+
+    #[error("{sled} needs snow or ice, not {terrain}")]
+    NoSnow { sled: String, terrain: Terrain },
+
+    #[error("{sled} is too heavy: {load_kg} kg for a team that pulls {capacity_kg} kg")]
+    TooHeavy {
+        sled: String,
+        load_kg: u32,
+        capacity_kg: u32,
+    },
+
+    #[error("{sled} stopped after {distance_km} km: {member} cannot pull ({source})")]
+    MemberCannotPull {
+        sled: String,
+        member: String,
+        distance_km: u32,
+        #[source]
+        source: PullingError,
+    },
+}
+
+/// A completed run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SledRun {
+    pub distance_km: u32,
+    pub stages: u32,
+    pub speed_kmh: u32,
+    /// Energy of the most tired dog at the finish
+    pub team_energy: EnergyLevel,
+}
+
+/// A sled pulled by a team of dogs - or any other pullers
+#[derive(Debug)]
+pub struct Sled<T = Dog> {
+    name: String,
+    manufacturer: String,
+    year: u32,
+    team: Vec<T>,
+    cargo_hold: CargoHold,
+}
+
+impl<T: Pulling + Participant> Sled<T> {
+    pub fn new(name: String, manufacturer: String, year: u32) -> Self {
+        Self {
+            name,
+            manufacturer,
+            year,
+            team: Vec::new(),
+            cargo_hold: CargoHold::new(CARGO_CAPACITY_KG),
+        }
+    }
+
+    /// Harness another puller at the front of the team
+    pub fn harness(mut self, member: T) -> Self {
+        self.team.push(member);
+        self
+    }
+
+    /// Take a puller out of the team by name
+    pub fn unharness(&mut self, name: &str) -> Option<T> {
+        let index = self
+            .team
+            .iter()
+            .position(|member| member.participant_name() == name)?;
+        Some(self.team.remove(index))
+    }
+
+    pub fn team(&self) -> &[T] {
+        &self.team
+    }
+
+    /// Combined pulling capacity of the team in kg
+    pub fn team_capacity_kg(&self) -> u32 {
+        self.team.iter().map(|member| member.max_pull_kg()).sum()
+    }
+
+    /// Share of the load a member pulls, by its part of the team's capacity
+    fn share_kg(&self, member: &T) -> u32 {
+        let capacity = self.team_capacity_kg().max(1) as u64;
+        (self.gross_weight_kg() as u64 * member.max_pull_kg() as u64).div_ceil(capacity) as u32
+    }
+
+    /// Speed in km/h the team can pull the sled at - that of its slowest member
+    pub fn speed(&self) -> u32 {
+        self.team
+            .iter()
+            .map(|member| member.pulling_speed(self.share_kg(member)))
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Mush across a distance of snow or ice, stage by stage
+    pub fn run(&mut self, distance_km: u32, terrain: Terrain) -> Result<SledRun, SledError> {
+        if self.team.is_empty() {
+            return Err(SledError::NoTeam {
+                sled: self.name.clone(),
+            });
+        }
+        if !matches!(terrain, Terrain::Snow | Terrain::Glacier) {
+            return Err(SledError::NoSnow {
+                sled: self.name.clone(),
+                terrain,
+            });
+        }
+        let capacity_kg = self.team_capacity_kg();
+        if self.gross_weight_kg() > capacity_kg {
+            return Err(SledError::TooHeavy {
+                sled: self.name.clone(),
+                load_kg: self.gross_weight_kg(),
+                capacity_kg,
+            });
+        }
+
+        let speed_kmh = self.speed();
+        let stages = distance_km.div_ceil(STAGE_KM);
+        for stage in 0..stages {
+            let shares: Vec<u32> = self
+                .team
+                .iter()
+                .map(|member| self.share_kg(member))
+                .collect();
+
+            // Nobody pulls unless everyone can
+            for (member, share) in self.team.iter().zip(&shares) {
+                member.check_pulling(*share, terrain).map_err(|source| {
+                    SledError::MemberCannotPull {
+                        sled: self.name.clone(),
+                        member: member.participant_name(),
+                        distance_km: (stage * STAGE_KM).min(distance_km),
+                        source,
+                    }
+                })?;
+            }
+            for (member, share) in self.team.iter_mut().zip(shares) {
+                member
+                    .pull(share, terrain)
+                    .expect("pulling was checked for the whole team");
+            }
+        }
+
+        Ok(SledRun {
+            distance_km,
+            stages,
+            speed_kmh,
+            team_energy: self.energy(),
+        })
+    }
+}
+
+impl<T> Vehicle for Sled<T> {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn vehicle_type(&self) -> &'static str {
+        "Sled"
+    }
+
+    fn manufacturer(&self) -> &str {
+        &self.manufacturer
+    }
+
+    fn year(&self) -> u32 {
+        self.year
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "{} {} {} ({}, {}-dog team)",
+            self.year,
+            self.manufacturer,
+            self.name,
+            self.vehicle_type(),
+            self.team.len()
+        )
+    }
+}
+
+impl<T> Payload for Sled<T> {
+    fn cargo_hold(&self) -> &CargoHold {
+        &self.cargo_hold
+    }
+
+    fn cargo_hold_mut(&mut self) -> &mut CargoHold {
+        &mut self.cargo_hold
+    }
+
+    fn empty_weight_kg(&self) -> u32 {
+        EMPTY_WEIGHT_KG
+    }
+}
+
+/// A sled is as fresh as its most tired dog; setting its energy sets the whole team's
+impl<T: HasEnergy> HasEnergy for Sled<T> {
+    fn energy(&self) -> EnergyLevel {
+        self.team
+            .iter()
+            .map(|member| member.energy())
+            .min()
+            .unwrap_or(EnergyLevel::Collapsed)
+    }
+
+    fn set_energy(&mut self, level: EnergyLevel) {
+        for member in &mut self.team {
+            member.set_energy(level);
+        }
+    }
+}
+
+impl<T: Pulling + Participant> Participant for Sled<T> {
+    fn participant_name(&self) -> String {
+        self.name()
+    }
+
+    /// The sled, its cargo and the dogs in harness
+    fn weight_kg(&self) -> u32 {
+        let team_kg: u32 = self.team.iter().map(|member| member.weight_kg()).sum();
+        self.gross_weight_kg() + team_kg
+    }
+
+    fn as_driver(&self) -> Option<&dyn Driving> {
+        Some(self)
+    }
+}
+
+impl<T: Pulling + Participant> Moving for Sled<T> {
+    fn check_movement(&self) -> Result<(), MovingError> {
+        if self.team.is_empty() {
+            return Err(MovingError::MovementBlocked {
+                reason: format!("{} has no team harnessed", self.name),
+            });
+        }
+        Ok(())
+    }
+}
+impl<T: Pulling + Participant> LandMove for Sled<T> {}
+
+impl<T: Pulling + Participant> Driving for Sled<T> {
+    fn max_speed(&self) -> u32 {
+        self.speed()
+    }
+
+    /// A sled goes where no road does, as long as there is snow
+    fn has_off_road_capability(&self) -> bool {
+        true
+    }
+
+    fn drive_on_terrain(&mut self, terrain: Terrain) -> Result<String, DrivingError> {
+        match self.run(STAGE_KM, terrain) {
+            Ok(run) => Ok(format!(
+                "{} runs {} km across {} terrain at {} km/h",
+                self.name, run.distance_km, terrain, run.speed_kmh
+            )),
+            Err(SledError::NoSnow { terrain, .. }) => {
+                Err(DrivingError::TerrainNotSupported { terrain })
+            }
+            Err(error) => Err(DrivingError::MovementError(MovingError::MovementBlocked {
+                reason: error.to_string(),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::dog::DogBreed;
+    use crate::animals::{BreedMix, Dog};
+
+    fn husky_team(size: usize) -> Sled {
+        (0..size).fold(
+            Sled::new("Iditarod".to_string(), "Prairie Sleds".to_string(), 2022),
+            |sled, index| sled.harness(Dog::new(format!("Husky {}", index), DogBreed::Husky)),
+        )
+    }
+
+    #[test]
+    fn test_team_pulls_by_combined_capacity() {
+        let mut sled = husky_team(6);
+        assert_eq!(sled.team_capacity_kg(), 276);
+        assert_eq!(sled.speed(), 6); // Only the empty sled behind
+
+        sled.load_cargo(200).unwrap();
+        assert_eq!(sled.speed(), 4);
+
+        // Bulldogs are no sled dogs
+        let sled = Sled::new("Pram".to_string(), "Home Made".to_string(), 2024)
+            .harness(Dog::new("Tank".to_string(), DogBreed::Bulldog))
+            .harness(Dog::new("Brick".to_string(), DogBreed::Bulldog));
+        assert_eq!(sled.team_capacity_kg(), 18);
+        let mut sled = sled;
+        assert!(matches!(
+            sled.run(10, Terrain::Snow),
+            Err(SledError::TooHeavy { load_kg: 30, .. })
+        ));
+
+        // A Husky mix still pulls more than its own weight
+        let mix = BreedMix::of(&[(DogBreed::Husky, 50), (DogBreed::Labrador, 50)]).unwrap();
+        let dog = Dog::mixed("Juneau".to_string(), mix);
+        assert_eq!(dog.max_pull_kg(), 28 * 140 / 100);
+    }
+
+    #[test]
+    fn test_load_and_terrain_drain_the_team() {
+        let mut light = husky_team(6);
+        let run = light.run(50, Terrain::Snow).unwrap();
+        assert_eq!(run.stages, 2);
+        assert_eq!(run.team_energy, EnergyLevel::Tired);
+
+        let mut heavy = husky_team(6);
+        heavy.load_cargo(200).unwrap();
+        let error = heavy.run(75, Terrain::Snow).unwrap_err();
+        assert!(matches!(
+            error,
+            SledError::MemberCannotPull {
+                distance_km: 50,
+                ..
+            }
+        ));
+        assert_eq!(heavy.energy(), EnergyLevel::Collapsed);
+
+        let mut glacier = husky_team(6);
+        assert!(glacier.run(25, Terrain::Glacier).is_ok());
+        assert_eq!(glacier.energy(), EnergyLevel::Tired);
+
+        assert!(matches!(
+            husky_team(6).run(25, Terrain::Road),
+            Err(SledError::NoSnow { .. })
+        ));
+        assert!(matches!(
+            husky_team(0).run(25, Terrain::Snow),
+            Err(SledError::NoTeam { .. })
+        ));
+    }
+}
@@ -0,0 +1,453 @@
+//! Infrastructure
+//!
+//! Vehicles do not take off, land, dock or refuel just anywhere. A [`Site`]
+//! on the map bundles the [`Facility`]s found there - an airport is a runway
+//! and a fuel station, a harbour a port and perhaps a helipad. Each vehicle
+//! states what it needs through [`UsesInfrastructure`]: a runway long enough
+//! for its type and wingspan, a helipad, or a berth deeper than its draft.
+//! Refuelling needs a station with the right supply; a nuclear submarine or
+//! a sailing yacht needs none at all.
+
+use crate::behaviors::flying::{Flying, FlyingError};
+use crate::core::{EnergyLevel, HasEnergy};
+use crate::vehicles::Vehicle;
+use crate::world::map::Position;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum InfrastructureError {
+    #[error("{vehicle} needs a runway, and {site} has none")]
+    NoRunway { vehicle: String, site: String },
+
+    #[error("{vehicle} needs {required_m} m of runway, {site} has {length_m} m")]
+    RunwayTooShort {
+        vehicle: String,
+        site: String,
+        length_m: u32,
+        required_m: u32,
+    },
+
+    #[error("{vehicle} needs a helipad or runway, and {site} has neither")]
+    NoHelipad { vehicle: String, site: String },
+
+    #[error("{vehicle} needs a port, and {site} has none")]
+    NoPort { vehicle: String, site: String },
+
+    #[error("{vehicle} draws {draft_m} m, the port at {site} is {depth_m} m deep")]
+    TooShallow {
+        vehicle: String,
+        site: String,
+        depth_m: u32,
+        draft_m: u32,
+    },
+
+    #[error("{vehicle} needs {supply}, which {site} does not offer")]
+    NoSupply {
+        vehicle: String,
+        site: String,
+        supply: EnergySupply,
+    },
+
+    #[error("{vehicle} never needs refuelling")]
+    NoRefuellingNeeded { vehicle: String },
+
+    #[error("Cannot take off: {0}")]
+    FlyingError(#[from] FlyingError),
+}
+
+/// How a vehicle's tank or battery gets filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnergySupply {
+    Fuel,
+    Charging,
+}
+
+impl fmt::Display for EnergySupply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EnergySupply::Fuel => "Fuel",
+            EnergySupply::Charging => "Charging",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A piece of infrastructure at a site
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Facility {
+    Runway {
+        length_m: u32,
+    },
+    Helipad,
+    /// A berth with this much water alongside
+    Port {
+        depth_m: u32,
+    },
+    Station {
+        supply: EnergySupply,
+    },
+}
+
+/// A place on the map and the facilities found there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Site {
+    pub name: String,
+    pub position: Position,
+    pub facilities: Vec<Facility>,
+}
+
+impl Site {
+    pub fn new(name: &str, position: Position) -> Self {
+        Self {
+            name: name.to_string(),
+            position,
+            facilities: Vec::new(),
+        }
+    }
+
+    /// Add a facility to the site
+    pub fn with(mut self, facility: Facility) -> Self {
+        self.facilities.push(facility);
+        self
+    }
+
+    /// Length of the longest runway, if there is one
+    pub fn longest_runway_m(&self) -> Option<u32> {
+        self.facilities
+            .iter()
+            .filter_map(|facility| match facility {
+                Facility::Runway { length_m } => Some(*length_m),
+                _ => None,
+            })
+            .max()
+    }
+
+    pub fn has_helipad(&self) -> bool {
+        self.facilities.contains(&Facility::Helipad)
+    }
+
+    /// Depth of the deepest berth, if there is a port
+    pub fn deepest_berth_m(&self) -> Option<u32> {
+        self.facilities
+            .iter()
+            .filter_map(|facility| match facility {
+                Facility::Port { depth_m } => Some(*depth_m),
+                _ => None,
+            })
+            .max()
+    }
+
+    pub fn supplies(&self, supply: EnergySupply) -> bool {
+        self.facilities.contains(&Facility::Station { supply })
+    }
+}
+
+/// What a vehicle needs to set off from a site or arrive at it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Requirement {
+    /// Goes anywhere its wheels or tracks take it
+    Nothing,
+    Runway {
+        length_m: u32,
+    },
+    /// A helipad - or a runway, which does just as well
+    Helipad,
+    /// A port with more water than the vehicle draws
+    Berth {
+        draft_m: u32,
+    },
+}
+
+/// Trait for vehicles that depend on infrastructure
+pub trait UsesInfrastructure: Vehicle + HasEnergy {
+    /// What the vehicle needs from a site to take off, land or dock
+    fn requirement(&self) -> Requirement;
+
+    /// Supplies the vehicle can refuel from - none if it never needs to
+    fn energy_supplies(&self) -> Vec<EnergySupply>;
+
+    /// Check if the site can handle this vehicle
+    fn check_site(&self, site: &Site) -> Result<(), InfrastructureError> {
+        let vehicle = self.name();
+        let site_name = site.name.clone();
+        match self.requirement() {
+            Requirement::Nothing => Ok(()),
+            Requirement::Runway { length_m } => match site.longest_runway_m() {
+                None => Err(InfrastructureError::NoRunway {
+                    vehicle,
+                    site: site_name,
+                }),
+                Some(available) if available < length_m => {
+                    Err(InfrastructureError::RunwayTooShort {
+                        vehicle,
+                        site: site_name,
+                        length_m: available,
+                        required_m: length_m,
+                    })
+                }
+                Some(_) => Ok(()),
+            },
+            Requirement::Helipad => {
+                if site.has_helipad() || site.longest_runway_m().is_some() {
+                    Ok(())
+                } else {
+                    Err(InfrastructureError::NoHelipad {
+                        vehicle,
+                        site: site_name,
+                    })
+                }
+            }
+            Requirement::Berth { draft_m } => match site.deepest_berth_m() {
+                None => Err(InfrastructureError::NoPort {
+                    vehicle,
+                    site: site_name,
+                }),
+                Some(depth_m) if depth_m <= draft_m => Err(InfrastructureError::TooShallow {
+                    vehicle,
+                    site: site_name,
+                    depth_m,
+                    draft_m,
+                }),
+                Some(_) => Ok(()),
+            },
+        }
+    }
+
+    /// Land, dock or park at a site
+    fn arrive_at(&self, site: &Site) -> Result<String, InfrastructureError> {
+        self.check_site(site)?;
+        let verb = match self.requirement() {
+            Requirement::Runway { .. } | Requirement::Helipad => "lands at",
+            Requirement::Berth { .. } => "docks at",
+            Requirement::Nothing => "pulls up at",
+        };
+        Ok(format!("{} {} {}", self.name(), verb, site.name))
+    }
+
+    /// Take off from a site - only with the right facilities there
+    fn take_off_from(&mut self, site: &Site) -> Result<String, InfrastructureError>
+    where
+        Self: Flying + Sized,
+    {
+        self.check_site(site)?;
+        self.fly()?;
+        Ok(format!("{} takes off from {}", self.name(), site.name))
+    }
+
+    /// Fill up at the site's station, once the vehicle is able to be there
+    fn refuel(&mut self, site: &Site) -> Result<String, InfrastructureError> {
+        let supplies = self.energy_supplies();
+        let Some(first) = supplies.first().copied() else {
+            return Err(InfrastructureError::NoRefuellingNeeded {
+                vehicle: self.name(),
+            });
+        };
+        self.check_site(site)?;
+
+        let supply = supplies
+            .into_iter()
+            .find(|supply| site.supplies(*supply))
+            .ok_or_else(|| InfrastructureError::NoSupply {
+                vehicle: self.name(),
+                site: site.name.clone(),
+                supply: first,
+            })?;
+        self.set_energy(EnergyLevel::Hyperactive);
+        Ok(format!(
+            "{} fills up with {} at {}",
+            self.name(),
+            supply,
+            site.name
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vehicles::airplane::{AirplaneEngine, AirplaneType};
+    use crate::vehicles::car::EngineType;
+    use crate::vehicles::helicopter::{HelicopterEngine, HelicopterType};
+    use crate::vehicles::motorcycle::MotorcycleType;
+    use crate::vehicles::ship::{PropulsionType, ShipType};
+    use crate::vehicles::{Airplane, Car, Helicopter, Motorcycle, Ship};
+
+    fn airport(runway_m: u32) -> Site {
+        Site::new("Airport", Position::new(0, 0))
+            .with(Facility::Runway { length_m: runway_m })
+            .with(Facility::Station {
+                supply: EnergySupply::Fuel,
+            })
+    }
+
+    fn harbour(depth_m: u32) -> Site {
+        Site::new("Harbour", Position::new(5, 0))
+            .with(Facility::Port { depth_m })
+            .with(Facility::Station {
+                supply: EnergySupply::Fuel,
+            })
+    }
+
+    fn airplane(airplane_type: AirplaneType, wingspan: u32) -> Airplane {
+        Airplane::new(
+            "Test".to_string(),
+            "Test Aero".to_string(),
+            2022,
+            airplane_type,
+            wingspan,
+            AirplaneEngine::Turboprop {
+                engines: 2,
+                power_each: 1500,
+            },
+        )
+    }
+
+    #[test]
+    fn test_runway_length_grows_with_the_airplane() {
+        let mut airliner = airplane(AirplaneType::Commercial, 35);
+        assert_eq!(airliner.required_runway_m(), 2150);
+        assert!(matches!(
+            airliner.take_off_from(&airport(1800)),
+            Err(InfrastructureError::RunwayTooShort {
+                required_m: 2150,
+                ..
+            })
+        ));
+        assert!(airliner.take_off_from(&airport(3000)).is_ok());
+        assert!(airliner.can_taxi_at(&airport(1800)));
+        assert!(!airliner.can_taxi_at(&harbour(10)));
+
+        let trainer = airplane(AirplaneType::Private, 11);
+        assert!(trainer.arrive_at(&airport(1800)).is_ok());
+
+        // A seaplane needs water, not concrete
+        let seaplane = airplane(AirplaneType::Seaplane, 15);
+        assert!(seaplane.can_taxi_at(&harbour(3)));
+        assert!(matches!(
+            seaplane.arrive_at(&airport(3000)),
+            Err(InfrastructureError::NoPort { .. })
+        ));
+
+        let helicopter = Helicopter::new(
+            "Medevac".to_string(),
+            "Airbus".to_string(),
+            2019,
+            HelicopterType::Emergency,
+            11,
+            HelicopterEngine::Turboshaft {
+                engines: 2,
+                power_each: 600,
+            },
+        );
+        let hospital = Site::new("Hospital", Position::new(2, 2)).with(Facility::Helipad);
+        assert!(helicopter.arrive_at(&hospital).is_ok());
+        assert!(helicopter.arrive_at(&airport(800)).is_ok());
+        assert!(matches!(
+            helicopter.arrive_at(&harbour(10)),
+            Err(InfrastructureError::NoHelipad { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ports_limit_the_draft() {
+        let freighter = Ship::new(
+            "Ever Given".to_string(),
+            "Imabari".to_string(),
+            2018,
+            ShipType::CargoShip,
+            100_000,
+            PropulsionType::Diesel {
+                engines: 1,
+                power_each: 60_000,
+            },
+        );
+        assert_eq!(freighter.draft_m(), 24);
+        assert!(matches!(
+            freighter.arrive_at(&harbour(15)),
+            Err(InfrastructureError::TooShallow {
+                depth_m: 15,
+                draft_m: 24,
+                ..
+            })
+        ));
+        assert_eq!(
+            freighter.arrive_at(&harbour(30)).unwrap(),
+            "Ever Given docks at Harbour"
+        );
+    }
+
+    #[test]
+    fn test_refuelling_needs_the_right_station() {
+        let mut electric = Car::new(
+            "Volt".to_string(),
+            "Tesla".to_string(),
+            2023,
+            EngineType::Electric {
+                battery_capacity: 75,
+            },
+        );
+        electric.set_energy(EnergyLevel::Tired);
+        let petrol_station =
+            Site::new("Petrol Station", Position::new(1, 1)).with(Facility::Station {
+                supply: EnergySupply::Fuel,
+            });
+        assert!(matches!(
+            electric.refuel(&petrol_station),
+            Err(InfrastructureError::NoSupply {
+                supply: EnergySupply::Charging,
+                ..
+            })
+        ));
+        let services = petrol_station.with(Facility::Station {
+            supply: EnergySupply::Charging,
+        });
+        assert!(electric.refuel(&services).is_ok());
+        assert_eq!(electric.energy(), EnergyLevel::Hyperactive);
+
+        let mut scooter = Motorcycle::new(
+            "Zero".to_string(),
+            "Zero Motorcycles".to_string(),
+            2023,
+            0,
+            MotorcycleType::Electric,
+        );
+        assert!(scooter.arrive_at(&harbour(3)).is_ok()); // Parks anywhere
+        assert!(matches!(
+            scooter.refuel(&harbour(3)),
+            Err(InfrastructureError::NoSupply { .. })
+        ));
+        assert!(scooter.refuel(&services).is_ok());
+
+        // A ship only refuels where it can dock
+        let mut ferry = Ship::new(
+            "Island Hopper".to_string(),
+            "Damen".to_string(),
+            2018,
+            ShipType::Ferry,
+            3000,
+            PropulsionType::Diesel {
+                engines: 2,
+                power_each: 2000,
+            },
+        );
+        assert!(matches!(
+            ferry.refuel(&airport(2000)),
+            Err(InfrastructureError::NoPort { .. })
+        ));
+        assert!(ferry.refuel(&harbour(12)).is_ok());
+
+        let mut submarine = Ship::new(
+            "Nautilus".to_string(),
+            "General Dynamics".to_string(),
+            2015,
+            ShipType::Submarine,
+            7000,
+            PropulsionType::Nuclear { reactors: 1 },
+        );
+        assert!(matches!(
+            submarine.refuel(&harbour(20)),
+            Err(InfrastructureError::NoRefuellingNeeded { .. })
+        ));
+    }
+}
@@ -0,0 +1,344 @@
+use crate::core::{Terrain, Weather};
+use crate::routes::SegmentKind;
+use std::fmt;
+use thiserror::Error;
+
+/// Water at or below this depth (meters) can be waded through
+pub const WADING_DEPTH: u32 = 1;
+
+/// Depth used for `~` tiles in text maps
+const DEEP_WATER_DEPTH: u32 = 10;
+
+/// A cell on the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Land(Terrain),
+    Water {
+        depth: u32,
+    },
+    /// Open sky with nothing to stand on (chasms, cloud banks)
+    Sky,
+}
+
+impl Tile {
+    /// What an entity faces when crossing this tile by swimming, driving or flying
+    pub fn segment_kind(&self) -> SegmentKind {
+        match self {
+            Tile::Land(terrain) => SegmentKind::Land(*terrain),
+            Tile::Water { depth } => SegmentKind::Water { depth: *depth },
+            Tile::Sky => SegmentKind::Air { altitude: 0 },
+        }
+    }
+
+    /// What a swimmer faces: water tiles are crossed at the surface, whatever their depth
+    pub fn swimming_kind(&self) -> SegmentKind {
+        match self {
+            Tile::Water { .. } => SegmentKind::Water { depth: 0 },
+            _ => self.segment_kind(),
+        }
+    }
+
+    /// Terrain a walker faces when wading through shallow water
+    pub fn wading_terrain(&self) -> Option<Terrain> {
+        match self {
+            Tile::Water { depth } if *depth <= WADING_DEPTH => Some(Terrain::Swamp),
+            _ => None,
+        }
+    }
+
+    /// Character used for this tile in text maps
+    pub fn symbol(&self) -> char {
+        match self {
+            Tile::Land(terrain) => match terrain {
+                Terrain::Road | Terrain::Pavement | Terrain::Sidewalk => '=',
+                Terrain::Grass => '.',
+                Terrain::Dirt => ':',
+                Terrain::Gravel | Terrain::Sand => 's',
+                Terrain::Rocky => 'r',
+                Terrain::Muddy => 'm',
+                Terrain::Snow => '*',
+                Terrain::Forest => 'T',
+                Terrain::Steep | Terrain::Mountain | Terrain::Extreme => '^',
+                Terrain::Swamp => 'w',
+                Terrain::Desert => 'd',
+                Terrain::Cliff => '#',
+                Terrain::Glacier => 'g',
+                Terrain::Volcano => 'v',
+            },
+            Tile::Water { depth } if *depth <= WADING_DEPTH => ',',
+            Tile::Water { .. } => '~',
+            Tile::Sky => ' ',
+        }
+    }
+
+    /// Parse a text map character
+    pub fn from_symbol(symbol: char) -> Option<Tile> {
+        let tile = match symbol {
+            '=' => Tile::Land(Terrain::Road),
+            '.' => Tile::Land(Terrain::Grass),
+            ':' => Tile::Land(Terrain::Dirt),
+            's' => Tile::Land(Terrain::Sand),
+            'r' => Tile::Land(Terrain::Rocky),
+            'm' => Tile::Land(Terrain::Muddy),
+            '*' => Tile::Land(Terrain::Snow),
+            'T' => Tile::Land(Terrain::Forest),
+            '^' => Tile::Land(Terrain::Mountain),
+            'w' => Tile::Land(Terrain::Swamp),
+            'd' => Tile::Land(Terrain::Desert),
+            '#' => Tile::Land(Terrain::Cliff),
+            'g' => Tile::Land(Terrain::Glacier),
+            'v' => Tile::Land(Terrain::Volcano),
+            ',' => Tile::Water {
+                depth: WADING_DEPTH,
+            },
+            '~' => Tile::Water {
+                depth: DEEP_WATER_DEPTH,
+            },
+            ' ' => Tile::Sky,
+            _ => return None,
+        };
+        Some(tile)
+    }
+}
+
+/// A cell coordinate; `x` grows to the east, `y` to the south
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Position {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Number of orthogonal steps between two positions
+    pub fn manhattan_distance(&self, other: Position) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MapError {
+    #[error("Map has no tiles")]
+    Empty,
+
+    #[error("Row {row} has {found} tiles, expected {expected}")]
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("Unknown map symbol '{symbol}' at {position}")]
+    UnknownSymbol { symbol: char, position: Position },
+}
+
+/// A rectangular world of tiles, each `tile_km` wide, with optional weather
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridMap {
+    width: usize,
+    height: usize,
+    tile_km: u32,
+    tiles: Vec<Tile>,
+    weather: Vec<Option<Weather>>,
+}
+
+impl GridMap {
+    /// Create a map filled with one kind of tile
+    pub fn new(width: usize, height: usize, fill: Tile) -> Self {
+        Self {
+            width,
+            height,
+            tile_km: 1,
+            tiles: vec![fill; width * height],
+            weather: vec![None; width * height],
+        }
+    }
+
+    /// Parse a map from text rows (see [`Tile::from_symbol`] for the legend)
+    pub fn from_rows(rows: &[&str]) -> Result<Self, MapError> {
+        let width = rows.first().map(|row| row.chars().count()).unwrap_or(0);
+        if width == 0 {
+            return Err(MapError::Empty);
+        }
+
+        let mut tiles = Vec::with_capacity(width * rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            let found = row.chars().count();
+            if found != width {
+                return Err(MapError::RaggedRow {
+                    row: y,
+                    expected: width,
+                    found,
+                });
+            }
+            for (x, symbol) in row.chars().enumerate() {
+                let tile = Tile::from_symbol(symbol).ok_or(MapError::UnknownSymbol {
+                    symbol,
+                    position: Position::new(x, y),
+                })?;
+                tiles.push(tile);
+            }
+        }
+
+        let mut map = Self::new(width, rows.len(), Tile::Sky);
+        map.tiles = tiles;
+        Ok(map)
+    }
+
+    /// Set the size of one tile in km
+    pub fn with_tile_km(mut self, tile_km: u32) -> Self {
+        self.tile_km = tile_km.max(1);
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn tile_km(&self) -> u32 {
+        self.tile_km
+    }
+
+    /// Check if a position lies on the map
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn tile(&self, position: Position) -> Option<Tile> {
+        self.index(position).map(|index| self.tiles[index])
+    }
+
+    pub fn set_tile(&mut self, position: Position, tile: Tile) {
+        if let Some(index) = self.index(position) {
+            self.tiles[index] = tile;
+        }
+    }
+
+    pub fn weather(&self, position: Position) -> Option<Weather> {
+        self.index(position).and_then(|index| self.weather[index])
+    }
+
+    /// Set the weather over a rectangular region (corners inclusive)
+    pub fn set_weather_region(&mut self, from: Position, to: Position, weather: Weather) {
+        for y in from.y.min(to.y)..=from.y.max(to.y) {
+            for x in from.x.min(to.x)..=from.x.max(to.x) {
+                if let Some(index) = self.index(Position::new(x, y)) {
+                    self.weather[index] = Some(weather);
+                }
+            }
+        }
+    }
+
+    /// Get the orthogonal neighbours of a position that lie on the map
+    pub fn neighbours(&self, position: Position) -> Vec<Position> {
+        let Position { x, y } = position;
+        let mut neighbours = Vec::with_capacity(4);
+        if y > 0 {
+            neighbours.push(Position::new(x, y - 1));
+        }
+        if x + 1 < self.width {
+            neighbours.push(Position::new(x + 1, y));
+        }
+        if y + 1 < self.height {
+            neighbours.push(Position::new(x, y + 1));
+        }
+        if x > 0 {
+            neighbours.push(Position::new(x - 1, y));
+        }
+        neighbours
+    }
+
+    fn index(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y * self.width + position.x)
+    }
+}
+
+impl fmt::Display for GridMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.tiles.chunks(self.width) {
+            let line: String = row.iter().map(Tile::symbol).collect();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_render() {
+        let rows = ["=.~", ",T "];
+        let map = GridMap::from_rows(&rows).unwrap();
+
+        assert_eq!(map.width(), 3);
+        assert_eq!(map.height(), 2);
+        assert_eq!(
+            map.tile(Position::new(2, 0)),
+            Some(Tile::Water { depth: 10 })
+        );
+        assert_eq!(map.tile(Position::new(2, 1)), Some(Tile::Sky));
+        assert_eq!(map.tile(Position::new(3, 0)), None);
+        assert_eq!(map.to_string(), "=.~\n,T \n");
+    }
+
+    #[test]
+    fn test_water_keeps_its_depth() {
+        let deep = Tile::from_symbol('~').unwrap();
+        assert_eq!(deep.segment_kind(), SegmentKind::Water { depth: 10 });
+        assert_eq!(deep.swimming_kind(), SegmentKind::Water { depth: 0 });
+
+        let shallow = Tile::from_symbol(',').unwrap();
+        assert_eq!(
+            shallow.segment_kind(),
+            SegmentKind::Water {
+                depth: WADING_DEPTH
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(GridMap::from_rows(&[]), Err(MapError::Empty));
+        assert!(matches!(
+            GridMap::from_rows(&["==", "="]),
+            Err(MapError::RaggedRow { row: 1, .. })
+        ));
+        assert!(matches!(
+            GridMap::from_rows(&["=?"]),
+            Err(MapError::UnknownSymbol { symbol: '?', .. })
+        ));
+    }
+
+    #[test]
+    fn test_weather_region() {
+        let mut map = GridMap::new(4, 4, Tile::Land(Terrain::Grass));
+        map.set_weather_region(Position::new(2, 2), Position::new(1, 1), Weather::Fog);
+
+        assert_eq!(map.weather(Position::new(1, 2)), Some(Weather::Fog));
+        assert_eq!(map.weather(Position::new(0, 0)), None);
+    }
+
+    #[test]
+    fn test_neighbours_stay_on_map() {
+        let map = GridMap::new(3, 3, Tile::Sky);
+
+        assert_eq!(map.neighbours(Position::new(0, 0)).len(), 2);
+        assert_eq!(map.neighbours(Position::new(1, 1)).len(), 4);
+    }
+}
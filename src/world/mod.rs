@@ -0,0 +1,9 @@
+pub mod infrastructure;
+pub mod map;
+pub mod pathfinding;
+
+pub use infrastructure::{
+    EnergySupply, Facility, InfrastructureError, Requirement, Site, UsesInfrastructure,
+};
+pub use map::{GridMap, MapError, Position, Tile};
+pub use pathfinding::{GridPath, PathError, PathStep, Pathfinder};
@@ -0,0 +1,378 @@
+//! Capability-aware pathfinding
+//!
+//! A* search over a [`GridMap`] using the same cost model as the route
+//! planner: each tile is turned into a [`SegmentKind`] one tile long and
+//! priced with [`RoutePlanner::profile`] for every mode the participant
+//! supports. Shallow water can be waded by walkers, and flying is blocked
+//! in weather that is not safe for flying and costs extra in rough weather.
+//! Weather also shifts the temperature each participant has to endure.
+
+use crate::behaviors::{Participant, TravelMode};
+use crate::routes::{ModeProfile, PlanPreference, RoutePlanner, SegmentKind};
+use crate::world::map::{GridMap, Position};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    #[error("Position {0} is outside the map")]
+    OutOfBounds(Position),
+
+    #[error("{participant} cannot find a path from {from} to {to}")]
+    NoPath {
+        participant: String,
+        from: Position,
+        to: Position,
+    },
+}
+
+/// Entering one tile of a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathStep {
+    pub position: Position,
+    pub mode: TravelMode,
+    pub seconds: u32,
+    /// Energy spent in thousandths of a level
+    pub energy_milli: u32,
+}
+
+/// A path across the map and what it costs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridPath {
+    pub participant: String,
+    pub start: Position,
+    /// Every tile entered after the start, in order
+    pub steps: Vec<PathStep>,
+    pub total_minutes: u32,
+    pub total_energy_levels: u32,
+}
+
+impl GridPath {
+    /// Get every position on the path, including the start
+    pub fn positions(&self) -> Vec<Position> {
+        std::iter::once(self.start)
+            .chain(self.steps.iter().map(|step| step.position))
+            .collect()
+    }
+
+    /// Get the mode used to enter each tile
+    pub fn modes(&self) -> Vec<TravelMode> {
+        self.steps.iter().map(|step| step.mode).collect()
+    }
+}
+
+/// Finds the cheapest path for a participant between two tiles
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pathfinder {
+    pub preference: PlanPreference,
+}
+
+impl Pathfinder {
+    pub fn new(preference: PlanPreference) -> Self {
+        Self { preference }
+    }
+
+    /// Price every mode on a tile - `None` if the participant cannot enter it
+    pub fn step(
+        &self,
+        map: &GridMap,
+        participant: &dyn Participant,
+        position: Position,
+    ) -> Option<PathStep> {
+        let tile = map.tile(position)?;
+        let weather = map.weather(position);
+
+        participant
+            .travel_modes()
+            .into_iter()
+            .filter_map(|mode| {
+                let kind = match (mode, tile.wading_terrain()) {
+                    (TravelMode::Walk, Some(terrain)) => SegmentKind::Land(terrain),
+                    (TravelMode::Swim, _) => tile.swimming_kind(),
+                    _ => tile.segment_kind(),
+                };
+                let temperature_c = kind.temperature_c()
+                    + weather.map_or(0, |weather| weather.temperature_shift_c());
+                let mut profile = RoutePlanner::profile_at(participant, mode, kind, temperature_c)?;
+
+                if mode == TravelMode::Fly
+                    && let Some(weather) = weather
+                {
+                    if !weather.is_safe_for_flying() {
+                        return None;
+                    }
+                    profile.effort += weather.energy_cost() as u32;
+                }
+
+                Some(Self::price(map, position, mode, &profile))
+            })
+            .min_by_key(|step| self.cost(step))
+    }
+
+    /// Find the cheapest path from `from` to `to`
+    pub fn find_path(
+        &self,
+        map: &GridMap,
+        participant: &dyn Participant,
+        from: Position,
+        to: Position,
+    ) -> Result<GridPath, PathError> {
+        for position in [from, to] {
+            if !map.contains(position) {
+                return Err(PathError::OutOfBounds(position));
+            }
+        }
+
+        let no_path = || PathError::NoPath {
+            participant: participant.participant_name(),
+            from,
+            to,
+        };
+
+        // Price every tile once; the cheapest step keeps the heuristic admissible
+        let mut steps = HashMap::new();
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                let position = Position::new(x, y);
+                if let Some(step) = self.step(map, participant, position) {
+                    steps.insert(position, step);
+                }
+            }
+        }
+        let cheapest = steps
+            .values()
+            .map(|step| self.cost(step))
+            .min()
+            .ok_or_else(no_path)?;
+        let heuristic =
+            |position: Position| (position.manhattan_distance(to) as u64).saturating_mul(cheapest);
+
+        let mut open = BinaryHeap::new();
+        let mut best = HashMap::from([(from, 0u64)]);
+        let mut came_from: HashMap<Position, Position> = HashMap::new();
+        open.push(Reverse((heuristic(from), 0u64, from)));
+
+        while let Some(Reverse((_, cost, position))) = open.pop() {
+            if position == to {
+                return Ok(self.build_path(participant, from, to, &came_from, &steps));
+            }
+            if cost > best[&position] {
+                continue; // Stale entry
+            }
+
+            for next in map.neighbours(position) {
+                let Some(step) = steps.get(&next) else {
+                    continue;
+                };
+                let next_cost = cost.saturating_add(self.cost(step));
+                if best.get(&next).is_none_or(|&known| next_cost < known) {
+                    best.insert(next, next_cost);
+                    came_from.insert(next, position);
+                    open.push(Reverse((
+                        next_cost.saturating_add(heuristic(next)),
+                        next_cost,
+                        next,
+                    )));
+                }
+            }
+        }
+
+        Err(no_path())
+    }
+
+    /// Ordering key for a step under the current preference
+    fn cost(&self, step: &PathStep) -> u64 {
+        let (primary, secondary) = match self.preference {
+            PlanPreference::Fastest => (step.seconds, step.energy_milli),
+            PlanPreference::LeastEnergy => (step.energy_milli, step.seconds),
+        };
+        ((primary as u64) << 32) + secondary as u64
+    }
+
+    fn price(
+        map: &GridMap,
+        position: Position,
+        mode: TravelMode,
+        profile: &ModeProfile,
+    ) -> PathStep {
+        // In u64: a large tile or a high effort does not fit in u32
+        let tile_km = map.tile_km() as u64;
+        let seconds = (tile_km * 3600).div_ceil(profile.speed_kmh.max(1) as u64);
+        let energy_milli =
+            (tile_km * profile.effort as u64 * 1000).div_ceil(profile.km_per_level.max(1) as u64);
+        PathStep {
+            position,
+            mode,
+            seconds: u32::try_from(seconds).unwrap_or(u32::MAX),
+            energy_milli: u32::try_from(energy_milli).unwrap_or(u32::MAX),
+        }
+    }
+
+    fn build_path(
+        &self,
+        participant: &dyn Participant,
+        from: Position,
+        to: Position,
+        came_from: &HashMap<Position, Position>,
+        steps: &HashMap<Position, PathStep>,
+    ) -> GridPath {
+        let mut path = Vec::new();
+        let mut position = to;
+        while position != from {
+            path.push(steps[&position]);
+            position = came_from[&position];
+        }
+        path.reverse();
+
+        let seconds = path
+            .iter()
+            .fold(0u32, |total, step| total.saturating_add(step.seconds));
+        let energy_milli = path
+            .iter()
+            .fold(0u32, |total, step| total.saturating_add(step.energy_milli));
+
+        GridPath {
+            participant: participant.participant_name(),
+            start: from,
+            steps: path,
+            total_minutes: seconds.div_ceil(60),
+            total_energy_levels: energy_milli.div_ceil(1000),
+        }
+    }
+}
+
+/// Check if a participant could stand on or cross a tile at all
+pub fn is_passable(map: &GridMap, participant: &dyn Participant, position: Position) -> bool {
+    Pathfinder::default()
+        .step(map, participant, position)
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animals::{Dog, dog::DogBreed};
+    use crate::core::Weather;
+    use crate::vehicles::helicopter::{HelicopterEngine, HelicopterType};
+    use crate::vehicles::{Car, Helicopter, car::EngineType};
+    use crate::world::map::Tile;
+
+    // A lake in the middle, a road around it and a cliff wall with one gap
+    const ROWS: [&str; 5] = [
+        "=========",
+        "=..~~~..=",
+        "=#.~~~.#=",
+        "=#,~~~.#=",
+        "=========",
+    ];
+
+    fn map() -> GridMap {
+        GridMap::from_rows(&ROWS).unwrap()
+    }
+
+    fn car() -> Car {
+        Car::new(
+            "Test".to_string(),
+            "Test Motors".to_string(),
+            2022,
+            EngineType::Electric {
+                battery_capacity: 100,
+            },
+        )
+    }
+
+    #[test]
+    fn test_car_stays_out_of_water() {
+        let map = map();
+        let path = Pathfinder::default()
+            .find_path(&map, &car(), Position::new(2, 2), Position::new(6, 2))
+            .unwrap();
+
+        assert!(path.modes().iter().all(|mode| *mode == TravelMode::Drive));
+        assert!(
+            path.positions()
+                .iter()
+                .all(|position| matches!(map.tile(*position), Some(Tile::Land(_))))
+        );
+        assert_eq!(path.steps.len(), 8); // Up and over the top of the lake
+    }
+
+    #[test]
+    fn test_helicopter_flies_straight() {
+        let helicopter = Helicopter::new(
+            "Test".to_string(),
+            "Test Aero".to_string(),
+            2020,
+            HelicopterType::Civilian,
+            11,
+            HelicopterEngine::Turboshaft {
+                engines: 1,
+                power_each: 500,
+            },
+        );
+        let from = Position::new(1, 2);
+        let to = Position::new(7, 2);
+
+        let path = Pathfinder::default()
+            .find_path(&map(), &helicopter, from, to)
+            .unwrap();
+        assert_eq!(path.steps.len(), from.manhattan_distance(to));
+        assert!(path.modes().iter().all(|mode| *mode == TravelMode::Fly));
+
+        // A thunderstorm over the lake forces a detour along the southern road
+        let mut stormy = map();
+        stormy.set_weather_region(
+            Position::new(2, 0),
+            Position::new(6, 3),
+            Weather::Thunderstorm,
+        );
+        let detour = Pathfinder::default()
+            .find_path(&stormy, &helicopter, from, to)
+            .unwrap();
+        assert_eq!(detour.steps.len(), 10);
+        assert!(detour.positions().contains(&Position::new(4, 4)));
+    }
+
+    #[test]
+    fn test_dog_wades_and_swims() {
+        let dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        let map = map();
+
+        assert!(is_passable(&map, &dog, Position::new(2, 3))); // Shallow water
+        assert!(!is_passable(&map, &dog, Position::new(1, 2))); // Cliff
+
+        let path = Pathfinder::new(PlanPreference::LeastEnergy)
+            .find_path(&map, &dog, Position::new(2, 2), Position::new(6, 2))
+            .unwrap();
+        assert_eq!(path.positions().last(), Some(&Position::new(6, 2)));
+        assert!(path.total_energy_levels > 0);
+        assert!(path.total_minutes > 0);
+    }
+
+    #[test]
+    fn test_huge_tiles_saturate_instead_of_overflowing() {
+        let dog = Dog::new("Rex".to_string(), DogBreed::Labrador);
+        let map = map().with_tile_km(u32::MAX);
+
+        let path = Pathfinder::new(PlanPreference::Fastest)
+            .find_path(&map, &dog, Position::new(2, 2), Position::new(6, 2))
+            .unwrap();
+        assert_eq!(path.positions().last(), Some(&Position::new(6, 2)));
+        assert_eq!(path.total_energy_levels, u32::MAX.div_ceil(1000));
+    }
+
+    #[test]
+    fn test_path_errors() {
+        let map = map();
+        assert_eq!(
+            Pathfinder::default().find_path(&map, &car(), Position::new(0, 0), Position::new(9, 0)),
+            Err(PathError::OutOfBounds(Position::new(9, 0)))
+        );
+
+        assert!(matches!(
+            Pathfinder::default().find_path(&map, &car(), Position::new(0, 0), Position::new(4, 2)),
+            Err(PathError::NoPath { .. })
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+//! Drives a vehicle through the engine lifecycle using only the public API
+
+use rust_traits_examples::behaviors::Driving;
+use rust_traits_examples::core::{EnergyLevel, HasEnergy};
+use rust_traits_examples::vehicles::car::EngineType;
+use rust_traits_examples::vehicles::{Car, EngineRunning, InMotion, Lifecycle, Parked};
+
+fn parked_car() -> Car<Parked> {
+    Car::parked(
+        "Beetle".to_string(),
+        "Volkswagen".to_string(),
+        2020,
+        EngineType::Gasoline {
+            cylinders: 4,
+            displacement: 1.6,
+        },
+    )
+}
+
+#[test]
+fn test_car_runs_through_the_whole_lifecycle() {
+    let car = parked_car();
+    assert_eq!(car.engine_state(), "Parked");
+
+    let mut car: Car<EngineRunning> = car.start().unwrap();
+    assert_eq!(car.engine_state(), "Engine Running");
+    car.set_energy(EnergyLevel::Hyperactive);
+    assert!(car.idle(10).contains("idles for 10 minutes"));
+
+    let mut car: Car<InMotion> = car.pull_away();
+    assert_eq!(car.engine_state(), "In Motion");
+    assert!(car.drive().is_ok());
+
+    let car: Car<EngineRunning> = car.stop();
+    let energy = car.energy();
+    let car: Car<Parked> = car.shut_down();
+    assert_eq!(car.engine_state(), "Parked");
+    assert_eq!(car.energy(), energy); // Fuel carries over between states
+}
+
+#[test]
+fn test_failed_start_hands_the_car_back() {
+    let mut car = parked_car();
+    car.set_energy(EnergyLevel::Collapsed);
+
+    let error = car.start().unwrap_err();
+    assert_eq!(error.to_string(), "Cannot start Beetle: fuel tank is empty");
+
+    let mut car = *error.vehicle;
+    car.set_energy(EnergyLevel::Normal);
+    assert_eq!(car.start().unwrap().engine_state(), "Engine Running");
+}